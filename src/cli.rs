@@ -0,0 +1,86 @@
+use std::env;
+use std::process;
+use macroquad::logging::error;
+use crate::model::state::{AssistLevel, FlightModelSetting};
+
+const HEADLESS_DEFAULT_TICKS: u64 = 60 * 60;
+
+#[derive(Clone, Debug, Default)]
+pub struct CliArgs {
+    pub headless_ticks: Option<u64>,
+    pub seed: Option<u64>,
+    pub record: Option<String>,
+    pub replay: Option<String>,
+    pub metrics_csv: Option<String>,
+    // headless only, the game takes them from the prefs
+    pub flight_model: Option<FlightModelSetting>,
+    pub assist: Option<AssistLevel>,
+    pub hot_reload: bool,
+}
+
+pub fn parse_args() -> CliArgs {
+    let mut result = CliArgs::default();
+    let mut args = env::args().skip(1).peekable();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--headless" => {
+                let ticks = match args.peek().map(|it| it.parse::<u64>()) {
+                    Some(Ok(ticks)) => {
+                        args.next();
+                        ticks
+                    }
+                    _ => HEADLESS_DEFAULT_TICKS,
+                };
+                result.headless_ticks = Some(ticks);
+            }
+            "--seed" => {
+                match args.next().map(|it| it.parse::<u64>()) {
+                    Some(Ok(seed)) => result.seed = Some(seed),
+                    _ => error!("--seed expects unsigned integer"),
+                }
+            }
+            "--record" => {
+                match args.next() {
+                    Some(path) => result.record = Some(path),
+                    None => error!("--record expects file name"),
+                }
+            }
+            "--replay" => {
+                match args.next() {
+                    Some(path) => result.replay = Some(path),
+                    None => error!("--replay expects file name"),
+                }
+            }
+            "--metrics-csv" => {
+                match args.next() {
+                    Some(path) => result.metrics_csv = Some(path),
+                    None => error!("--metrics-csv expects file name"),
+                }
+            }
+            "--flight-model" => {
+                match args.next().map(|it| it.parse::<FlightModelSetting>()) {
+                    Some(Ok(it)) => result.flight_model = Some(it),
+                    _ => error!("--flight-model expects per_plane, simulation or arcade"),
+                }
+            }
+            "--assist" => {
+                match args.next().map(|it| it.parse::<AssistLevel>()) {
+                    Some(Ok(it)) => result.assist = Some(it),
+                    _ => error!("--assist expects off, damping or full"),
+                }
+            }
+            "--hot-reload" => result.hot_reload = true,
+            _ => error!("unknown argument: {}", arg),
+        }
+    }
+    if result.headless_ticks.is_none() {
+        // in the game metrics are exported with F6, flight settings are chosen in the menu
+        for (flag, present) in [("--metrics-csv", result.metrics_csv.is_some()), ("--flight-model", result.flight_model.is_some()), ("--assist", result.assist.is_some())] {
+            if present {
+                error!("{} requires --headless", flag);
+                process::exit(2);
+            }
+        }
+    }
+    result
+}
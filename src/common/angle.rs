@@ -2,6 +2,7 @@ use std::f32::consts::PI;
 use std::fmt::{Display, Formatter, Pointer, UpperExp};
 use std::ops::{Add, AddAssign, Sub};
 use macroquad::prelude::{Mat2, Vec2};
+use crate::common::rng::Rng;
 use crate::common::unsorted::gen_range;
 
 #[derive(Copy, Clone, PartialOrd, PartialEq, Debug)]
@@ -13,6 +14,10 @@ impl Angle {
     pub fn random() -> Angle {
         gen_range(-PI..PI).as_radians()
     }
+
+    pub fn random_by(rng: &mut Rng) -> Angle {
+        rng.gen_range(-PI..PI).as_radians()
+    }
 }
 
 pub trait AsRadians {
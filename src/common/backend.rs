@@ -0,0 +1,26 @@
+use std::any::TypeId;
+use std::fmt::Debug;
+use macroquad::prelude::{Image, Texture2D};
+
+// everything resources need from the window: GL context for textures and shaders, audio device
+// for sounds. chosen once, when ResourceManager is created
+pub trait Backend: Debug {
+    // image is decoded by the caller, which also takes the size from it
+    fn load_texture(&self, image: &Image) -> Texture2D;
+
+    // resources of other types are never loaded, so they must not be read
+    fn can_load(&self, resource_type: TypeId) -> bool;
+}
+
+#[derive(Debug)]
+pub struct WindowBackend;
+
+impl Backend for WindowBackend {
+    fn load_texture(&self, image: &Image) -> Texture2D {
+        Texture2D::from_image(image)
+    }
+
+    fn can_load(&self, _resource_type: TypeId) -> bool {
+        true
+    }
+}
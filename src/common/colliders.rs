@@ -1,29 +1,209 @@
-// use std::marker::PhantomData;
-// use macroquad::prelude::Vec2;
-//
-// #[derive(Debug, Clone)]
-// pub enum Collider {
-//     Circle {
-//         center: Vec2,
-//         radius: f32,
-//     }
-// }
-//
-// pub struct ColliderHandle {}
-//
-// #[derive(Clone, Debug)]
-// pub struct CollisionField {
-//     items: Vec<ColliderBox>,
-//
-// }
-//
-// struct ColliderBox {
-//
-// }
-//
-// impl CollisionField {
-//     pub fn create_collider<T: Copy, F: Fn(T) -> Vec2>(&mut self, collider: Collider, key: T, get_pos: F) -> ColliderHandle {
-//
-//         todo!()
-//     }
-// }
\ No newline at end of file
+use std::collections::HashMap;
+use macroquad::prelude::Vec2;
+use crate::common::line_circle::Line;
+
+// Broadphase: uniform grid of circles, rebuilt every tick.
+// Location is wrapped along x, so columns are wrapped too, and every candidate comes with
+// an offset that moves it to the same side of the seam as the query.
+
+#[derive(Clone, Debug)]
+pub struct CollisionField<K> {
+    cell_size: f32,
+    width: f32,
+    cell_width: f32,
+    cols: i32,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    entries: Vec<Entry<K>>,
+}
+
+#[derive(Clone, Debug)]
+struct Entry<K> {
+    key: K,
+    center: Vec2,
+    radius: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Candidate<K> {
+    pub key: K,
+    // to be added to the candidate position before checking it against the query shape
+    pub offset: Vec2,
+}
+
+impl<K: Copy> CollisionField<K> {
+    pub fn new(cell_size: f32) -> Self {
+        CollisionField {
+            cell_size,
+            width: 0.0,
+            cell_width: cell_size,
+            cols: 1,
+            cells: HashMap::new(),
+            entries: vec![],
+        }
+    }
+
+    // width of the wrapped x axis (location width)
+    pub fn clear(&mut self, width: f32) {
+        // columns fit the width exactly, so the last one wraps to the first
+        self.cols = ((width / self.cell_size).round() as i32).max(1);
+        self.width = width;
+        self.cell_width = width / self.cols as f32;
+        // keep allocated cells, they are likely to be reused next tick
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+        self.entries.clear();
+    }
+
+    pub fn insert(&mut self, key: K, center: Vec2, radius: f32) {
+        let index = self.entries.len();
+        self.entries.push(Entry { key, center, radius });
+        let (cols, rows) = self.cell_range(center - Vec2::splat(radius), center + Vec2::splat(radius));
+        for col in cols {
+            for row in rows.clone() {
+                self.cells.entry((col.rem_euclid(self.cols), row)).or_default().push(index);
+            }
+        }
+    }
+
+    pub fn query_circle(&self, center: Vec2, radius: f32) -> Vec<Candidate<K>> {
+        let (cols, rows) = self.cell_range(center - Vec2::splat(radius), center + Vec2::splat(radius));
+        let mut found = vec![];
+        for col in cols {
+            for row in rows.clone() {
+                self.collect_cell(col, row, &mut found);
+            }
+        }
+        self.to_candidates(found, center)
+    }
+
+    // segment from p1 to p2, inflated by thickness
+    pub fn query_segment(&self, p1: Vec2, p2: Vec2, thickness: f32) -> Vec<Candidate<K>> {
+        let line = Line { p1, p2 };
+        let (cols, rows) = self.cell_range(p1.min(p2) - Vec2::splat(thickness), p1.max(p2) + Vec2::splat(thickness));
+        // long diagonal rays cover a lot of cells in the bounding box, but cross only few of them
+        let cell_reach = Vec2::new(self.cell_width, self.cell_size).length() * 0.5 + thickness;
+        let mut found = vec![];
+        for col in cols {
+            for row in rows.clone() {
+                let cell_center = Vec2::new((col as f32 + 0.5) * self.cell_width, (row as f32 + 0.5) * self.cell_size);
+                if line.distance_to_point(cell_center) <= cell_reach {
+                    self.collect_cell(col, row, &mut found);
+                }
+            }
+        }
+        self.to_candidates(found, (p1 + p2) * 0.5)
+    }
+
+    fn cell_range(&self, min: Vec2, max: Vec2) -> (std::ops::Range<i32>, std::ops::RangeInclusive<i32>) {
+        let col_min = (min.x / self.cell_width).floor() as i32;
+        let col_max = (max.x / self.cell_width).floor() as i32;
+        // wider than the location - every column once
+        let col_max = col_max.min(col_min + self.cols - 1);
+        let row_min = (min.y / self.cell_size).floor() as i32;
+        let row_max = (max.y / self.cell_size).floor() as i32;
+        (col_min..col_max + 1, row_min..=row_max)
+    }
+
+    fn collect_cell(&self, col: i32, row: i32, found: &mut Vec<usize>) {
+        if let Some(cell) = self.cells.get(&(col.rem_euclid(self.cols), row)) {
+            found.extend_from_slice(cell);
+        }
+    }
+
+    fn to_candidates(&self, mut found: Vec<usize>, query_center: Vec2) -> Vec<Candidate<K>> {
+        // insertion order, to not depend on the cells layout
+        found.sort_unstable();
+        found.dedup();
+        found.into_iter()
+            .map(|index| {
+                let entry = &self.entries[index];
+                let wraps = ((entry.center.x - query_center.x) / self.width).round();
+                Candidate { key: entry.key, offset: Vec2::new(-wraps * self.width, 0.0) }
+            })
+            .collect()
+    }
+}
+
+// Narrow shape of a static image: solid cells of the mask covered by circles,
+// each circumscribed around its cell. Returns (center, radius) in mask pixels.
+// A cell counts as solid if at least half of its pixels are.
+pub fn circles_from_mask(width: usize, height: usize, cell: usize, is_solid: impl Fn(usize, usize) -> bool) -> Vec<(Vec2, f32)> {
+    let mut circles = vec![];
+    for y0 in (0..height).step_by(cell.max(1)) {
+        for x0 in (0..width).step_by(cell.max(1)) {
+            let x1 = (x0 + cell).min(width);
+            let y1 = (y0 + cell).min(height);
+            let solid = (y0..y1)
+                .flat_map(|y| (x0..x1).map(move |x| (x, y)))
+                .filter(|(x, y)| is_solid(*x, *y))
+                .count();
+            if solid * 2 >= (x1 - x0) * (y1 - y0) {
+                let size = Vec2::new((x1 - x0) as f32, (y1 - y0) as f32);
+                circles.push((Vec2::new(x0 as f32, y0 as f32) + size * 0.5, size.length() * 0.5));
+            }
+        }
+    }
+    circles
+}
+
+// radius around origin that covers every circle, for a cheap early-out before the circles themselves
+pub fn bounding_radius(circles: &[(Vec2, f32)], origin: Vec2) -> f32 {
+    circles.iter().map(|(center, radius)| center.distance(origin) + radius).fold(0.0, f32::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+    use crate::common::colliders::{bounding_radius, Candidate, CollisionField, circles_from_mask};
+
+    fn field() -> CollisionField<u32> {
+        let mut field = CollisionField::new(100.0);
+        field.clear(1000.0);
+        field.insert(1, Vec2::new(150.0, 150.0), 10.0);
+        field.insert(2, Vec2::new(550.0, 150.0), 10.0);
+        field.insert(3, Vec2::new(990.0, 150.0), 10.0);
+        field
+    }
+
+    #[test]
+    fn circle_finds_only_nearby() {
+        let keys = field().query_circle(Vec2::new(140.0, 160.0), 20.0).iter().map(|it| it.key).collect::<Vec<_>>();
+        assert_eq!(vec![1], keys);
+    }
+
+    #[test]
+    fn circle_across_seam() {
+        let found = field().query_circle(Vec2::new(5.0, 150.0), 20.0);
+        assert_eq!(vec![Candidate { key: 3, offset: Vec2::new(-1000.0, 0.0) }], found);
+    }
+
+    #[test]
+    fn segment_finds_along_the_line() {
+        let keys = field().query_segment(Vec2::new(100.0, 150.0), Vec2::new(600.0, 150.0), 1.0).iter().map(|it| it.key).collect::<Vec<_>>();
+        assert_eq!(vec![1, 2], keys);
+    }
+
+    #[test]
+    fn segment_skips_far_cells_of_bounding_box() {
+        let mut field = CollisionField::new(100.0);
+        field.clear(1000.0);
+        field.insert(1, Vec2::new(850.0, 150.0), 10.0);
+        let found = field.query_segment(Vec2::new(50.0, 50.0), Vec2::new(950.0, 950.0), 1.0);
+        assert!(found.is_empty());
+    }
+
+    #[test]
+    fn mask_cells_become_circles() {
+        // solid top-left 2x2 and a single pixel in the bottom-right cell
+        let circles = circles_from_mask(4, 4, 2, |x, y| (x < 2 && y < 2) || (x, y) == (3, 3));
+        assert_eq!(vec![(Vec2::new(1.0, 1.0), 2.0_f32.sqrt())], circles);
+    }
+
+    #[test]
+    fn bounding_radius_covers_farthest_circle() {
+        let circles = vec![(Vec2::new(1.0, 0.0), 1.0), (Vec2::new(0.0, -4.0), 2.0)];
+        assert_eq!(6.0, bounding_radius(&circles, Vec2::ZERO));
+        assert_eq!(0.0, bounding_radius(&[], Vec2::ZERO));
+    }
+}
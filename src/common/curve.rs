@@ -5,6 +5,7 @@ use std::rc::Rc;
 use macroquad::math::clamp;
 
 use crate::common::angle::Angle;
+use crate::common::rng::Rng;
 use crate::common::unsorted::gen_range;
 
 type F<T> = dyn Fn(f32) -> T;
@@ -96,6 +97,10 @@ impl<T: Lerp<T>> Curve<T> {
     pub fn random(&self) -> T {
         self.lerp(gen_range(0.0..1.0))
     }
+
+    pub fn random_by(&self, rng: &mut Rng) -> T {
+        self.lerp(rng.gen_range(0.0..1.0))
+    }
 }
 
 impl<T: 'static> Mul<f32> for Curve<T>
@@ -0,0 +1,166 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::SystemTime;
+use macroquad::logging::{error, info};
+use macroquad::time::get_time;
+
+// Dev-only. When enabled, assets are read from the source tree instead of embedded copies,
+// and every file read is remembered together with the resource that was loading at the moment.
+// `poll_changed` finds resources whose files were changed since then, so their loaders can be re-run.
+// Loaded bytes are leaked, because definitions refer them as 'static (just like embedded ones).
+
+const POLL_INTERVAL_SEC: f64 = 0.5;
+
+pub trait Reload {
+    // identifies the reloaded thing, so repeated loads don't multiply dependents
+    fn key(&self) -> usize;
+    fn reload(&self);
+}
+
+#[derive(Default)]
+struct HotReload {
+    enabled: bool,
+    last_poll: f64,
+    loading: Vec<Rc<dyn Reload>>,
+    watched: HashMap<PathBuf, WatchedFile>,
+}
+
+struct WatchedFile {
+    modified: Option<SystemTime>,
+    dependents: HashMap<usize, Rc<dyn Reload>>,
+}
+
+thread_local! {
+    static HOT_RELOAD: RefCell<HotReload> = Default::default();
+}
+
+// path is relative to the source file, like in `include_bytes!`
+#[macro_export] macro_rules! asset_bytes {
+    ($path: literal) => {
+        $crate::common::hot_reload::asset_bytes(include_bytes!($path), concat!(env!("CARGO_MANIFEST_DIR"), "/", file!()), $path)
+    };
+}
+
+#[macro_export] macro_rules! asset_str {
+    ($path: literal) => {
+        $crate::common::hot_reload::asset_str(include_str!($path), concat!(env!("CARGO_MANIFEST_DIR"), "/", file!()), $path)
+    };
+}
+
+pub fn enable() {
+    info!("[HOT_RELOAD] enabled");
+    HOT_RELOAD.with(|it| it.borrow_mut().enabled = true);
+}
+
+pub fn is_enabled() -> bool {
+    HOT_RELOAD.with(|it| it.borrow().enabled)
+}
+
+pub struct LoadingScope(());
+
+impl Drop for LoadingScope {
+    fn drop(&mut self) {
+        HOT_RELOAD.with(|it| it.borrow_mut().loading.pop());
+    }
+}
+
+// files read while the scope is alive are attributed to the given reload
+pub fn enter_loading<F: FnOnce() -> Rc<dyn Reload>>(reload: F) -> Option<LoadingScope> {
+    HOT_RELOAD.with(|it| {
+        let mut hot_reload = it.borrow_mut();
+        if !hot_reload.enabled {
+            return None;
+        }
+        hot_reload.loading.push(reload());
+        Some(LoadingScope(()))
+    })
+}
+
+pub fn asset_bytes(embedded: &'static [u8], source_file: &str, path: &str) -> &'static [u8] {
+    match read_watched(source_file, path) {
+        Some(bytes) => Box::leak(bytes.into_boxed_slice()),
+        None => embedded,
+    }
+}
+
+pub fn asset_str(embedded: &'static str, source_file: &str, path: &str) -> &'static str {
+    match read_watched(source_file, path).map(String::from_utf8) {
+        Some(Ok(text)) => Box::leak(text.into_boxed_str()),
+        Some(Err(err)) => {
+            error!("[HOT_RELOAD] {} is not a text: {}", path, err);
+            embedded
+        }
+        None => embedded,
+    }
+}
+
+// for files that are not resources, like content definitions
+pub fn watch(path: &Path, reload: Rc<dyn Reload>) {
+    HOT_RELOAD.with(|it| {
+        let mut hot_reload = it.borrow_mut();
+        if hot_reload.enabled {
+            add_dependent(&mut hot_reload, path, reload);
+        }
+    });
+}
+
+// dependents of files changed since the last poll. empty if disabled or polled recently
+pub fn poll_changed() -> Vec<Rc<dyn Reload>> {
+    HOT_RELOAD.with(|it| {
+        let mut hot_reload = it.borrow_mut();
+        let now = get_time();
+        if !hot_reload.enabled || now - hot_reload.last_poll < POLL_INTERVAL_SEC {
+            return vec![];
+        }
+        hot_reload.last_poll = now;
+        let mut changed: HashMap<usize, Rc<dyn Reload>> = HashMap::new();
+        for (path, file) in hot_reload.watched.iter_mut() {
+            let modified = modified_time(path);
+            if modified != file.modified {
+                info!("[HOT_RELOAD] changed: {}", path.display());
+                file.modified = modified;
+                changed.extend(file.dependents.iter().map(|(key, it)| (*key, it.clone())));
+            }
+        }
+        changed.into_values().collect()
+    })
+}
+
+fn read_watched(source_file: &str, path: &str) -> Option<Vec<u8>> {
+    let full_path = Path::new(source_file).parent()?.join(path);
+    let enabled = HOT_RELOAD.with(|it| {
+        let mut hot_reload = it.borrow_mut();
+        if !hot_reload.enabled {
+            return false;
+        }
+        if let Some(reload) = hot_reload.loading.last().cloned() {
+            add_dependent(&mut hot_reload, &full_path, reload);
+        }
+        true
+    });
+    if !enabled {
+        return None;
+    }
+    match fs::read(&full_path) {
+        Ok(bytes) => Some(bytes),
+        Err(err) => {
+            error!("[HOT_RELOAD] failed to read {}, using embedded: {}", full_path.display(), err);
+            None
+        }
+    }
+}
+
+fn add_dependent(hot_reload: &mut HotReload, path: &Path, reload: Rc<dyn Reload>) {
+    hot_reload.watched
+        .entry(path.to_path_buf())
+        .or_insert_with(|| WatchedFile { modified: modified_time(path), dependents: Default::default() })
+        .dependents
+        .insert(reload.key(), reload);
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|it| it.modified()).ok()
+}
@@ -0,0 +1,45 @@
+use macroquad::prelude::Vec2;
+use crate::common::angle::{Angle, AsRadians};
+
+// state is simulated with fixed ticks, but drawn at arbitrary frame rate,
+// so things are drawn somewhere between previous and current tick.
+
+pub fn lerp_pos(prev: Vec2, current: Vec2, alpha: f32, loc_size: Vec2) -> Vec2 {
+    let delta = current - prev;
+    // wrapped over location edge during the tick. just jump, otherwise it will fly over the whole location
+    if delta.x.abs() > loc_size.x * 0.5 {
+        return current;
+    }
+    prev + delta * alpha
+}
+
+pub fn lerp_angle(prev: Angle, current: Angle, alpha: f32) -> Angle {
+    let delta = (current - prev).normalize();
+    (prev.to_rad() + delta.to_rad() * alpha).as_radians().normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+    use macroquad::prelude::Vec2;
+    use crate::common::angle::AsRadians;
+    use crate::common::interpolation::{lerp_angle, lerp_pos};
+
+    #[test]
+    fn pos_halfway() {
+        let pos = lerp_pos(Vec2::new(0.0, 0.0), Vec2::new(10.0, 20.0), 0.5, Vec2::new(1000.0, 1000.0));
+        assert_eq!(Vec2::new(5.0, 10.0), pos);
+    }
+
+    #[test]
+    fn pos_wrapped() {
+        let pos = lerp_pos(Vec2::new(995.0, 0.0), Vec2::new(5.0, 0.0), 0.5, Vec2::new(1000.0, 1000.0));
+        assert_eq!(Vec2::new(5.0, 0.0), pos);
+    }
+
+    #[test]
+    fn angle_shortest_arc() {
+        let angle = lerp_angle((PI - 0.1).as_radians(), (-PI + 0.1).as_radians(), 0.5);
+        assert!((angle.to_rad().abs() - PI).abs() < 0.001);
+    }
+}
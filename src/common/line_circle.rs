@@ -107,6 +107,17 @@ impl Line {
         intersections.sort_unstable_by(|a, b| a.x.partial_cmp(&b.x).unwrap());
         intersections
     }
+
+    // distance to the closest point of the segment
+    pub fn distance_to_point(&self, point: Vec2) -> f32 {
+        let delta = self.p2 - self.p1;
+        let length_sq = delta.length_squared();
+        if length_sq < EPS {
+            return self.p1.distance(point);
+        }
+        let t = ((point - self.p1).dot(delta) / length_sq).clamp(0.0, 1.0);
+        (self.p1 + delta * t).distance(point)
+    }
 }
 
 #[cfg(test)]
@@ -1,30 +1,276 @@
-use std::borrow::BorrowMut;
 use std::cell::{Cell, Ref, RefCell};
-use std::collections::HashMap;
-use std::fmt::Display;
-use std::rc::Rc;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Display, Write};
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write as IoWrite};
+use std::iter;
+use macroquad::logging::error;
 use macroquad::prelude::Vec2;
+use crate::common::angle::Angle;
+
+// 10 seconds of ticks
+pub const HISTORY_TICKS: usize = 600;
 
 #[derive(Default, Debug)]
 pub struct Metrics {
+    // values of the current tick, as displayed
     pub metrics: RefCell<HashMap<&'static str, String>>,
     pub metrics_order: RefCell<Vec<&'static str>>,
     pub enabled: Cell<bool>,
+    samples: RefCell<HashMap<&'static str, Vec<(&'static str, f32)>>>,
+    history: RefCell<MetricsHistory>,
+    // every tick goes there, while history keeps only the last HISTORY_TICKS
+    csv: RefCell<Option<BufWriter<File>>>,
+}
+
+// one row per tick with anything recorded. channels go in order of the first record
+#[derive(Default, Clone, Debug)]
+pub struct MetricsHistory {
+    pub ticks: VecDeque<u64>,
+    pub channels: Vec<MetricChannel>,
+}
+
+#[derive(Clone, Debug)]
+pub struct MetricChannel {
+    pub name: &'static str,
+    // empty for scalars
+    pub component: &'static str,
+    // NaN for ticks the channel wasn't recorded in
+    pub samples: VecDeque<f32>,
+}
+
+// one sample per component, so vectors are graphed and exported by axis
+pub trait MetricValue: Display {
+    fn samples(&self) -> Vec<(&'static str, f32)>;
+}
+
+impl MetricValue for f32 {
+    fn samples(&self) -> Vec<(&'static str, f32)> {
+        vec![("", *self)]
+    }
+}
+
+impl MetricValue for Vec2 {
+    fn samples(&self) -> Vec<(&'static str, f32)> {
+        vec![("x", self.x), ("y", self.y)]
+    }
+}
+
+impl MetricValue for Angle {
+    fn samples(&self) -> Vec<(&'static str, f32)> {
+        vec![("", self.to_rad())]
+    }
+}
+
+fn title(name: &str, component: &str) -> String {
+    if component.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", name, component)
+    }
+}
+
+impl MetricChannel {
+    pub fn title(&self) -> String {
+        title(self.name, self.component)
+    }
+
+    pub fn latest(&self) -> Option<f32> {
+        self.samples.back().copied().filter(|it| it.is_finite())
+    }
+
+    pub fn range(&self) -> Option<(f32, f32)> {
+        self.samples.iter()
+            .filter(|it| it.is_finite())
+            .fold(None, |acc, &it| match acc {
+                None => Some((it, it)),
+                Some((min, max)) => Some((min.min(it), max.max(it))),
+            })
+    }
 }
 
 impl Metrics {
     pub fn clear(&self) {
         self.metrics.borrow_mut().clear();
         self.metrics_order.borrow_mut().clear();
+        self.samples.borrow_mut().clear();
     }
 
     pub fn set_enabled(&self, value: bool) {
         self.enabled.set(value);
     }
 
-    pub fn record<T: Display>(&self, name: &'static str, value: T) {
-        if self.enabled.get() && self.metrics.borrow_mut().insert(name, value.to_string()).is_none() {
+    // the last value recorded during the tick wins
+    pub fn record<T: MetricValue>(&self, name: &'static str, value: T) {
+        if !self.enabled.get() {
+            return;
+        }
+        self.samples.borrow_mut().insert(name, value.samples());
+        if self.metrics.borrow_mut().insert(name, value.to_string()).is_none() {
             self.metrics_order.borrow_mut().push(name);
         }
     }
-}
\ No newline at end of file
+
+    // moves values recorded during the tick into history (and csv stream, if any)
+    pub fn end_tick(&self, tick: u64) {
+        let samples = self.samples.borrow();
+        if samples.is_empty() {
+            return;
+        }
+        let order = self.metrics_order.borrow();
+        let mut history = self.history.borrow_mut();
+        let history = &mut *history;
+        for name in order.iter() {
+            for (component, _) in samples.get(name).into_iter().flatten() {
+                if !history.channels.iter().any(|it| it.name == *name && it.component == *component) {
+                    history.channels.push(MetricChannel {
+                        name: *name,
+                        component: *component,
+                        samples: iter::repeat(f32::NAN).take(history.ticks.len()).collect(),
+                    });
+                }
+            }
+        }
+        history.ticks.push_back(tick);
+        for channel in &mut history.channels {
+            let value = samples.get(channel.name)
+                .and_then(|it| it.iter().find(|(component, _)| *component == channel.component))
+                .map_or(f32::NAN, |(_, value)| *value);
+            channel.samples.push_back(value);
+        }
+        if history.ticks.len() > HISTORY_TICKS {
+            history.ticks.pop_front();
+            for channel in &mut history.channels {
+                channel.samples.pop_front();
+            }
+        }
+        let mut csv = self.csv.borrow_mut();
+        if let Some(out) = csv.as_mut() {
+            let written: io::Result<()> = order.iter()
+                .flat_map(|name| samples.get(name).into_iter().flatten().map(move |it| (*name, it)))
+                .try_for_each(|(name, (component, value))| writeln!(out, "{},{},{}", tick, title(name, component), value));
+            if let Err(err) = written {
+                error!("[METRICS] csv stream stopped: {}", err);
+                *csv = None;
+            }
+        }
+    }
+
+    pub fn history(&self) -> Ref<MetricsHistory> {
+        self.history.borrow()
+    }
+
+    pub fn to_csv(&self) -> String {
+        let history = self.history.borrow();
+        let mut csv = String::new();
+        csv.push_str("tick");
+        for channel in &history.channels {
+            write!(csv, ",{}", channel.title()).unwrap();
+        }
+        csv.push('\n');
+        for (i, tick) in history.ticks.iter().enumerate() {
+            write!(csv, "{}", tick).unwrap();
+            for channel in &history.channels {
+                csv.push(',');
+                let value = channel.samples[i];
+                if value.is_finite() {
+                    write!(csv, "{}", value).unwrap();
+                }
+            }
+            csv.push('\n');
+        }
+        csv
+    }
+
+    pub fn export_csv(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_csv())
+            .map_err(|err| format!("failed to write {}: {}", path, err))
+    }
+
+    // unlike export_csv, writes all the ticks from now on, one row per value: tick,metric,value
+    pub fn stream_csv(&self, path: &str) -> Result<(), String> {
+        let mut out = File::create(path)
+            .map(BufWriter::new)
+            .map_err(|err| format!("failed to create {}: {}", path, err))?;
+        writeln!(out, "tick,metric,value")
+            .map_err(|err| format!("failed to write {}: {}", path, err))?;
+        *self.csv.borrow_mut() = Some(out);
+        Ok(())
+    }
+
+    pub fn finish_csv(&self) -> Result<(), String> {
+        match self.csv.borrow_mut().take() {
+            Some(mut out) => out.flush().map_err(|err| format!("failed to write metrics: {}", err)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+    use crate::common::metrics::{HISTORY_TICKS, Metrics};
+
+    fn tick(metrics: &Metrics, tick: u64, values: &[(&'static str, f32)]) {
+        metrics.clear();
+        metrics.set_enabled(true);
+        for (name, value) in values {
+            metrics.record(*name, *value);
+        }
+        metrics.end_tick(tick);
+    }
+
+    #[test]
+    fn late_channel_is_back_filled() {
+        let metrics = Metrics::default();
+        tick(&metrics, 0, &[("speed", 1.0)]);
+        tick(&metrics, 1, &[("speed", 2.0), ("hp", 10.0)]);
+        assert_eq!("tick,speed,hp\n0,1,\n1,2,10\n", metrics.to_csv());
+    }
+
+    #[test]
+    fn disabled_records_nothing() {
+        let metrics = Metrics::default();
+        metrics.record("dV/gravity", Vec2::new(0.0, 1.0));
+        metrics.end_tick(0);
+        assert!(metrics.history().ticks.is_empty());
+    }
+
+    #[test]
+    fn vectors_are_split_by_axis() {
+        let metrics = Metrics::default();
+        metrics.set_enabled(true);
+        metrics.record("velocity", Vec2::new(3.0, -4.0));
+        metrics.end_tick(0);
+        assert_eq!("tick,velocity.x,velocity.y\n0,3,-4\n", metrics.to_csv());
+    }
+
+    #[test]
+    fn stream_keeps_every_tick() {
+        let path = std::env::temp_dir().join("metrics_stream_keeps_every_tick.csv");
+        let path = path.to_str().unwrap();
+        let metrics = Metrics::default();
+        metrics.stream_csv(path).unwrap();
+        for i in 0..HISTORY_TICKS as u64 + 10 {
+            tick(&metrics, i, &[("speed", i as f32)]);
+        }
+        metrics.finish_csv().unwrap();
+        let csv = std::fs::read_to_string(path).unwrap();
+        assert_eq!(HISTORY_TICKS + 11, csv.lines().count());
+        assert_eq!(Some("0,speed,0"), csv.lines().nth(1));
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let metrics = Metrics::default();
+        for i in 0..HISTORY_TICKS as u64 + 10 {
+            tick(&metrics, i, &[("speed", i as f32)]);
+        }
+        let history = metrics.history();
+        assert_eq!(HISTORY_TICKS, history.ticks.len());
+        assert_eq!(Some(10), history.ticks.front().copied());
+        assert_eq!(Some((10.0, (HISTORY_TICKS + 9) as f32)), history.channels[0].range());
+    }
+}
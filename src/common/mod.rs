@@ -18,4 +18,9 @@ pub mod sound;
 pub mod line_circle;
 pub mod composite;
 pub mod composite_bounded;
-pub mod prefs;
\ No newline at end of file
+pub mod prefs;
+pub mod rng;
+pub mod interpolation;
+pub mod toroidal;
+pub mod hot_reload;
+pub mod backend;
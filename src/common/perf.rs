@@ -1,10 +1,12 @@
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
 use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
-use macroquad::logging::{info, trace};
+use macroquad::logging::{error, info, trace};
 
 struct Perf {
     prev: Instant,
@@ -92,3 +94,240 @@ pub fn perf_report() {
         }
     });
 }
+
+// Scoped profiler. Scopes nest, frames are delimited by perf_frame_end.
+// Breakdown is averaged over a window of frames to be readable on screen.
+
+const BREAKDOWN_WINDOW: usize = 30;
+
+#[derive(Clone, Debug)]
+pub struct ScopeRecord {
+    pub name: &'static str,
+    pub depth: usize,
+    // since profiler start
+    pub start_sec: f64,
+    pub duration_sec: f64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PerfBreakdown {
+    pub frame_ms: f32,
+    // scopes tree in depth-first order, values are per frame
+    pub rows: Vec<PerfRow>,
+}
+
+#[derive(Clone, Debug)]
+pub struct PerfRow {
+    pub name: &'static str,
+    pub depth: usize,
+    pub ms: f32,
+    pub calls: f32,
+}
+
+struct Profiler {
+    origin: Instant,
+    depth: usize,
+    frame_start: Instant,
+    frame: Vec<ScopeRecord>,
+    window: PerfBreakdown,
+    window_frames: usize,
+    breakdown: PerfBreakdown,
+    capture: Option<Capture>,
+}
+
+struct Capture {
+    path: String,
+    frames_left: usize,
+    frames: Vec<ScopeRecord>,
+    scopes: Vec<ScopeRecord>,
+}
+
+impl Profiler {
+    fn new() -> Self {
+        let now = Instant::now();
+        Profiler {
+            origin: now,
+            depth: 0,
+            frame_start: now,
+            frame: vec![],
+            window: Default::default(),
+            window_frames: 0,
+            breakdown: Default::default(),
+            capture: None,
+        }
+    }
+
+    fn end_frame(&mut self) -> Option<Capture> {
+        let now = Instant::now();
+        // scopes are recorded when they end, so parents go after children
+        self.frame.sort_by(|a, b| a.start_sec.partial_cmp(&b.start_sec)
+            .unwrap_or(Ordering::Equal)
+            .then(a.depth.cmp(&b.depth)));
+        let frame = ScopeRecord {
+            name: "frame",
+            depth: 0,
+            start_sec: (self.frame_start - self.origin).as_secs_f64(),
+            duration_sec: (now - self.frame_start).as_secs_f64(),
+        };
+
+        add_to_breakdown(&mut self.window, &frame, &self.frame);
+        self.window_frames += 1;
+        if self.window_frames >= BREAKDOWN_WINDOW {
+            let frames = self.window_frames as f32;
+            self.breakdown = PerfBreakdown {
+                frame_ms: self.window.frame_ms / frames,
+                rows: self.window.rows.iter()
+                    .map(|it| PerfRow { ms: it.ms / frames, calls: it.calls / frames, ..it.clone() })
+                    .collect(),
+            };
+            self.window = Default::default();
+            self.window_frames = 0;
+        }
+
+        let mut finished = None;
+        if let Some(capture) = &mut self.capture {
+            capture.frames.push(frame);
+            capture.scopes.append(&mut self.frame);
+            capture.frames_left -= 1;
+            if capture.frames_left == 0 {
+                finished = self.capture.take();
+            }
+        }
+        self.frame.clear();
+        self.frame_start = now;
+        finished
+    }
+}
+
+fn add_to_breakdown(breakdown: &mut PerfBreakdown, frame: &ScopeRecord, scopes: &[ScopeRecord]) {
+    breakdown.frame_ms += (frame.duration_sec * 1000.0) as f32;
+    // scopes seen for the first time are inserted after the previous one to keep the tree order
+    let mut cursor = 0;
+    for scope in scopes {
+        let position = breakdown.rows.iter().position(|it| it.name == scope.name && it.depth == scope.depth);
+        let index = match position {
+            Some(index) => index,
+            None => {
+                breakdown.rows.insert(cursor, PerfRow { name: scope.name, depth: scope.depth, ms: 0.0, calls: 0.0 });
+                cursor
+            }
+        };
+        let row = &mut breakdown.rows[index];
+        row.ms += (scope.duration_sec * 1000.0) as f32;
+        row.calls += 1.0;
+        cursor = index + 1;
+    }
+}
+
+// https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+fn to_chrome_trace(frames: &[ScopeRecord], scopes: &[ScopeRecord]) -> String {
+    let mut json = String::new();
+    json.push_str("{\"traceEvents\":[");
+    for (i, record) in frames.iter().chain(scopes.iter()).enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        write!(
+            json,
+            "\n{{\"name\":{:?},\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":0,\"tid\":0}}",
+            record.name,
+            record.start_sec * 1_000_000.0,
+            record.duration_sec * 1_000_000.0,
+        ).unwrap();
+    }
+    json.push_str("\n],\"displayTimeUnit\":\"ms\"}\n");
+    json
+}
+
+thread_local! {
+    static PROFILER: RefCell<Profiler> = RefCell::new(Profiler::new());
+}
+
+pub fn perf_scope<R>(name: &'static str, f: impl FnOnce() -> R) -> R {
+    let start = PROFILER.with(|profiler| {
+        profiler.borrow_mut().depth += 1;
+        Instant::now()
+    });
+    let result = f();
+    let end = Instant::now();
+    PROFILER.with(|profiler| {
+        let mut profiler = profiler.borrow_mut();
+        profiler.depth -= 1;
+        let record = ScopeRecord {
+            name,
+            depth: profiler.depth,
+            start_sec: (start - profiler.origin).as_secs_f64(),
+            duration_sec: (end - start).as_secs_f64(),
+        };
+        profiler.frame.push(record);
+    });
+    result
+}
+
+pub fn perf_frame_end() {
+    let finished = PROFILER.with(|profiler| profiler.borrow_mut().end_frame());
+    if let Some(capture) = finished {
+        let result = fs::write(&capture.path, to_chrome_trace(&capture.frames, &capture.scopes));
+        match result {
+            Ok(()) => info!("[PERF] {} frames captured to {}", capture.frames.len(), capture.path),
+            Err(err) => error!("[PERF] failed to write {}: {}", capture.path, err),
+        }
+    }
+}
+
+// trace of the next frames in Chrome trace format (chrome://tracing, ui.perfetto.dev)
+pub fn perf_capture(path: String, frames: usize) {
+    PROFILER.with(|profiler| {
+        let mut profiler = profiler.borrow_mut();
+        if profiler.capture.is_some() {
+            info!("[PERF] capture is already in progress");
+            return;
+        }
+        info!("[PERF] capturing {} frames", frames);
+        profiler.capture = Some(Capture { path, frames_left: frames.max(1), frames: vec![], scopes: vec![] });
+    });
+}
+
+pub fn perf_is_capturing() -> bool {
+    PROFILER.with(|profiler| profiler.borrow().capture.is_some())
+}
+
+pub fn perf_breakdown() -> PerfBreakdown {
+    PROFILER.with(|profiler| profiler.borrow().breakdown.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::common::perf::{add_to_breakdown, PerfBreakdown, ScopeRecord, to_chrome_trace};
+
+    fn scope(name: &'static str, depth: usize, start_sec: f64, duration_sec: f64) -> ScopeRecord {
+        ScopeRecord { name, depth, start_sec, duration_sec }
+    }
+
+    #[test]
+    fn breakdown_keeps_tree_order() {
+        let mut breakdown = PerfBreakdown::default();
+        let frame = scope("frame", 0, 0.0, 0.016);
+        add_to_breakdown(&mut breakdown, &frame, &[scope("update", 0, 0.0, 0.002), scope("draw", 0, 0.002, 0.010)]);
+        // tick didn't happen on the first frame
+        add_to_breakdown(&mut breakdown, &frame, &[
+            scope("update", 0, 0.0, 0.004),
+            scope("tick", 1, 0.0, 0.004),
+            scope("draw", 0, 0.004, 0.010),
+        ]);
+        let rows = breakdown.rows.iter().map(|it| (it.name, it.calls)).collect::<Vec<_>>();
+        assert_eq!(vec![("update", 2.0), ("tick", 1.0), ("draw", 2.0)], rows);
+    }
+
+    #[test]
+    fn chrome_trace_events() {
+        let trace = to_chrome_trace(&[scope("frame", 0, 0.5, 0.016)], &[scope("update", 0, 0.5, 0.002)]);
+        assert_eq!(
+            "{\"traceEvents\":[\n\
+            {\"name\":\"frame\",\"ph\":\"X\",\"ts\":500000.000,\"dur\":16000.000,\"pid\":0,\"tid\":0},\n\
+            {\"name\":\"update\",\"ph\":\"X\",\"ts\":500000.000,\"dur\":2000.000,\"pid\":0,\"tid\":0}\n\
+            ],\"displayTimeUnit\":\"ms\"}\n",
+            trace,
+        );
+    }
+}
@@ -5,10 +5,10 @@ use std::ops::{Div, Mul};
 
 use crate::common::angle::{Angle, AsRadians};
 use crate::common::metrics::Metrics;
-use crate::model::def::{Location, SlideStabilization, Stabilization, SteerStabilization};
+use crate::model::def::{Aerodynamics, ArcadeFlight, Location, SlideStabilization, Stabilization, SteerStabilization};
 use crate::model::state::{RotState, TransState};
 use crate::Plane;
-use crate::resources::constants::{DRAG, GRAVITY, NOMINAL_SPEED, SPEED_ABS_MAX};
+use crate::resources::constants::{GRAVITY, NOMINAL_SPEED, PLANE_THRUST_NOMINAL};
 use crate::resources::objects::objects;
 
 pub fn apply_rotation(body: &mut RotState, dt: f32) {
@@ -37,9 +37,9 @@ pub fn apply_gravity(plane: &mut TransState, metrics: &Metrics, dt: f32)  {
     plane.velocity += dv;
 }
 
-pub fn apply_drag(plane: &mut TransState, metrics: &Metrics, dt: f32, dt_total: f32) {
+pub fn apply_drag(plane: &mut TransState, metrics: &Metrics, drag: f32, dt: f32, dt_total: f32) {
     let speed = plane.velocity.length();
-    let drag = DRAG * speed.powi(2) * dt;
+    let drag = drag * speed.powi(2) * dt;
     let dv = if drag > speed {
         -plane.velocity / dt_total * dt
     } else {
@@ -49,17 +49,36 @@ pub fn apply_drag(plane: &mut TransState, metrics: &Metrics, dt: f32, dt_total:
     plane.velocity += dv;
 }
 
-pub fn apply_thrust(metrics: &Metrics, dt: f32, acceleration: f32, rot: &RotState, pos: &mut TransState) {
+pub fn apply_thrust(metrics: &Metrics, dt: f32, acceleration: f32, max_speed: f32, rot: &RotState, pos: &mut TransState) {
     let thrust_dir = rot.angle.to_vec2_norm();
     let thrust_amount = acceleration * dt;
     let dv = thrust_dir * thrust_amount;
     metrics.record("dV/thrust", dv);
     pos.velocity += dv;
-    if pos.velocity.length() > SPEED_ABS_MAX {
-        pos.velocity = pos.velocity.normalize() * SPEED_ABS_MAX;
+    if pos.velocity.length() > max_speed {
+        pos.velocity = pos.velocity.normalize() * max_speed;
     }
 }
 
+// perpendicular to the movement, to the side the nose points
+pub fn apply_lift(metrics: &Metrics, dt: f32, trans: &mut TransState, rot: &RotState, def: &Aerodynamics) {
+    let dv = match trans.velocity.try_normalize() {
+        Some(move_dir) => {
+            let attack_angle = move_dir.angle_between(rot.angle.to_vec2_norm());
+            if attack_angle.abs() < def.stall_angle {
+                let by_attack = def.lift_by_attack.lerp(attack_angle.abs() / def.stall_angle);
+                let by_speed = (trans.velocity.length() / NOMINAL_SPEED).powi(2);
+                move_dir.perp() * attack_angle.signum() * def.lift * by_speed * by_attack * dt
+            } else {
+                Vec2::ZERO
+            }
+        }
+        None => Vec2::ZERO,
+    };
+    metrics.record("dV/lift", dv);
+    trans.velocity += dv;
+}
+
 pub fn apply_slide(metrics: &Metrics, dt: f32, dt_total: f32, trans: &mut TransState, rot: &RotState, def: &SlideStabilization) {
     let dv = if let Some(move_dir) = trans.velocity.try_normalize() {
         let plane_dir = rot.angle.to_vec2_norm();
@@ -82,6 +101,27 @@ pub fn apply_slide(metrics: &Metrics, dt: f32, dt_total: f32, trans: &mut TransS
     trans.velocity += dv;
 }
 
+// arcade replacement of apply_gravity: plane holds altitude unless throttle is low
+pub fn apply_arcade_gravity(trans: &mut TransState, metrics: &Metrics, dt: f32, acceleration: f32, def: &ArcadeFlight) {
+    let by_thrust = def.gravity_by_thrust.lerp(acceleration / PLANE_THRUST_NOMINAL);
+    let dv = Vec2::new(0.0, GRAVITY * by_thrust * dt);
+    metrics.record("dV/gravity", dv);
+    trans.velocity += dv;
+}
+
+// arcade replacement of apply_slide: velocity turns to the nose keeping the speed
+pub fn apply_nose_follow(metrics: &Metrics, dt: f32, trans: &mut TransState, rot: &RotState, def: &ArcadeFlight) {
+    let speed = trans.velocity.length();
+    let follow = if def.inertia_sec > 0.0 { 1.0 - (-dt / def.inertia_sec).exp() } else { 1.0 };
+    let target = rot.angle.to_vec2_norm() * speed;
+    let velocity = (trans.velocity + (target - trans.velocity) * follow)
+        .try_normalize()
+        .map(|it| it * speed)
+        .unwrap_or(target);
+    metrics.record("dV/follow", velocity - trans.velocity);
+    trans.velocity = velocity;
+}
+
 pub fn apply_steering(body: &mut TransState, rot: &mut RotState, def: &SteerStabilization, desired_rot: Angle, rec: &Metrics, dt: f32) {
     let by_speed = def.steering_by_speed.lerp(body.velocity.length() / NOMINAL_SPEED);
     let by_attack = def.steering_by_attack.lerp(body.velocity.angle_between(rot.angle.to_vec2_norm()).abs() / PI);
@@ -106,3 +146,102 @@ pub fn apply_steering(body: &mut TransState, rot: &mut RotState, def: &SteerStab
 
     rot.ang_velocity_rad += limited_accel * dt;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+    use macroquad::prelude::Vec2;
+    use crate::common::angle::AsRadians;
+    use crate::common::curve::Curve;
+    use crate::common::metrics::Metrics;
+    use crate::common::physics::{apply_arcade_gravity, apply_drag, apply_lift, apply_nose_follow, apply_thrust};
+    use crate::model::def::{Aerodynamics, ArcadeFlight};
+    use crate::model::state::{RotState, TransState};
+    use crate::resources::constants::{DRAG, GRAVITY, NOMINAL_SPEED, PLANE_THRUST_NOMINAL};
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn moving_right() -> TransState {
+        TransState { pos: Vec2::ZERO, velocity: Vec2::new(NOMINAL_SPEED, 0.0) }
+    }
+
+    fn nose_at(angle_rad: f32) -> RotState {
+        RotState { angle: angle_rad.as_radians(), ang_velocity_rad: 0.0 }
+    }
+
+    fn arcade(inertia_sec: f32) -> ArcadeFlight {
+        ArcadeFlight {
+            inertia_sec,
+            gravity_by_thrust: Curve::new([1.0, 0.0]),
+        }
+    }
+
+    fn aerodynamics(stall_angle: f32) -> Aerodynamics {
+        Aerodynamics {
+            drag: DRAG,
+            lift: GRAVITY,
+            lift_by_attack: Curve::new([1.0]),
+            stall_angle,
+            max_speed: NOMINAL_SPEED,
+        }
+    }
+
+    #[test]
+    fn nose_follow_keeps_speed() {
+        let mut trans = moving_right();
+        apply_nose_follow(&Metrics::default(), DT, &mut trans, &nose_at(PI * 0.5), &arcade(0.3));
+        assert!(trans.velocity.y > 0.0);
+        assert!((trans.velocity.length() - NOMINAL_SPEED).abs() < 0.01);
+    }
+
+    #[test]
+    fn nose_follow_without_inertia_is_instant() {
+        let mut trans = moving_right();
+        apply_nose_follow(&Metrics::default(), DT, &mut trans, &nose_at(PI * 0.5), &arcade(0.0));
+        assert!(trans.velocity.x.abs() < 0.01);
+        assert!((trans.velocity.y - NOMINAL_SPEED).abs() < 0.01);
+    }
+
+    #[test]
+    fn arcade_gravity_fades_with_thrust() {
+        let mut idle = moving_right();
+        let mut full = moving_right();
+        apply_arcade_gravity(&mut idle, &Metrics::default(), DT, 0.0, &arcade(0.3));
+        apply_arcade_gravity(&mut full, &Metrics::default(), DT, PLANE_THRUST_NOMINAL, &arcade(0.3));
+        assert!((idle.velocity.y - GRAVITY * DT).abs() < 0.001);
+        assert_eq!(0.0, full.velocity.y);
+    }
+
+    #[test]
+    fn drag_follows_airframe_coefficient() {
+        let mut light = moving_right();
+        let mut heavy = moving_right();
+        apply_drag(&mut light, &Metrics::default(), DRAG, DT, DT);
+        apply_drag(&mut heavy, &Metrics::default(), DRAG * 2.0, DT, DT);
+        let light_loss = NOMINAL_SPEED - light.velocity.x;
+        let heavy_loss = NOMINAL_SPEED - heavy.velocity.x;
+        assert!(light_loss > 0.0);
+        assert!((heavy_loss - light_loss * 2.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn thrust_is_capped_by_airframe_max_speed() {
+        let mut trans = moving_right();
+        apply_thrust(&Metrics::default(), DT, NOMINAL_SPEED * 100.0, NOMINAL_SPEED * 1.5, &nose_at(0.0), &mut trans);
+        assert!((trans.velocity.length() - NOMINAL_SPEED * 1.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn lift_turns_velocity_to_the_nose() {
+        let mut trans = moving_right();
+        apply_lift(&Metrics::default(), DT, &mut trans, &nose_at(0.1), &aerodynamics(PI / 12.0));
+        assert!(trans.velocity.y > 0.0);
+    }
+
+    #[test]
+    fn no_lift_beyond_stall_angle() {
+        let mut trans = moving_right();
+        apply_lift(&Metrics::default(), DT, &mut trans, &nose_at(0.1), &aerodynamics(PI / 36.0));
+        assert_eq!(Vec2::new(NOMINAL_SPEED, 0.0), trans.velocity);
+    }
+}
@@ -1,24 +1,61 @@
-use std::collections::hash_map::{Iter, IterMut};
-use std::collections::HashMap;
 use std::hash::Hash;
 use crate::common::contract::{Get, GetMut, Insert, InsertSimple};
 
+// Slot map: removed slots are reused, but with the next generation, so ids of removed items
+// never point to the newer ones.
 #[derive(Clone, Debug)]
 pub struct Pool<K: PoolKey, V> {
-    items: HashMap<K, V>,
-    next_id: K,
+    // in insertion order to keep iteration order (and so random numbers consumption) the same for the same seed
+    items: Vec<(K, V)>,
+    slots: Vec<SlotState>,
+    free: Vec<u32>,
+}
+
+#[derive(Clone, Debug)]
+struct SlotState {
+    generation: u32,
+    // position in items
+    item: Option<usize>,
+}
+
+#[derive(Copy, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Debug)]
+pub struct Slot {
+    index: u32,
+    generation: u32,
+}
+
+impl Slot {
+    // for ids written into saves and replays. first generation goes as 1, 2, 3...
+    pub fn to_raw(self) -> i64 {
+        ((self.generation as i64) << 32) | (self.index as i64 + 1)
+    }
+
+    pub fn from_raw(raw: i64) -> Slot {
+        Slot {
+            index: ((raw & 0xFFFF_FFFF) - 1) as u32,
+            generation: (raw >> 32) as u32,
+        }
+    }
 }
 
 pub trait PoolKey: Hash + PartialEq + Eq + Copy {
-    fn initial() -> Self;
-    fn next(&self) -> Self;
+    fn from_slot(slot: Slot) -> Self;
+    fn slot(&self) -> Slot;
 }
 
 impl<K: PoolKey, V> Insert<K, V> for Pool<K, V> {
     fn insert(&mut self, value: V) -> K {
-        let key = self.next_id;
-        self.next_id = key.next();
-        self.items.insert(key, value);
+        let index = match self.free.pop() {
+            Some(index) => index,
+            None => {
+                self.slots.push(SlotState { generation: 0, item: None });
+                (self.slots.len() - 1) as u32
+            }
+        };
+        let slot = &mut self.slots[index as usize];
+        slot.item = Some(self.items.len());
+        let key = K::from_slot(Slot { index, generation: slot.generation });
+        self.items.push((key, value));
         key
     }
 }
@@ -32,39 +69,64 @@ impl<K: PoolKey, V> InsertSimple<V> for Pool<K, V> {
 impl<K: PoolKey, V> Pool<K, V> {
     pub fn new() -> Self {
         Self {
-            items: HashMap::new(),
-            next_id: K::initial(),
+            items: Vec::new(),
+            slots: Vec::new(),
+            free: Vec::new(),
         }
     }
 
     pub fn remove(&mut self, id: K) -> Option<V> {
-        self.items.remove(&id)
+        let position = self.position(&id)?;
+        let slot = &mut self.slots[id.slot().index as usize];
+        slot.item = None;
+        slot.generation = slot.generation.wrapping_add(1);
+        self.free.push(id.slot().index);
+        // shifting is fine for pools of this size, and keeps the order
+        let (_, value) = self.items.remove(position);
+        for (key, _) in &self.items[position..] {
+            if let Some(item) = &mut self.slots[key.slot().index as usize].item {
+                *item -= 1;
+            }
+        }
+        Some(value)
     }
 
-    pub fn iter_mut(&mut self) -> IterMut<'_, K, V> {
-        self.items.iter_mut()
+    // false for ids of removed items, even if their slot is occupied again
+    pub fn contains(&self, id: &K) -> bool {
+        self.position(id).is_some()
     }
 
-    pub fn iter(&self) -> Iter<'_, K, V> {
-        self.items.iter()
+    pub fn iter_mut(&mut self) -> impl Iterator<Item=(&K, &mut V)> {
+        self.items.iter_mut().map(|(k, v)| (&*k, v))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item=(&K, &V)> {
+        self.items.iter().map(|(k, v)| (k, v))
     }
 
     pub fn len(&self) -> usize {
         self.items.len()
     }
+
+    fn position(&self, id: &K) -> Option<usize> {
+        let slot = id.slot();
+        self.slots.get(slot.index as usize)
+            .filter(|it| it.generation == slot.generation)
+            .and_then(|it| it.item)
+    }
 }
 
 impl <K: PoolKey, V> Get<K,V> for Pool<K,V> {
     #[inline]
     fn get(&self, k: &K) -> Option<&V> {
-        self.items.get(k)
+        self.position(k).map(|it| &self.items[it].1)
     }
 }
 
 impl <K: PoolKey, V> GetMut<K,V> for Pool<K,V> {
     #[inline]
     fn get_mut(&mut self, k: &K) -> Option<&mut V> {
-        self.items.get_mut(k)
+        self.position(k).map(|it| &mut self.items[it].1)
     }
 }
 
@@ -77,3 +139,61 @@ impl <K: PoolKey, V, T: Into<Vec<V>>> From<T> for Pool<K, V> {
         pool
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::contract::{Get, Insert};
+    use crate::common::pool::{Pool, PoolKey, Slot};
+
+    #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
+    struct TestId(Slot);
+
+    impl PoolKey for TestId {
+        fn from_slot(slot: Slot) -> Self { TestId(slot) }
+        fn slot(&self) -> Slot { self.0 }
+    }
+
+    #[test]
+    fn insertion_order_survives_removal() {
+        let mut pool: Pool<TestId, &str> = Pool::new();
+        let a = pool.insert("a");
+        pool.insert("b");
+        pool.insert("c");
+        pool.remove(a);
+        pool.insert("d");
+        let values: Vec<&str> = pool.iter().map(|(_, it)| *it).collect();
+        assert_eq!(vec!["b", "c", "d"], values);
+    }
+
+    #[test]
+    fn stale_id_misses_reused_slot() {
+        let mut pool: Pool<TestId, &str> = Pool::new();
+        let a = pool.insert("a");
+        pool.remove(a);
+        let b = pool.insert("b");
+        assert_eq!(a.0.index, b.0.index);
+        assert_eq!(None, pool.get(&a));
+        assert!(!pool.contains(&a));
+        assert_eq!(Some(&"b"), pool.get(&b));
+        assert_eq!(None, pool.remove(a));
+    }
+
+    #[test]
+    fn lookup_after_shift() {
+        let mut pool: Pool<TestId, &str> = Pool::new();
+        let a = pool.insert("a");
+        let b = pool.insert("b");
+        let c = pool.insert("c");
+        pool.remove(a);
+        assert_eq!(Some(&"b"), pool.get(&b));
+        assert_eq!(Some(&"c"), pool.get(&c));
+    }
+
+    #[test]
+    fn raw_ids() {
+        let first = Slot { index: 0, generation: 0 };
+        assert_eq!(1, first.to_raw());
+        let reused = Slot { index: 4, generation: 3 };
+        assert_eq!(reused, Slot::from_raw(reused.to_raw()));
+    }
+}
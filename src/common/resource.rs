@@ -4,6 +4,7 @@ use std::cell::RefCell;
 use std::cell::Cell;
 use std::cell::UnsafeCell;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::collections::VecDeque;
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -15,6 +16,9 @@ use std::rc::Weak;
 use futures::future::LocalBoxFuture;
 use futures::FutureExt;
 use macroquad::rand::rand;
+use crate::common::backend::Backend;
+use crate::common::hot_reload;
+use crate::common::hot_reload::Reload;
 use crate::common::perf::perf_task;
 use crate::error;
 
@@ -29,12 +33,15 @@ pub struct ResourceManager {
 
 #[derive(Debug)]
 struct ResourceManagerBox {
+    backend: Box<dyn Backend>,
     on_progress_change: fn(i32, i32) -> LocalBoxFuture<'static, ()>,
     completed: Cell<i32>,
     pending: Cell<i32>,
     cache: RefCell<HashMap<TypeId, RawTypeManager>>,
     tasks: RefCell<VecDeque<Box<dyn LoadingTask>>>,
     seal: UnsafeCell<Cell<bool>>,
+    // detached, but referred by id. weak, so replaced ones (by hot reload) don't count as duplicates
+    named: RefCell<Vec<(TypeId, &'static str, Weak<dyn Any>)>>,
 }
 
 
@@ -48,15 +55,28 @@ enum GenericResourceLoad<T> {
     Async(ResourceLoadAsync<T>),
 }
 
+impl<T> Clone for GenericResourceLoad<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for GenericResourceLoad<T> {}
+
 #[derive(Debug)]
 struct SpecializedLoadingTask<T> {
     data: Weak<ResourceBox<T>>,
     loader: GenericResourceLoad<T>,
 }
 
-impl<T: Debug> LoadingTask for SpecializedLoadingTask<T> {
+impl<T: 'static + Debug> LoadingTask for SpecializedLoadingTask<T> {
     fn load(&self, rm: ResourceManager) -> LocalBoxFuture<()> {
         async move {
+            let _scope = hot_reload::enter_loading(|| Rc::new(ResourceReload {
+                rm: Rc::downgrade(&rm.inner),
+                data: self.data.clone(),
+                loader: self.loader,
+            }));
             let value = match self.loader {
                 GenericResourceLoad::Plain(f) => {
                     async {
@@ -74,6 +94,29 @@ impl<T: Debug> LoadingTask for SpecializedLoadingTask<T> {
     }
 }
 
+// re-runs the loader of already loaded resource. the value is replaced by the next `poll_tasks`
+struct ResourceReload<T> {
+    rm: Weak<ResourceManagerBox>,
+    data: Weak<ResourceBox<T>>,
+    loader: GenericResourceLoad<T>,
+}
+
+impl<T: 'static + Debug> Reload for ResourceReload<T> {
+    fn key(&self) -> usize {
+        self.data.as_ptr() as usize
+    }
+
+    fn reload(&self) {
+        if let Some(rm) = self.rm.upgrade() {
+            rm.pending.set(rm.pending.get() + 1);
+            rm.tasks.borrow_mut().push_back(Box::new(SpecializedLoadingTask {
+                data: self.data.clone(),
+                loader: self.loader,
+            }));
+        }
+    }
+}
+
 pub trait ResourceGet<T> {
     fn get(&self, rm: &ResourceManager) -> Resource<T>;
 }
@@ -90,6 +133,29 @@ impl<T: 'static + Debug> ResourceGet<T> for ResourceLoad<T> {
     }
 }
 
+pub trait ResourceRegister<T> {
+    fn register(&self, rm: &ResourceManager, id: &str);
+}
+
+impl<T: 'static + Debug> ResourceRegister<T> for ResourceLoadAsync<T> {
+    fn register(&self, rm: &ResourceManager, id: &str) {
+        rm.register_loader(id, GenericResourceLoad::Async(*self), ResourceKey::Async(self))
+    }
+}
+
+impl<T: 'static + Debug> ResourceRegister<T> for ResourceLoad<T> {
+    fn register(&self, rm: &ResourceManager, id: &str) {
+        rm.register_loader(id, GenericResourceLoad::Plain(*self), ResourceKey::Plain(self))
+    }
+}
+
+// registers each constant under its own name, so ids are as stable as the names in code
+#[macro_export] macro_rules! register_resources {
+    ($rm: expr, $($res: ident),* $(,)?) => {
+        $($crate::common::resource::ResourceRegister::register(&$res, $rm, stringify!($res));)*
+    };
+}
+
 pub trait ResourceManagerRc {
     fn update_progress(&self) -> LocalBoxFuture<'static, ()>;
     fn poll_tasks(&self) -> LocalBoxFuture<'static, ()>;
@@ -101,23 +167,131 @@ impl ResourceManager {
         if rm.completed.get() != rm.pending.get() {
             error!("WARNING: completed != pending ({} != {})", rm.completed.get(), rm.pending.get());
         }
+        let mut named = HashSet::new();
+        for (type_id, id, value) in rm.named.borrow().iter() {
+            if value.strong_count() > 0 && !named.insert((*type_id, *id)) {
+                error!("WARNING: resource id defined twice: {}", id);
+            }
+        }
     }
 }
 
 impl ResourceManager {
-    pub fn new(on_progress_change: fn(i32, i32) -> LocalBoxFuture<'static, ()>) -> ResourceManager {
+    pub fn new(backend: Box<dyn Backend>, on_progress_change: fn(i32, i32) -> LocalBoxFuture<'static, ()>) -> ResourceManager {
         ResourceManager {
             inner: Rc::new(ResourceManagerBox {
+                backend,
                 on_progress_change,
                 completed: Default::default(),
                 pending: Default::default(),
                 cache: Default::default(),
                 tasks: Default::default(),
                 seal: UnsafeCell::new(Cell::new(false)),
+                named: Default::default(),
             })
         }
     }
 
+    pub fn backend(&self) -> &dyn Backend {
+        self.inner.backend.as_ref()
+    }
+
+    // call only at the root of main loop (see the comment in `ResourceBox::set_value`)
+    pub fn reload_changed(&self) -> LocalBoxFuture<'static, ()> {
+        let rm = self.clone();
+        async move {
+            if !hot_reload::is_enabled() {
+                return;
+            }
+            let changed = hot_reload::poll_changed();
+            if changed.is_empty() {
+                return;
+            }
+            // content definitions are replaced right away, so unsealed for a while
+            let seal = unsafe { &*rm.inner.seal.get() };
+            seal.set(false);
+            // reloading reads files again, so it's done outside of hot_reload borrow
+            for reload in changed.iter() {
+                reload.reload();
+            }
+            seal.set(true);
+            // happens in the middle of the game, so no loading screen
+            rm.poll_tasks_silent().await;
+        }.boxed_local()
+    }
+
+    pub fn get_by_id<T: 'static + Debug>(&self, id: &str) -> Option<Resource<T>> {
+        let (key, loader) = {
+            let mut by_type = self.inner.cache.borrow_mut();
+            let by_ptr = by_type.get_mut(&TypeId::of::<T>())?.specialize::<T>();
+            if let Some(value) = by_ptr.defined.get(id) {
+                return Some(Resource(ResourceImpl::Managed(value.clone())));
+            }
+            *by_ptr.by_id.get(id)?
+        };
+        let load_synchronously = matches!(loader, GenericResourceLoad::Plain(_));
+        Some(self.allocate_res(loader, key, load_synchronously))
+    }
+
+    // replaces the value of resource with given id (everyone who already holds it sees the new value)
+    // or adds a new resource if the id is unknown. works only until resources are sealed.
+    pub fn define<T: 'static + Debug>(&self, id: &str, value: T) -> Resource<T> {
+        let res_box = {
+            let mut by_type = self.inner.cache.borrow_mut();
+            let by_ptr = by_type
+                .entry(TypeId::of::<T>())
+                .or_insert_with(|| SpecializedTypeManager::<T>::new().despecialize())
+                .specialize::<T>();
+            let key = by_ptr.by_id.get(id).map(|(key, _)| *key);
+            let existing = by_ptr.defined.get(id).cloned()
+                .or_else(|| key.and_then(|key| by_ptr.values.get(&key)).and_then(|it| it.upgrade()));
+            let res_box = existing.unwrap_or_else(|| {
+                let res_box = Rc::new(ResourceBox::new(SealPtr { sealed: self.inner.seal.get() }));
+                *res_box.id.borrow_mut() = Some(id.to_owned());
+                if let Some(key) = key {
+                    by_ptr.values.insert(key, Rc::downgrade(&res_box));
+                }
+                res_box
+            });
+            by_ptr.defined.insert(id.to_owned(), res_box.clone());
+            res_box
+        };
+        res_box.set_value(value);
+        Resource(ResourceImpl::Managed(res_box))
+    }
+
+    // detached resource, that still can be referred by id (in saves). unlike `define`, the value
+    // is not kept by ResourceManager, so ids are only checked for uniqueness (see perform_debug_checks)
+    pub fn named<T: 'static>(&self, id: &'static str, value: T) -> Resource<T> {
+        {
+            let mut by_type = self.inner.cache.borrow_mut();
+            if let Some(by_ptr) = by_type.get_mut(&TypeId::of::<T>()) {
+                if by_ptr.specialize::<T>().by_id.contains_key(id) {
+                    error!("WARNING: resource id is already registered: {}", id);
+                }
+            }
+        }
+        let value = Rc::new(value);
+        let weak: Weak<dyn Any> = Rc::downgrade(&value);
+        self.inner.named.borrow_mut().push((TypeId::of::<T>(), id, weak));
+        Resource(ResourceImpl::Detached(Uid::new(), Some(id), value))
+    }
+
+    fn register_loader<T: 'static + Debug>(&self, id: &str, loader: GenericResourceLoad<T>, key: ResourceKey<T>) {
+        let mut by_type = self.inner.cache.borrow_mut();
+        let by_ptr = by_type
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| SpecializedTypeManager::<T>::new().despecialize())
+            .specialize::<T>();
+        if by_ptr.by_id.insert(id.to_owned(), (key, loader)).is_some() {
+            error!("WARNING: resource id registered twice: {}", id);
+        }
+        by_ptr.ids.insert(key, id.to_owned());
+        if let Some(value) = by_ptr.values.get(&key).and_then(|it| it.upgrade()) {
+            *value.id.borrow_mut() = Some(id.to_owned());
+        }
+    }
+
     fn allocate_res<T: 'static + Debug>(&self, loader: GenericResourceLoad<T>, key: ResourceKey<T>, load_synchronously: bool) -> Resource<T> {
         let res_box = Rc::new(ResourceBox::new(SealPtr { sealed: self.inner.seal.get() }));
         {
@@ -134,8 +308,18 @@ impl ResourceManager {
                 }
             }
             by_ptr.values.insert(key, Rc::downgrade(&res_box));
+            *res_box.id.borrow_mut() = by_ptr.ids.get(&key).cloned();
+        }
+        if !self.inner.backend.can_load(TypeId::of::<T>()) {
+            // stays unloaded, see Backend::can_load
+            return Resource(ResourceImpl::Managed(res_box));
         }
         if load_synchronously {
+            let _scope = hot_reload::enter_loading(|| Rc::new(ResourceReload {
+                rm: Rc::downgrade(&self.inner),
+                data: Rc::downgrade(&res_box),
+                loader,
+            }));
             let value = match loader {
                 GenericResourceLoad::Plain(f) => f(self.clone()),
                 GenericResourceLoad::Async(_) => panic!("cannot load synchronously async resource")
@@ -170,22 +354,36 @@ impl ResourceManagerRc for ResourceManager {
     }
 
     fn poll_tasks(&self) -> LocalBoxFuture<'static, ()> {
-        let rm = self.clone();
-        async move {
-            let seal = unsafe { &*rm.inner.seal.get() };
-            seal.set(false);
-            loop {
-                let task = rm.inner.tasks.borrow_mut().pop_front();
-                if let Some(task) = task {
-                    task.load(rm.clone()).await;
+        poll_tasks(self.clone(), true)
+    }
+}
+
+impl ResourceManager {
+    // same as `poll_tasks`, but without `on_progress_change`, which draws the loading screen
+    pub fn poll_tasks_silent(&self) -> LocalBoxFuture<'static, ()> {
+        poll_tasks(self.clone(), false)
+    }
+}
+
+fn poll_tasks(rm: ResourceManager, report_progress: bool) -> LocalBoxFuture<'static, ()> {
+    async move {
+        let seal = unsafe { &*rm.inner.seal.get() };
+        seal.set(false);
+        loop {
+            let task = rm.inner.tasks.borrow_mut().pop_front();
+            if let Some(task) = task {
+                task.load(rm.clone()).await;
+                if report_progress {
                     rm.update_progress().await;
                 } else {
-                    break;
+                    rm.inner.completed.set(rm.inner.completed.get() + 1);
                 }
+            } else {
+                break;
             }
-            seal.set(true);
-        }.boxed_local()
-    }
+        }
+        seal.set(true);
+    }.boxed_local()
 }
 
 #[derive(Debug)]
@@ -199,12 +397,19 @@ impl RawTypeManager {
 
 struct SpecializedTypeManager<T> {
     values: HashMap<ResourceKey<T>, Weak<ResourceBox<T>>>,
+    by_id: HashMap<String, (ResourceKey<T>, GenericResourceLoad<T>)>,
+    ids: HashMap<ResourceKey<T>, String>,
+    // defined explicitly rather than loaded, so nothing else keeps them alive
+    defined: HashMap<String, Rc<ResourceBox<T>>>,
 }
 
 impl<T: 'static> SpecializedTypeManager<T> {
     fn new() -> SpecializedTypeManager<T> {
         SpecializedTypeManager {
-            values: Default::default()
+            values: Default::default(),
+            by_id: Default::default(),
+            ids: Default::default(),
+            defined: Default::default(),
         }
     }
     fn despecialize(self) -> RawTypeManager {
@@ -217,6 +422,14 @@ enum ResourceKey<T> {
     Async(*const ResourceLoadAsync<T>),
 }
 
+impl<T> Clone for ResourceKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ResourceKey<T> {}
+
 impl<T> PartialEq<Self> for ResourceKey<T> {
     fn eq(&self, other: &Self) -> bool {
         match self {
@@ -262,13 +475,15 @@ impl Uid {
 
 #[derive(Debug, Clone)]
 enum ResourceImpl<T> {
-    Detached(Uid, Rc<T>),
+    Detached(Uid, Option<&'static str>, Rc<T>),
     Managed(Rc<ResourceBox<T>>),
 }
 
 #[derive(Debug)]
 struct ResourceBox<T> {
     uid: Uid,
+    // None for resources, which loaders are not registered
+    id: RefCell<Option<String>>,
     value: UnsafeCell<Option<T>>,
     seal: SealPtr,
 }
@@ -276,15 +491,15 @@ struct ResourceBox<T> {
 impl<T> PartialEq<Self> for Resource<T> {
     fn eq(&self, other: &Self) -> bool {
         match &self.0 {
-            ResourceImpl::Detached(a, _) => {
+            ResourceImpl::Detached(a, _, _) => {
                 match &other.0 {
-                    ResourceImpl::Detached(b, _) => a == b,
+                    ResourceImpl::Detached(b, _, _) => a == b,
                     ResourceImpl::Managed(_) => false,
                 }
             }
             ResourceImpl::Managed(a) => {
                 match &other.0 {
-                    ResourceImpl::Detached(_, _) => false,
+                    ResourceImpl::Detached(_, _, _) => false,
                     ResourceImpl::Managed(b) => a.value.get() == b.value.get(),
                 }
             }
@@ -297,7 +512,7 @@ impl<T> Eq for Resource<T> {}
 impl <T> Hash for Resource<T> {
     fn hash<H: Hasher>(&self, state: &mut H) {
         match &self.0 {
-            ResourceImpl::Detached(a, _) => {
+            ResourceImpl::Detached(a, _, _) => {
                 a.hash(state);
             }
             ResourceImpl::Managed(a) => {
@@ -316,6 +531,7 @@ impl<T> ResourceBox<T> {
     fn new(seal: SealPtr) -> Self {
         ResourceBox {
             uid: Uid::new(),
+            id: RefCell::new(None),
             value: UnsafeCell::new(None),
             seal,
         }
@@ -352,14 +568,21 @@ impl<T> ResourceBox<T> {
 
 impl<T> Resource<T> {
     pub fn detached(t: T) -> Self {
-        Resource(ResourceImpl::Detached(Uid::new(), Rc::new(t)))
+        Resource(ResourceImpl::Detached(Uid::new(), None, Rc::new(t)))
+    }
+
+    pub fn id(&self) -> Option<String> {
+        match &self.0 {
+            ResourceImpl::Detached(_, id, _) => id.map(|it| it.to_owned()),
+            ResourceImpl::Managed(v) => v.id.borrow().clone(),
+        }
     }
 }
 
 impl<T> Clone for Resource<T> {
     fn clone(&self) -> Self {
         match &self.0 {
-            ResourceImpl::Detached(uid, v) => Resource(ResourceImpl::Detached(*uid, v.clone())),
+            ResourceImpl::Detached(uid, id, v) => Resource(ResourceImpl::Detached(*uid, *id, v.clone())),
             ResourceImpl::Managed(v) => Resource(ResourceImpl::Managed(v.clone()))
         }
     }
@@ -370,7 +593,7 @@ impl<T> Deref for Resource<T> {
 
     fn deref(&self) -> &Self::Target {
         match &self.0 {
-            ResourceImpl::Detached(_, v) => v.deref(),
+            ResourceImpl::Detached(_, _, v) => v.deref(),
             ResourceImpl::Managed(v) => v.get_value_ref(),
         }
     }
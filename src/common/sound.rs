@@ -30,10 +30,12 @@ impl StopSound for Vec<Option<Resource<GameSound>>> {
 impl StopSound for Option<Resource<GameSound>> {
     fn stop(&self, manager: &mut AudioManager) {
         if let Some(sound) = self {
-            stop_sound(sound.sound);
+            if !manager.muted {
+                stop_sound(sound.sound);
+            }
             for i in manager.looped.indices().rev() {
                 let state = manager.looped.get(i).unwrap();
-                if state.def.sound == sound.sound {
+                if state.def == *sound {
                     manager.looped.remove(i);
                 }
             }
@@ -59,6 +61,10 @@ impl SoundList for Vec<Option<Resource<GameSound>>> {
 impl PlaySound for Option<Resource<GameSound>> {
     fn play_once(&self, manager: &AudioManager) {
         if let Some(sound) = self {
+            // sounds may be not loaded at all when muted (see Backend::can_load), so checked first
+            if manager.muted {
+                return;
+            }
             if let Some(throttling_sec) = sound.throttling_sec {
                 let mut cool_down_sec = manager.cool_down_sec.borrow_mut();
                 let remaining = cool_down_sec.get(sound).copied().unwrap_or(0.0);
@@ -73,7 +79,9 @@ impl PlaySound for Option<Resource<GameSound>> {
 
     fn play_looped(&self, manager: &mut AudioManager) {
         if let Some(sound) = self {
-            play_sound(sound.sound, PlaySoundParams { looped: true, volume: sound.base_volume * manager.volume.get() });
+            if !manager.muted {
+                play_sound(sound.sound, PlaySoundParams { looped: true, volume: sound.base_volume * manager.volume.get() });
+            }
             manager.looped.push(LoopedSoundState {
                 def: sound.clone(),
                 instance_volume: 1.0,
@@ -84,12 +92,14 @@ impl PlaySound for Option<Resource<GameSound>> {
     fn set_volume(&self, volume: f32, manager: &mut AudioManager) {
         if let Some(sound) = self {
             for state in &mut manager.looped {
-                if state.def.sound == sound.sound {
+                if state.def == *sound {
                     state.instance_volume = volume;
                 }
             }
 
-            set_sound_volume(sound.sound, volume * sound.base_volume * manager.volume.get());
+            if !manager.muted {
+                set_sound_volume(sound.sound, volume * sound.base_volume * manager.volume.get());
+            }
         }
     }
 }
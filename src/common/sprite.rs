@@ -7,12 +7,13 @@ use macroquad::color::WHITE;
 use macroquad::material::Material;
 use macroquad::miniquad::gl::glUniform3f;
 use macroquad::models::{draw_mesh, Mesh, Vertex};
-use macroquad::prelude::{Color, gl_use_default_material, gl_use_material, load_material, Rect, Texture2D};
+use macroquad::prelude::{Color, gl_use_default_material, gl_use_material, Image, load_material, Rect, Texture2D};
 use crate::common::angle::Angle;
-use crate::common::resource::Resource;
+use crate::common::colliders;
+use crate::common::resource::{Resource, ResourceManager};
 use crate::common::unsorted::{ModifyColor, ToColor, WithMut};
 use crate::model::data::SpriteMod;
-use crate::model::def::{MaterialInstance, Sprite, SpriteRegion, UniformSupplier};
+use crate::model::def::{CollisionCircle, MaterialInstance, Sprite, SpriteRegion, UniformSupplier};
 
 use crate::resources::constants::SCALE_SPEED;
 
@@ -20,17 +21,45 @@ thread_local! {
     pub static DEBUG_TINT: Cell<Color> = Cell::new(WHITE);
 }
 
-pub async fn load_sprite(data: &[u8], sprite_mods: Vec<SpriteMod>) -> Sprite {
-    let texture = Texture2D::from_file_with_format(data, None);
-    let size = Vec2::new(texture.width(), texture.height());
+pub async fn load_sprite(rm: ResourceManager, data: &[u8], sprite_mods: Vec<SpriteMod>) -> Sprite {
+    sprite_from_image(&rm, &Image::from_file_with_format(data, None), sprite_mods)
+}
+
+fn sprite_from_image(rm: &ResourceManager, image: &Image, sprite_mods: Vec<SpriteMod>) -> Sprite {
+    let texture = rm.backend().load_texture(image);
+    let size = Vec2::new(image.width() as f32, image.height() as f32);
     let origin = Vec2::ONE * 0.5;
     let region = SpriteRegion { x0: 0.0, y0: 0.0, x1: 1.0, y1: 1.0 };
     let scale = 1.0;
-    let mut sprite = Sprite { texture, origin_normalized: origin, size, scale, angle: Angle::ZERO, region, collision_circle_normalized: None };
+    let mut sprite = Sprite { texture, origin_normalized: origin, size, scale, angle: Angle::ZERO, region, collision_circle_normalized: None, collision_circles: vec![], collision_bound: 0.0 };
     apply_sprite_mods(&mut sprite, sprite_mods);
     sprite
 }
 
+// same as load_sprite, but also covers opaque pixels with collision_circles
+pub async fn load_sprite_with_collision(rm: ResourceManager, data: &[u8], sprite_mods: Vec<SpriteMod>, cell_px: usize) -> Sprite {
+    let image = Image::from_file_with_format(data, None);
+    let mut sprite = sprite_from_image(&rm, &image, sprite_mods);
+    let circles = colliders::circles_from_mask(image.width(), image.height(), cell_px, |x, y| image.get_pixel(x as u32, y as u32).a > 0.5);
+    sprite.collision_bound = colliders::bounding_radius(&circles, sprite.origin_normalized * sprite.size);
+    sprite.collision_circles = circles.into_iter()
+        .map(|(center, radius)| CollisionCircle { center, radius })
+        .collect();
+    sprite
+}
+
+// collision_circles relative to the sprite origin, in world units
+pub fn world_collision_circles(sprite: &Sprite, scale: f32) -> impl Iterator<Item=CollisionCircle> + '_ {
+    let origin = sprite.origin_normalized * sprite.size;
+    let k = sprite.scale * SCALE_SPEED * scale;
+    sprite.collision_circles.iter().map(move |it| CollisionCircle { center: (it.center - origin) * k, radius: it.radius * k })
+}
+
+// collision_bound in world units
+pub fn world_collision_bound(sprite: &Sprite, scale: f32) -> f32 {
+    sprite.collision_bound * sprite.scale * SCALE_SPEED * scale
+}
+
 pub fn apply_sprite_mods(sprite: &mut Sprite, sprite_mods: Vec<SpriteMod>) {
     for sprite_mod in sprite_mods {
         match sprite_mod {
@@ -48,16 +77,16 @@ pub enum SheetRegion {
     Cells(Vec<(usize, usize)>),
 }
 
-pub async fn load_sprites_from_files(files: Vec<&[u8]>, sprite_mods: Vec<SpriteMod>) -> Vec<Sprite>
+pub async fn load_sprites_from_files(rm: ResourceManager, files: Vec<&[u8]>, sprite_mods: Vec<SpriteMod>) -> Vec<Sprite>
 {
     let mut sprites = vec![];
     for file in files {
-        sprites.push(load_sprite(file, sprite_mods.clone()).await);
+        sprites.push(load_sprite(rm.clone(), file, sprite_mods.clone()).await);
     }
     sprites
 }
 
-pub fn load_sprites_from_sheet<F: Fn(&mut Sprite)>(data: &[u8], sheet_size: (usize, usize), region: SheetRegion, f: F) -> Vec<Sprite> {
+pub fn load_sprites_from_sheet<F: Fn(&mut Sprite)>(rm: &ResourceManager, data: &[u8], sheet_size: (usize, usize), region: SheetRegion, f: F) -> Vec<Sprite> {
     let cells: Vec<(usize, usize)> = match region {
         SheetRegion::All => (0..sheet_size.1)
             .flat_map(|y| (0..sheet_size.0)
@@ -69,8 +98,10 @@ pub fn load_sprites_from_sheet<F: Fn(&mut Sprite)>(data: &[u8], sheet_size: (usi
             .collect(),
         SheetRegion::Cells(cells) => cells
     };
-    let texture = Texture2D::from_file_with_format(data, None);
-    let size = Vec2::new(texture.width() / sheet_size.0 as f32, texture.height() / sheet_size.1 as f32);
+    let image = Image::from_file_with_format(data, None);
+    let texture = rm.backend().load_texture(&image);
+    let texture_size = Vec2::new(image.width() as f32, image.height() as f32);
+    let size = Vec2::new(texture_size.x / sheet_size.0 as f32, texture_size.y / sheet_size.1 as f32);
     let cell_size = (1.0 / sheet_size.0 as f32, 1.0 / sheet_size.1 as f32);
     cells
         .iter()
@@ -84,7 +115,9 @@ pub fn load_sprites_from_sheet<F: Fn(&mut Sprite)>(data: &[u8], sheet_size: (usi
                 scale: 1.0,
                 angle: Angle::ZERO,
                 region: SpriteRegion { x0: u0, y0: v0, x1: u0 + cell_size.0, y1: v0 + cell_size.1 },
-                collision_circle_normalized: None
+                collision_circle_normalized: None,
+                collision_circles: vec![],
+                collision_bound: 0.0,
             };
             f(&mut sprite);
             sprite
@@ -0,0 +1,55 @@
+use macroquad::prelude::Vec2;
+
+// location is wrapped along x (see physics::apply_velocity), so there are always two ways
+// from one point to another. everything that measures or aims should take the shortest one.
+
+// shortest vector from `from` to `to`
+pub fn delta(from: Vec2, to: Vec2, loc_size: Vec2) -> Vec2 {
+    let mut delta = to - from;
+    if loc_size.x > 0.0 {
+        delta.x -= (delta.x / loc_size.x).round() * loc_size.x;
+    }
+    delta
+}
+
+pub fn distance(a: Vec2, b: Vec2, loc_size: Vec2) -> f32 {
+    delta(a, b, loc_size).length()
+}
+
+// same point with x within [0, loc_size.x)
+pub fn wrap(pos: Vec2, loc_size: Vec2) -> Vec2 {
+    if loc_size.x <= 0.0 {
+        return pos;
+    }
+    Vec2::new(pos.x.rem_euclid(loc_size.x), pos.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+    use crate::common::toroidal::{delta, distance, wrap};
+
+    const SIZE: Vec2 = Vec2::new(1000.0, 500.0);
+
+    #[test]
+    fn delta_plain() {
+        assert_eq!(Vec2::new(100.0, -50.0), delta(Vec2::new(400.0, 100.0), Vec2::new(500.0, 50.0), SIZE));
+    }
+
+    #[test]
+    fn delta_across_seam() {
+        assert_eq!(Vec2::new(20.0, 0.0), delta(Vec2::new(990.0, 100.0), Vec2::new(10.0, 100.0), SIZE));
+        assert_eq!(Vec2::new(-20.0, 0.0), delta(Vec2::new(10.0, 100.0), Vec2::new(990.0, 100.0), SIZE));
+    }
+
+    #[test]
+    fn distance_across_seam() {
+        assert_eq!(30.0, distance(Vec2::new(5.0, 0.0), Vec2::new(975.0, 0.0), SIZE));
+    }
+
+    #[test]
+    fn wrap_both_sides() {
+        assert_eq!(Vec2::new(10.0, 7.0), wrap(Vec2::new(1010.0, 7.0), SIZE));
+        assert_eq!(Vec2::new(990.0, 7.0), wrap(Vec2::new(-10.0, 7.0), SIZE));
+    }
+}
@@ -3,9 +3,23 @@ use std::fs::File;
 use macroquad::logging::error;
 use crate::Conf;
 
-pub fn deal_with_config_file(default_conf: Conf) -> Conf {
+pub struct AppConf {
+    pub window: Conf,
+    pub pacing: FramePacing,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FramePacing {
+    // sleeps the rest of the frame
+    Sleep { max_fps: f64 },
+    // relies on driver. frame rate is not guaranteed, because vsync is just a hint
+    VSync,
+    Uncapped,
+}
+
+pub fn deal_with_config_file(default_conf: AppConf) -> AppConf {
     let path = "config.conf";
-    if File::open(path).is_ok() {
+    let mut conf = if File::open(path).is_ok() {
         match Ini::load_from_file(path) {
             Ok(ini) => ini_to_conf(ini, default_conf),
             Err(err) => {
@@ -17,33 +31,66 @@ pub fn deal_with_config_file(default_conf: Conf) -> Conf {
         let default = conf_to_ini(&default_conf);
         default.write_to_file(path);
         default_conf
-    }
+    };
+    conf.window.platform.swap_interval = Some(match conf.pacing {
+        FramePacing::VSync => 1,
+        FramePacing::Sleep { .. } | FramePacing::Uncapped => 0,
+    });
+    conf
 }
 
-fn conf_to_ini(conf: &Conf) -> Ini {
+fn conf_to_ini(conf: &AppConf) -> Ini {
     let mut ini = Ini::new();
+    let (pacing, max_fps) = match conf.pacing {
+        FramePacing::Sleep { max_fps } => ("sleep", max_fps),
+        FramePacing::VSync => ("vsync", 60.0),
+        FramePacing::Uncapped => ("uncapped", 60.0),
+    };
     ini.with_general_section()
-        .set("window_width", conf.window_width.to_string())
-        .set("window_height", conf.window_height.to_string())
-        .set("fullscreen", conf.fullscreen.to_string());
+        .set("window_width", conf.window.window_width.to_string())
+        .set("window_height", conf.window.window_height.to_string())
+        .set("fullscreen", conf.window.fullscreen.to_string())
+        .set("frame_pacing", pacing)
+        .set("max_fps", max_fps.to_string());
     ini
 }
 
-fn ini_to_conf(ini: Ini, conf: Conf) -> Conf {
+fn ini_to_conf(ini: Ini, conf: AppConf) -> AppConf {
     let mut conf = conf;
     if let Some(v) = ini.general_section().get("window_width") {
         if let Ok(v) = v.parse() {
-            conf.window_width = v;
+            conf.window.window_width = v;
         }
     }
     if let Some(v) = ini.general_section().get("window_height") {
         if let Ok(v) = v.parse() {
-            conf.window_height = v;
+            conf.window.window_height = v;
         }
     }
     if let Some(v) = ini.general_section().get("fullscreen") {
         if let Ok(v) = v.parse() {
-            conf.fullscreen = v;
+            conf.window.fullscreen = v;
+        }
+    }
+    let mut max_fps = match conf.pacing {
+        FramePacing::Sleep { max_fps } => max_fps,
+        _ => 60.0,
+    };
+    if let Some(v) = ini.general_section().get("max_fps") {
+        match v.parse::<f64>() {
+            Ok(v) if v > 0.0 => max_fps = v,
+            _ => error!("invalid max_fps: {}", v),
+        }
+    }
+    match ini.general_section().get("frame_pacing") {
+        Some("sleep") => conf.pacing = FramePacing::Sleep { max_fps },
+        Some("vsync") => conf.pacing = FramePacing::VSync,
+        Some("uncapped") => conf.pacing = FramePacing::Uncapped,
+        Some(v) => error!("invalid frame_pacing: {}. expected one of: sleep, vsync, uncapped", v),
+        None => {
+            if let FramePacing::Sleep { .. } = conf.pacing {
+                conf.pacing = FramePacing::Sleep { max_fps };
+            }
         }
     }
     conf
@@ -5,16 +5,15 @@ use crate::common::contract::GetMut;
 use crate::game::plane;
 use crate::GameState;
 use crate::model::state::BotState;
-use crate::rand::gen_range;
 use crate::resources::constants::NOMINAL_SPEED;
 
 pub fn init_bots(state: &mut GameState) {
     for i in 0..state.location.bots.len() {
         let bot = &state.location.bots[i];
-        let direction_x = if gen_range(0, 1) == 0 { -1.0 } else { 1.0 };
+        let direction_x = if state.rng.gen_range(0..2) == 0 { -1.0 } else { 1.0 };
         let pos = Vec2::new(
-            gen_range(0.0, state.location.size.x),
-            bot.height_normalized.lerp(gen_range(0.0, 1.0)) * state.location.size.y,
+            state.rng.gen_range(0.0..state.location.size.x),
+            bot.height_normalized.lerp(state.rng.gen_range(0.0..1.0)) * state.location.size.y,
         );
         let plane = plane::allocate_plane(
             &mut state.planes,
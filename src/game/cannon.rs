@@ -14,19 +14,24 @@ use crate::common::{physics, sprite};
 use crate::common::angle::{Angle, AsRadians};
 use crate::common::resource::Resource;
 use crate::common::camera::ViewPort;
+use crate::common::colliders::CollisionField;
 use crate::common::contract::{Get, InsertSimple};
 use crate::common::curve::Curve;
 use crate::common::frame::FrameCtx;
+use crate::common::interpolation;
 use crate::common::line_circle::Line;
 use crate::common::metrics::Metrics;
 use crate::common::pool::Pool;
+use crate::common::rng::Rng;
 use crate::common::sound::PlaySound;
 use crate::common::sprite::draw_sprite;
+use crate::common::toroidal;
 use crate::common::unsorted::{gen_range, IndexRange, ToAngle, WithMut};
 use crate::game::{particles, plane};
-use crate::model::def::{Projectile, Cannon, CannonBarrel, CollisionCircle, Location, GameSound, Collider, Mob, CollisionRay, HitScanLook, ProjectileRot, ProjectileMod};
-use crate::model::state::{AudioManager, ProjectileState, CannonState, Durable, IsleId, IsleState, MobBaseState, MobState, ParticleEmitterState, ParticlesState, RotState, SpriteClipState, SubSystems, TransState, WeaponOwner, GameCommand, MobId, RayState, ProjectileHomingState, ProjectileStateMod, Ammo, RayTrans, DeviceSpec};
+use crate::model::def::{Projectile, Cannon, CannonBarrel, CollisionCircle, Location, GameSound, Collider, Mob, CollisionRay, HitScanLook, ProjectileRot, ProjectileMod, SplashDamage, DamageKind};
+use crate::model::state::{AudioManager, ProjectileState, CannonState, Durable, IsleId, IsleState, MobBaseState, MobState, ParticleEmitterState, ParticlesState, RotState, SpriteClipState, SubSystems, TransState, WeaponOwner, GameCommand, MobId, RayState, ProjectileHomingState, ProjectileStateMod, Ammo, RayTrans, DeviceSpec, ColliderKey};
 use crate::model::state::DamageTarget;
+use crate::resources::constants::SPEED_ABS_MAX;
 
 pub fn update_projectiles(state: &mut GameState, dt: &FrameCtx, vp: &ViewPort) {
     for i in state.projectiles.indices().rev() {
@@ -45,7 +50,8 @@ pub fn update_projectiles(state: &mut GameState, dt: &FrameCtx, vp: &ViewPort) {
             vp,
             &state.player,
             &mut state.commands,
-            &state.reachable_mobs,
+            &state.colliders,
+            &mut state.rng,
         );
         match action {
             HitAction::Proceed => {}
@@ -57,24 +63,20 @@ pub fn update_projectiles(state: &mut GameState, dt: &FrameCtx, vp: &ViewPort) {
                         //explosion shouldn't move as missile
                         velocity: Vec2::ZERO,
                     };
-                    particles::emit_explosion(&explosion.explosion, &pos, &mut state.particles, &state.subsystems);
+                    particles::emit_explosion(&explosion.explosion, &pos, &mut state.particles, &mut state.debris, &mut state.rng, &state.subsystems);
                 }
                 if let Some(splash_damage) = &projectile.def.splash_damage {
-                    for mob_id in &state.reachable_mobs {
-                        if let Some(mob) = state.mobs.get(mob_id) {
-                            let mob_pos = mob.anchor.get_pos_rel().get_abs(&state.isles) + mob.base.def.collider_unscaled.center * mob.base.def.scale;
-                            let mob_radius = mob.base.def.collider_unscaled.radius * mob.base.def.scale;
-                            let distance = mob_pos.distance(projectile.trans.pos) - mob_radius;
-                            let distance_norm = (distance / splash_damage.radius).max(0.0);
-                            if distance_norm < 1.0 {
-                                state.commands.insert_simple(GameCommand::Damage {
-                                    amount: splash_damage.damage.random() * splash_damage.damage_factor_by_distance_norm.lerp(distance_norm),
-                                    source: projectile.owner,
-                                    target: DamageTarget::Mob(*mob_id),
-                                })
-                            }
-                        }
-                    }
+                    apply_splash_damage(
+                        splash_damage,
+                        projectile.trans.pos,
+                        projectile.owner,
+                        &state.colliders,
+                        &state.planes,
+                        &state.mobs,
+                        &state.isles,
+                        &mut state.commands,
+                        &mut state.rng,
+                    );
                 }
             }
             HitAction::SilentDiscard => {
@@ -92,6 +94,61 @@ pub fn update_projectiles(state: &mut GameState, dt: &FrameCtx, vp: &ViewPort) {
     }
 }
 
+// everything damageable in the radius, including planes, unless SplashDamage spares it
+fn apply_splash_damage(
+    splash_damage: &SplashDamage,
+    center: Vec2,
+    owner: WeaponOwner,
+    colliders: &CollisionField<ColliderKey>,
+    planes: &Pool<PlaneId, PlaneState>,
+    mobs: &Pool<MobId, MobState>,
+    isles: &Pool<IsleId, IsleState>,
+    commands: &mut impl InsertSimple<GameCommand>,
+    rng: &mut Rng,
+) {
+    for candidate in colliders.query_circle(center, splash_damage.radius) {
+        let (target, pos, radius) = match candidate.key {
+            ColliderKey::Mob(id) => match mobs.get(&id) {
+                Some(mob) if matches!(mob.base.durable, Durable::Good { .. }) => (
+                    DamageTarget::Mob(id),
+                    mob.anchor.get_pos_rel().get_abs(isles) + mob.base.def.collider_unscaled.center * mob.base.def.scale,
+                    mob.base.def.collider_unscaled.radius * mob.base.def.scale,
+                ),
+                _ => continue,
+            },
+            ColliderKey::Plane(id) => match planes.get(&id) {
+                Some(plane) if matches!(plane.durable, Durable::Good { .. }) => (
+                    DamageTarget::Plane(id),
+                    plane.trans.pos,
+                    plane.def.collision_radius,
+                ),
+                _ => continue,
+            },
+            ColliderKey::Loot(_) => continue,
+        };
+        if is_spared(splash_damage, owner, target) {
+            continue;
+        }
+        let distance = (pos + candidate.offset).distance(center) - radius;
+        let distance_norm = (distance / splash_damage.radius).max(0.0);
+        if distance_norm < 1.0 {
+            commands.insert_simple(GameCommand::Damage {
+                amount: splash_damage.damage.random_by(rng) * splash_damage.damage_factor_by_distance_norm.lerp(distance_norm),
+                kind: splash_damage.damage_kind,
+                source: owner,
+                target,
+            })
+        }
+    }
+}
+
+fn is_spared(splash_damage: &SplashDamage, owner: WeaponOwner, target: DamageTarget) -> bool {
+    match (owner, target) {
+        (WeaponOwner::Plane(owner), DamageTarget::Plane(id)) if owner == id => !splash_damage.self_damage,
+        _ => owner.faction() == Some(target.faction()) && !splash_damage.friendly_fire,
+    }
+}
+
 fn is_on_screen(vp: &ViewPort, projectile: &ProjectileState) -> bool {
     let collider_abs = CollisionCircle {
         center: projectile.trans.pos,
@@ -126,7 +183,8 @@ fn update_projectile(
     vp: &ViewPort,
     player: &PlayerState,
     commands: &mut impl InsertSimple<GameCommand>,
-    reachable_mobs: &Vec<MobId>,
+    colliders: &CollisionField<ColliderKey>,
+    rng: &mut Rng,
 ) -> HitAction {
     let timeout = if let Some(rem) = &mut projectile.remaining_seconds {
         if *rem <= 0.0 {
@@ -156,7 +214,15 @@ fn update_projectile(
                 match m {
                     ProjectileHomingState::Plane(target) => {
                         if let Some(target) = planes.get(&target) {
-                            homing = Some((target.trans.pos - projectile.trans.pos).to_angle());
+                            homing = Some(toroidal::delta(projectile.trans.pos, target.trans.pos, location.size).to_angle());
+                        }
+                    }
+                    ProjectileHomingState::Mob(target) => {
+                        if let Some(target) = mobs.get(&target) {
+                            if let Durable::Good { .. } = target.base.durable {
+                                let center = target.anchor.get_pos_rel().get_abs(&*isles) + target.base.def.collider_unscaled.center * target.base.def.scale;
+                                homing = Some(toroidal::delta(projectile.trans.pos, center, location.size).to_angle());
+                            }
                         }
                     }
                 }
@@ -171,10 +237,10 @@ fn update_projectile(
         assert!(homing.is_none(), "homing not supported for projectiles without stabilization")
     }
     if let Some(acceleration) = &projectile.def.acceleration {
-        physics::apply_thrust(metrics, dt.dt, *acceleration, &projectile.rot, &mut projectile.trans)
+        physics::apply_thrust(metrics, dt.dt, *acceleration, SPEED_ABS_MAX, &projectile.rot, &mut projectile.trans)
     }
     physics::apply_gravity(&mut projectile.trans, metrics, dt.dt);
-    physics::apply_drag(&mut projectile.trans, &metrics, dt.dt, dt.dt);
+    physics::apply_drag(&mut projectile.trans, &metrics, projectile.def.body.drag, dt.dt, dt.dt);
     if let Some(stabilization) = &projectile.def.stabilization {
         physics::apply_slide(metrics, dt.dt, dt.dt, &mut projectile.trans, &projectile.rot, &stabilization.slide);
     }
@@ -195,6 +261,7 @@ fn update_projectile(
             pos: projectile.trans.pos,
             source: projectile.owner,
             damage: projectile.def.damage.clone(),
+            damage_kind: projectile.def.damage_kind,
             hit_sound: projectile.def.hit_sound.clone(),
             collider: Collider::Ray(CollisionRay {
                 origin: projectile.trans.pos,
@@ -203,7 +270,8 @@ fn update_projectile(
                 thickness: projectile.def.collision_radius,
             }),
         },
-        reachable_mobs,
+        colliders,
+        rng,
     )
 }
 
@@ -211,15 +279,21 @@ struct Threat {
     pos: Vec2,
     source: WeaponOwner,
     damage: Curve<f32>,
+    damage_kind: DamageKind,
     hit_sound: Option<Resource<GameSound>>,
     collider: Collider,
 }
 
-fn check_ray(ray: CollisionRay, candidate_pos: Vec2, candidate_radius: f32) -> bool {
+fn ray_segment(ray: CollisionRay) -> (Vec2, Vec2) {
     // for "intrusiveness", especially for explosive missiles
-    let effective_radius = candidate_radius + ray.thickness;
     let effective_origin = ray.origin - ray.dir * ray.thickness;
-    let line = Line { p1: effective_origin, p2: effective_origin + ray.dir.normalize() * ray.distance };
+    (effective_origin, effective_origin + ray.dir.normalize() * ray.distance)
+}
+
+fn check_ray(ray: CollisionRay, candidate_pos: Vec2, candidate_radius: f32) -> bool {
+    let effective_radius = candidate_radius + ray.thickness;
+    let (p1, p2) = ray_segment(ray);
+    let line = Line { p1, p2 };
     line.p1.distance(candidate_pos) < effective_radius
         || line.p2.distance(candidate_pos) < effective_radius
         || line.circle_intersections(candidate_pos.x, candidate_pos.y, effective_radius, true).is_empty().not()
@@ -232,15 +306,32 @@ fn try_hit(
     commands: &mut impl InsertSimple<GameCommand>,
     subsystems: &SubSystems,
     threat: Threat,
-    reachable_mobs: &Vec<MobId>,
+    colliders: &CollisionField<ColliderKey>,
+    rng: &mut Rng,
 ) -> HitAction {
-    for (id, plane) in planes.iter() {
+    let candidates = match threat.collider {
+        Collider::Circle(collider) => colliders.query_circle(collider.center, collider.radius),
+        Collider::Ray(ray) => {
+            let (p1, p2) = ray_segment(ray);
+            colliders.query_segment(p1, p2, ray.thickness)
+        }
+    };
+
+    for candidate in candidates.iter() {
+        let (id, plane) = match candidate.key {
+            ColliderKey::Plane(id) => match planes.get(&id) {
+                Some(plane) => (id, plane),
+                None => continue,
+            },
+            ColliderKey::Mob(_) | ColliderKey::Loot(_) => continue,
+        };
+        let plane_pos = plane.trans.pos + candidate.offset;
         match plane.durable {
             Durable::Good { .. } => {}
             Durable::Destroyed(_) => continue,
         }
         let me = match threat.source {
-            WeaponOwner::Plane(plane_id) => plane_id == *id,
+            WeaponOwner::Plane(plane_id) => plane_id == id,
             WeaponOwner::Mob => false,
             WeaponOwner::Environment => false,
         };
@@ -249,36 +340,41 @@ fn try_hit(
         }
         let hit = match threat.collider {
             Collider::Circle(collider) => {
-                let distance = plane.trans.pos.distance(collider.center);
+                let distance = plane_pos.distance(collider.center);
                 distance < collider.radius + plane.def.collision_radius
             }
             Collider::Ray(ray) => {
-                check_ray(ray, plane.trans.pos, plane.def.collision_radius)
+                check_ray(ray, plane_pos, plane.def.collision_radius)
             }
         };
         if hit {
             commands.insert_simple(GameCommand::Damage {
-                amount: threat.damage.random(),
+                amount: threat.damage.random_by(rng),
+                kind: threat.damage_kind,
                 source: threat.source,
-                target: DamageTarget::Plane(*id),
+                target: DamageTarget::Plane(id),
             });
             threat.hit_sound.play_once(&subsystems.audio);
-            return HitAction::Hit { point: plane.trans.pos };
+            return HitAction::Hit { point: plane_pos };
         }
     }
 
-    let mut sorted_mobs = reachable_mobs.iter()
-        .filter_map(|it| mobs.get(it).map(|mob| (it, mob)))
+    let mut sorted_mobs = candidates.iter()
+        .filter_map(|it| match it.key {
+            ColliderKey::Mob(id) => mobs.get(&id).map(|mob| (id, mob, mob.anchor.get_pos_rel().get_abs(isles) + it.offset)),
+            ColliderKey::Plane(_) | ColliderKey::Loot(_) => None,
+        })
         .to_vec();
-    sorted_mobs.sort_by_key(|(_, it)| it.anchor.get_pos_rel().get_abs(isles).distance(threat.pos) as i32);
-    for (id, mob) in sorted_mobs {
+    sorted_mobs.sort_by_key(|(_, _, pos)| pos.distance(threat.pos) as i32);
+    for (id, mob, mob_pos) in sorted_mobs {
         if let Some(hit) = try_hit_mob(
-            *id,
+            id,
             &mob.base,
-            mob.anchor.get_pos_rel().get_abs(isles),
+            mob_pos,
             subsystems,
             &threat,
             commands,
+            rng,
         ) {
             return hit;
         }
@@ -293,6 +389,7 @@ fn try_hit_mob(
     settings: &SubSystems,
     threat: &Threat,
     commands: &mut impl InsertSimple<GameCommand>,
+    rng: &mut Rng,
 ) -> Option<HitAction> {
     let me = match threat.source {
         WeaponOwner::Plane(plane_id) => false,
@@ -316,11 +413,12 @@ fn try_hit_mob(
                 }
             };
             if hit {
-                let damage = threat.damage.random();
+                let damage = threat.damage.random_by(rng);
                 commands.insert_simple(GameCommand::Damage {
                     target: DamageTarget::Mob(mob_id),
                     source: threat.source,
                     amount: damage,
+                    kind: threat.damage_kind,
                 });
                 threat.hit_sound.play_once(&settings.audio);
                 return Some(HitAction::Hit { point: mob_pos });
@@ -338,10 +436,14 @@ pub fn update_cannon(
     frame: &FrameCtx,
     commands: &mut impl InsertSimple<GameCommand>,
     energy: &mut f32,
+    rng: &mut Rng,
 ) {
     if cannon.recovery_seconds > 0.0 {
         cannon.recovery_seconds -= frame.dt;
     }
+    if let Ammo::Heat { cooling_per_sec, .. } = cannon.ammo {
+        cool_down(&mut cannon.heat, &mut cannon.overheat_seconds, cooling_per_sec, frame.dt);
+    }
     if cannon.trigger && cannon.recovery_seconds <= 0.0 {
         cannon.recovery_seconds += 1.0 / cannon.def.rate;
 
@@ -363,10 +465,13 @@ pub fn update_cannon(
                     true
                 }
             }
+            Ammo::Heat { heat_per_shot, lockout_sec, .. } => {
+                heat_up(&mut cannon.heat, &mut cannon.overheat_seconds, *heat_per_shot, *lockout_sec)
+            }
         };
 
         if enough_ammo {
-            let initial_angle = rot.angle + Angle::degrees(cannon.def.spread_degrees.random());
+            let initial_angle = rot.angle + Angle::degrees(cannon.def.spread_degrees.random_by(rng));
             commands.insert_simple(GameCommand::FireCannon {
                 bal: bal.clone(),
                 rot: rot.clone(),
@@ -378,6 +483,26 @@ pub fn update_cannon(
     }
 }
 
+fn cool_down(heat: &mut f32, overheat_seconds: &mut f32, cooling_per_sec: f32, dt: f32) {
+    if *overheat_seconds > 0.0 {
+        *overheat_seconds -= dt;
+    }
+    *heat = (*heat - cooling_per_sec * dt).max(0.0);
+}
+
+// false if the weapon is locked by overheat. reaching full heat locks it for lockout_sec
+fn heat_up(heat: &mut f32, overheat_seconds: &mut f32, heat_per_shot: f32, lockout_sec: f32) -> bool {
+    if *overheat_seconds > 0.0 {
+        return false;
+    }
+    *heat += heat_per_shot;
+    if *heat >= 1.0 {
+        *heat = 1.0;
+        *overheat_seconds = lockout_sec;
+    }
+    true
+}
+
 pub fn fire(
     player: &PlayerState,
     trans: &TransState,
@@ -391,8 +516,9 @@ pub fn fire(
     planes: &Pool<PlaneId, PlaneState>,
     isles: &Pool<IsleId, IsleState>,
     mobs: &Pool<MobId, MobState>,
-    reachable_mobs: &Vec<MobId>,
+    colliders: &CollisionField<ColliderKey>,
     commands: &mut impl InsertSimple<GameCommand>,
+    rng: &mut Rng,
 ) {
     cannon.sound.play_once(&settings.audio);
     match def {
@@ -401,7 +527,15 @@ pub fn fire(
             for m in &def.mods {
                 match m {
                     ProjectileMod::Homing => {
-                        if let Some(player) = player.plane {
+                        let is_player = player.plane
+                            .map(|it| WeaponOwner::Plane(it) == owner)
+                            .unwrap_or(false);
+                        if is_player {
+                            // no lock, no homing
+                            if let Some(lock) = player.lock_on.as_ref().filter(|it| it.is_locked()) {
+                                mods.push(ProjectileStateMod::Homing(ProjectileHomingState::Mob(lock.target)));
+                            }
+                        } else if let Some(player) = player.plane {
                             mods.push(ProjectileStateMod::Homing(ProjectileHomingState::Plane(player)));
                         }
                     }
@@ -429,6 +563,8 @@ pub fn fire(
                 exhaust_clip: def.exhaust_clip.as_ref().map(|it| {
                     SpriteClipState::new(it)
                 }),
+                pos_prev: trans.pos,
+                angle_prev: rot.angle,
             })
         }
         CannonBarrel::HitScan(hit_scan) => {
@@ -442,6 +578,7 @@ pub fn fire(
                     pos: trans.pos,
                     source: owner,
                     damage: hit_scan.action.damage.clone(),
+                    damage_kind: hit_scan.action.damage_kind,
                     hit_sound: None,
                     collider: Collider::Ray(CollisionRay {
                         origin: trans.pos,
@@ -450,7 +587,8 @@ pub fn fire(
                         thickness: hit_scan.action.collider_thickness,
                     }),
                 },
-                reachable_mobs,
+                colliders,
+                rng,
             );
             match &hit_scan.look {
                 HitScanLook::None => {}
@@ -486,13 +624,21 @@ pub fn fire(
     }
 }
 
+pub fn interpolated_trans(state: &GameState, projectile: &ProjectileState) -> (Vec2, Angle) {
+    (
+        interpolation::lerp_pos(projectile.pos_prev, projectile.trans.pos, state.tick_alpha, state.location.size),
+        interpolation::lerp_angle(projectile.angle_prev, projectile.rot.angle, state.tick_alpha),
+    )
+}
+
 pub fn draw_projectiles(state: &GameState, view_port: &ViewPort) {
     for projectile in &state.projectiles {
         if let Some(sprite) = &projectile.def.body.sprite {
-            view_port.port(projectile.trans.pos, 1.0, |ported| {
+            let (pos, angle) = interpolated_trans(state, projectile);
+            view_port.port(pos, 1.0, |ported| {
                 draw_sprite(sprite, ported.screen_pos, |it| {
                     it.screen_scale = ported.screen_scale;
-                    it.angle = projectile.rot.angle;
+                    it.angle = angle;
                     if let Some(pulse) = &projectile.def.pulsation {
                         it.screen_scale *= pulse.scale.random();
                     }
@@ -528,3 +674,92 @@ pub fn draw_projectiles(state: &GameState, view_port: &ViewPort) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::common::curve::Curve;
+    use crate::game::cannon::{cool_down, heat_up, is_spared};
+    use crate::model::def::{DamageKind, SplashDamage};
+    use crate::model::state::{DamageTarget, WeaponOwner};
+    use crate::model::state::tests::{mob, plane};
+
+    fn splash(friendly_fire: bool, self_damage: bool) -> SplashDamage {
+        SplashDamage {
+            damage: Curve::new([10.0]),
+            damage_kind: DamageKind::Explosive,
+            radius: 100.0,
+            damage_factor_by_distance_norm: Curve::new([1.0, 0.0]),
+            friendly_fire,
+            self_damage,
+        }
+    }
+
+    #[test]
+    fn shooter_spared_unless_self_damage() {
+        let target = DamageTarget::Plane(plane(1));
+        assert!(is_spared(&splash(true, false), WeaponOwner::Plane(plane(1)), target));
+        assert!(!is_spared(&splash(false, true), WeaponOwner::Plane(plane(1)), target));
+    }
+
+    #[test]
+    fn friendly_plane_spared_unless_friendly_fire() {
+        let target = DamageTarget::Plane(plane(2));
+        assert!(is_spared(&splash(false, true), WeaponOwner::Plane(plane(1)), target));
+        assert!(!is_spared(&splash(true, false), WeaponOwner::Plane(plane(1)), target));
+    }
+
+    #[test]
+    fn mobs_spared_unless_friendly_fire() {
+        let target = DamageTarget::Mob(mob(1));
+        assert!(is_spared(&splash(false, false), WeaponOwner::Mob, target));
+        assert!(!is_spared(&splash(true, false), WeaponOwner::Mob, target));
+    }
+
+    #[test]
+    fn enemies_and_environment_always_hit() {
+        let policy = splash(false, false);
+        assert!(!is_spared(&policy, WeaponOwner::Mob, DamageTarget::Plane(plane(1))));
+        assert!(!is_spared(&policy, WeaponOwner::Plane(plane(1)), DamageTarget::Mob(mob(1))));
+        assert!(!is_spared(&policy, WeaponOwner::Environment, DamageTarget::Plane(plane(1))));
+        assert!(!is_spared(&policy, WeaponOwner::Environment, DamageTarget::Mob(mob(1))));
+    }
+
+    #[test]
+    fn heat_accumulates_per_shot() {
+        let (mut heat, mut overheat) = (0.0, 0.0);
+        for _ in 0..3 {
+            assert!(heat_up(&mut heat, &mut overheat, 0.25, 2.0));
+        }
+        assert!((heat - 0.75).abs() < 0.0001);
+        assert_eq!(0.0, overheat);
+    }
+
+    #[test]
+    fn full_heat_locks_the_weapon() {
+        let (mut heat, mut overheat) = (0.9, 0.0);
+        assert!(heat_up(&mut heat, &mut overheat, 0.25, 2.0));
+        assert_eq!(1.0, heat);
+        assert_eq!(2.0, overheat);
+        assert!(!heat_up(&mut heat, &mut overheat, 0.25, 2.0));
+        assert_eq!(1.0, heat);
+    }
+
+    #[test]
+    fn lockout_ends_after_cooling() {
+        let (mut heat, mut overheat) = (1.0, 2.0);
+        cool_down(&mut heat, &mut overheat, 0.25, 1.0);
+        assert!((heat - 0.75).abs() < 0.0001);
+        assert!(!heat_up(&mut heat, &mut overheat, 0.25, 2.0));
+        cool_down(&mut heat, &mut overheat, 0.25, 1.0);
+        assert!(heat_up(&mut heat, &mut overheat, 0.25, 2.0));
+        assert!((heat - 0.75).abs() < 0.0001);
+    }
+
+    #[test]
+    fn heat_does_not_go_below_zero() {
+        let (mut heat, mut overheat) = (0.1, 0.0);
+        cool_down(&mut heat, &mut overheat, 0.25, 1.0);
+        assert_eq!(0.0, heat);
+        assert_eq!(0.0, overheat);
+    }
+}
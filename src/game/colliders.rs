@@ -0,0 +1,29 @@
+use crate::GameState;
+use crate::common::contract::Get;
+use crate::model::state::ColliderKey;
+
+pub const CELL_SIZE: f32 = 256.0;
+
+// should be called after everything moved, but before projectiles and loot pickup are checked against it
+pub fn update(state: &mut GameState) {
+    let colliders = &mut state.colliders;
+    colliders.clear(state.location.size.x);
+
+    for (id, plane) in state.planes.iter() {
+        colliders.insert(ColliderKey::Plane(*id), plane.trans.pos, plane.def.collision_radius);
+    }
+
+    // the rest are too far to be shot anyway
+    for id in state.reachable_mobs.iter() {
+        if let Some(mob) = state.mobs.get(id) {
+            let def = &mob.base.def;
+            let center = mob.anchor.get_pos_rel().get_abs(&state.isles) + def.collider_unscaled.center * def.scale;
+            colliders.insert(ColliderKey::Mob(*id), center, def.collider_unscaled.radius * def.scale);
+        }
+    }
+
+    for (id, loot) in state.loot.iter() {
+        let collider = &loot.def.collider_unscaled;
+        colliders.insert(ColliderKey::Loot(*id), loot.pos.get_abs(&state.isles) + collider.center, collider.radius);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{GameState, PlaneId, PlaneState};
+use crate::common::camera::ViewPort;
+use crate::common::colliders::CollisionField;
+use crate::common::contract::{Get, InsertSimple};
+use crate::common::frame::FrameCtx;
+use crate::common::interpolation;
+use crate::common::physics;
+use crate::common::pool::Pool;
+use crate::common::sprite::draw_sprite;
+use crate::game::particles;
+use crate::model::def::FragmentDamage;
+use crate::model::state::{ColliderKey, DamageTarget, DebrisState, Durable, GameCommand, WeaponOwner};
+use crate::Vec2;
+
+pub fn update(state: &mut GameState, dt: &FrameCtx) {
+    let mut i = 0;
+    while i < state.debris.len() {
+        let debris = &mut state.debris[i];
+        let explosion = debris.explosion.clone();
+        let def = &explosion.fragments[debris.fragment];
+
+        physics::apply_gravity(&mut debris.trans, &state.metrics, dt.dt);
+        physics::apply_drag(&mut debris.trans, &state.metrics, def.body.drag, dt.dt, dt.dt);
+        physics::apply_velocity(&mut debris.trans, &state.location, dt.dt);
+        physics::apply_rotation(&mut debris.rot, dt.dt);
+
+        debris.emission_queue += dt.dt * def.trail.spawn_rate;
+        while debris.emission_queue > 0.0 {
+            debris.emission_queue -= 1.0;
+            particles::emit_particles(&def.trail.emission, debris.trans.pos, None, &mut state.particles);
+        }
+
+        if let Some(remaining_seconds) = &mut debris.remaining_seconds {
+            *remaining_seconds -= dt.dt;
+        }
+        // apply_velocity keeps it inside the location, so the bottom is where it lands
+        let mut burnt = debris.remaining_seconds.map(|it| it <= 0.0).unwrap_or(false)
+            || debris.trans.pos.y >= state.location.size.y;
+
+        if let Some(damage) = &def.damage {
+            if let Some(plane_id) = find_hit(&state.colliders, &state.planes, debris.trans.pos, damage) {
+                state.commands.insert_simple(GameCommand::Damage {
+                    target: DamageTarget::Plane(plane_id),
+                    amount: damage.amount,
+                    kind: damage.kind,
+                    source: WeaponOwner::Environment,
+                });
+                burnt = true;
+            }
+        }
+
+        if burnt {
+            state.debris.swap_remove(i);
+        } else {
+            i += 1;
+        }
+    }
+}
+
+fn find_hit(colliders: &CollisionField<ColliderKey>, planes: &Pool<PlaneId, PlaneState>, pos: Vec2, damage: &FragmentDamage) -> Option<PlaneId> {
+    colliders.query_circle(pos, damage.radius).into_iter().find_map(|candidate| {
+        let id = match candidate.key {
+            ColliderKey::Plane(id) => id,
+            ColliderKey::Mob(_) | ColliderKey::Loot(_) => return None,
+        };
+        let plane = planes.get(&id)?;
+        if !matches!(plane.durable, Durable::Good { .. }) {
+            return None;
+        }
+        let distance = (plane.trans.pos + candidate.offset).distance(pos);
+        (distance < plane.def.collision_radius + damage.radius).then(|| id)
+    })
+}
+
+pub fn draw(state: &GameState, view_port: &ViewPort) {
+    for debris in &state.debris {
+        if let Some(sprite) = &debris.def().body.sprite {
+            let pos = interpolation::lerp_pos(debris.pos_prev, debris.trans.pos, state.tick_alpha, state.location.size);
+            let angle = interpolation::lerp_angle(debris.angle_prev, debris.rot.angle, state.tick_alpha);
+            view_port.port(pos, 1.0, |ported| {
+                draw_sprite(sprite, ported.screen_pos, |it| {
+                    it.screen_scale = ported.screen_scale;
+                    it.angle = angle;
+                });
+            });
+        }
+    }
+}
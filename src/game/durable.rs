@@ -4,7 +4,7 @@ use crate::common::frame::FrameCtx;
 use crate::common::resource::Resource;
 use crate::common::sound::PlaySound;
 use crate::GameState;
-use crate::model::def::MaterialInstance;
+use crate::model::def::{DamageKind, MaterialInstance, Resistances};
 use crate::model::state::{Durable, WeaponOwner};
 use crate::resources::constants::PAIN_SECONDS;
 
@@ -14,19 +14,27 @@ impl Durable {
         Durable::Good { hp, hp_prev: 0.0, pain_remaining_seconds: 0.0 }
     }
 
-    pub fn accept_damage(&mut self, damage: f32, offender: WeaponOwner) {
+    // returns the damage applied after resistances, None if already destroyed
+    pub fn accept_damage(&mut self, damage: f32, kind: DamageKind, resistances: &Resistances, offender: WeaponOwner) -> Option<f32> {
+        let damage = resist(damage, kind, resistances);
         match self {
             Durable::Good { ref mut hp, .. } => {
                 *hp -= damage;
                 if *hp < 0.0 {
                     *self = Durable::Destroyed(offender)
                 }
+                Some(damage)
             },
-            Durable::Destroyed(_) => {}
+            Durable::Destroyed(_) => None,
         }
     }
 }
 
+// full immunity at most, damage never heals
+fn resist(damage: f32, kind: DamageKind, resistances: &Resistances) -> f32 {
+    damage * (1.0 - resistances.get(&kind).copied().unwrap_or(0.0).min(1.0))
+}
+
 pub fn update(state: &mut GameState, dt: &FrameCtx) {
     for mob_id in state.reachable_mobs.iter() {
         if let Some(mob) = state.mobs.get_mut(mob_id) {
@@ -67,3 +75,42 @@ pub fn pain_option(state: &GameState, durable: &Durable) -> Option<Resource<Mate
         Durable::Destroyed(_) => None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::game::durable::resist;
+    use crate::model::def::{DamageKind, Resistances};
+    use crate::model::state::{Durable, WeaponOwner};
+
+    fn resistances() -> Resistances {
+        HashMap::from([(DamageKind::Kinetic, 0.5), (DamageKind::Energy, -0.5)])
+    }
+
+    #[test]
+    fn resistance_absorbs_and_weakness_amplifies() {
+        assert_eq!(10.0, resist(20.0, DamageKind::Kinetic, &resistances()));
+        assert_eq!(30.0, resist(20.0, DamageKind::Energy, &resistances()));
+        assert_eq!(20.0, resist(20.0, DamageKind::Fire, &resistances()));
+    }
+
+    #[test]
+    fn resistance_never_heals() {
+        let resistances = HashMap::from([(DamageKind::Kinetic, 1.5)]);
+        assert_eq!(0.0, resist(20.0, DamageKind::Kinetic, &resistances));
+    }
+
+    #[test]
+    fn lethal_damage_destroys() {
+        let mut durable = Durable::new(10.0);
+        assert_eq!(Some(30.0), durable.accept_damage(20.0, DamageKind::Energy, &resistances(), WeaponOwner::Environment));
+        assert!(matches!(durable, Durable::Destroyed(WeaponOwner::Environment)));
+    }
+
+    #[test]
+    fn destroyed_accepts_nothing() {
+        let mut durable = Durable::Destroyed(WeaponOwner::Mob);
+        assert_eq!(None, durable.accept_damage(20.0, DamageKind::Kinetic, &resistances(), WeaponOwner::Environment));
+        assert!(matches!(durable, Durable::Destroyed(WeaponOwner::Mob)));
+    }
+}
@@ -0,0 +1,24 @@
+use crate::GameState;
+use crate::game::{loot, progression, rpg, sounds, stats};
+use crate::game::ui::notifications;
+use crate::model::state::GameEvent;
+
+// Reactions to gameplay events. Code that detects things only publishes events,
+// so new features are hooked in here instead of core loops.
+const SUBSCRIBERS: &[fn(&mut GameState, &GameEvent)] = &[
+    loot::on_event,
+    rpg::on_event,
+    progression::on_event,
+    stats::on_event,
+    sounds::on_event,
+    notifications::on_event,
+];
+
+pub fn dispatch(state: &mut GameState) {
+    // subscribers may publish more events (kill gives XP, XP gives level), so they are handled in the same tick
+    while let Some(event) = state.events.pop_front() {
+        for subscriber in SUBSCRIBERS {
+            subscriber(state, &event);
+        }
+    }
+}
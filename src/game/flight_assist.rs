@@ -0,0 +1,118 @@
+use std::f32::consts::PI;
+use crate::common::angle::{Angle, AsRadians};
+use crate::model::def::FlightAssist;
+use crate::model::state::{AssistLevel, PlaneState, RotState};
+
+// Layer between player controls (desired_rot) and physics::apply_steering.
+// apply_steering chases the target angle with limited acceleration and tends to overshoot,
+// so the target is moved back by the current angular velocity (PD controller).
+
+pub fn assisted_rot(level: AssistLevel, plane: &mut PlaneState) -> Angle {
+    let def = &plane.def.assist;
+    plane.stall_recovery = is_stall_recovery(level, def, plane.trans.velocity.length(), plane.stall_recovery);
+    let target = if plane.stall_recovery {
+        recovery_target(def, plane.rot.angle)
+    } else {
+        plane.desired_rot
+    };
+    steer_to(level, def, &plane.rot, target)
+}
+
+// once started, recovery goes on until a higher speed, so it doesn't flicker at the stall speed
+fn is_stall_recovery(level: AssistLevel, def: &FlightAssist, speed: f32, recovering: bool) -> bool {
+    match level {
+        AssistLevel::Full if recovering => speed < def.recovery_speed,
+        AssistLevel::Full => speed < def.stall_speed,
+        AssistLevel::Off | AssistLevel::Damping => false,
+    }
+}
+
+// keep the direction, but dive to regain speed
+fn recovery_target(def: &FlightAssist, angle: Angle) -> Angle {
+    if angle.to_vec2_norm().x >= 0.0 {
+        def.recovery_dive
+    } else {
+        PI.as_radians() - def.recovery_dive
+    }
+}
+
+fn steer_to(level: AssistLevel, def: &FlightAssist, rot: &RotState, target: Angle) -> Angle {
+    match level {
+        AssistLevel::Off => target,
+        AssistLevel::Damping | AssistLevel::Full => {
+            let error = rot.angle.to_vec2_norm().angle_between(target.to_vec2_norm());
+            let corrected = (def.kp * error - def.kd * rot.ang_velocity_rad).clamp(-PI, PI);
+            (rot.angle + corrected.as_radians()).normalize()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+    use crate::common::angle::{Angle, AsRadians};
+    use crate::game::flight_assist::{is_stall_recovery, recovery_target, steer_to};
+    use crate::model::def::FlightAssist;
+    use crate::model::state::{AssistLevel, RotState};
+
+    fn assist() -> FlightAssist {
+        FlightAssist {
+            kp: 1.0,
+            kd: 0.5,
+            stall_speed: 200.0,
+            recovery_speed: 400.0,
+            recovery_dive: Angle::degrees(45.0),
+        }
+    }
+
+    fn rot(angle: f32, ang_velocity_rad: f32) -> RotState {
+        RotState { angle: angle.as_radians(), ang_velocity_rad }
+    }
+
+    #[test]
+    fn off_passes_target_through() {
+        let target = 1.0_f32.as_radians();
+        assert_eq!(target, steer_to(AssistLevel::Off, &assist(), &rot(0.0, 3.0), target));
+    }
+
+    #[test]
+    fn proportional_part_without_rotation() {
+        let it = steer_to(AssistLevel::Damping, &assist(), &rot(0.0, 0.0), 0.5_f32.as_radians());
+        assert!((it.to_rad() - 0.5).abs() < 0.0001);
+    }
+
+    #[test]
+    fn derivative_part_holds_back_rotation() {
+        // already turning to the target at 1 rad/s, so the target is moved back by kd * 1
+        let it = steer_to(AssistLevel::Damping, &assist(), &rot(0.0, 1.0), 0.5_f32.as_radians());
+        assert!(it.to_rad().abs() < 0.0001);
+    }
+
+    #[test]
+    fn output_is_limited_to_half_turn() {
+        let it = steer_to(AssistLevel::Full, &assist(), &rot(0.0, -10.0), 0.0_f32.as_radians());
+        assert!((it.to_rad().abs() - PI).abs() < 0.0001);
+    }
+
+    #[test]
+    fn stall_recovery_has_hysteresis() {
+        let def = assist();
+        assert!(!is_stall_recovery(AssistLevel::Full, &def, 300.0, false));
+        assert!(is_stall_recovery(AssistLevel::Full, &def, 100.0, false));
+        assert!(is_stall_recovery(AssistLevel::Full, &def, 300.0, true));
+        assert!(!is_stall_recovery(AssistLevel::Full, &def, 500.0, true));
+    }
+
+    #[test]
+    fn only_full_assist_recovers() {
+        assert!(!is_stall_recovery(AssistLevel::Damping, &assist(), 0.0, true));
+        assert!(!is_stall_recovery(AssistLevel::Off, &assist(), 0.0, true));
+    }
+
+    #[test]
+    fn recovery_dives_keeping_direction() {
+        let def = assist();
+        assert_eq!(def.recovery_dive, recovery_target(&def, 0.2_f32.as_radians()));
+        assert!((recovery_target(&def, (PI - 0.2).as_radians()).to_rad() - PI * 0.75).abs() < 0.0001);
+    }
+}
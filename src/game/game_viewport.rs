@@ -1,6 +1,7 @@
 use macroquad::prelude::{screen_height, screen_width};
 use macroquad::math::Rect;
 use crate::common::camera::ViewPort;
+use crate::common::interpolation;
 use crate::{GameState, Vec2};
 use crate::common::unsorted::RectExtOps;
 use crate::resources::constants::LOGIC_RESOLUTION;
@@ -8,12 +9,20 @@ use crate::resources::constants::LOGIC_RESOLUTION;
 const BOUNDS_MARGIN: f32 = 1.0;
 
 pub fn create_viewport(state: &GameState) -> ViewPort {
-    let (sw, sh) = LOGIC_RESOLUTION;
-
-    let screen_size = Vec2::new(
+    create_viewport_for_screen(state, Vec2::new(
         screen_width(),
         screen_height(),
-    );
+    ))
+}
+
+// doesn't touch the window, so it's usable when game is simulated without display
+pub fn create_virtual_viewport(state: &GameState) -> ViewPort {
+    let (sw, sh) = LOGIC_RESOLUTION;
+    create_viewport_for_screen(state, Vec2::new(sw, sh))
+}
+
+pub fn create_viewport_for_screen(state: &GameState, screen_size: Vec2) -> ViewPort {
+    let (sw, sh) = LOGIC_RESOLUTION;
 
     let view_scale = (screen_size.x / sw).max(screen_size.y / sh);
 
@@ -26,20 +35,22 @@ pub fn create_viewport(state: &GameState) -> ViewPort {
 
     let loc_size = state.location.size;
 
+    let camera_pos = interpolation::lerp_pos(state.player.camera_pos_prev, state.player.camera_pos, state.tick_alpha, loc_size);
+
     let left_pass = bounds.overlaps(&Rect::new(-loc_size.x, 0.0, loc_size.x, loc_size.y)
-        .offset(-state.player.camera_pos)
+        .offset(-camera_pos)
         .scale_with_pos(view_scale)
         .offset(screen_size * 0.5)
     );
 
     let right_pass = bounds.overlaps(&Rect::new(loc_size.x, 0.0, loc_size.x, loc_size.y)
-        .offset(-state.player.camera_pos)
+        .offset(-camera_pos)
         .scale_with_pos(view_scale)
         .offset(screen_size * 0.5)
     );
 
     let view_port = ViewPort {
-        camera_pos: state.player.camera_pos,
+        camera_pos,
         screen_size,
         view_scale,
         bounds,
@@ -4,7 +4,6 @@ use crate::game::{isles, mobs};
 use crate::{f32, GameState, Vec2};
 use crate::common::contract::{GetMut, Insert};
 use crate::common::curve::Curve;
-use crate::common::unsorted::gen_range;
 use crate::model::def::{IsleSpawn, MobKind, MobRank};
 use crate::model::state::{IsleState, MobMission, RelativePos, TransState};
 
@@ -15,7 +14,7 @@ pub fn generate(state: &mut GameState, generator: &LocationGenerator001) {
             let group_pos = generate_archipelago_pos(state, archipelago, &mut archipelago_positions);
             let mut isle_positions = vec![];
             for spawn in &archipelago.isles {
-                for _ in 0..spawn.count.random() {
+                for _ in 0..spawn.count.random_by(&mut state.rng) {
                     let isle_pos = generate_isle_pos(state, archipelago, group_pos, &mut isle_positions);
                     let isle_id = state.isles.insert(IsleState {
                         order: isle_pos.y as i32,
@@ -29,16 +28,17 @@ pub fn generate(state: &mut GameState, generator: &LocationGenerator001) {
                         course: Vec2::ONE,
                         guard_count_threshold: 0,
                         guard_rank: Default::default(),
+                        pos_prev: isle_pos,
                     });
 
                     let isle = state.isles.get_mut(&isle_id).unwrap();
                     let mut ranks = HashSet::new();
 
                     for mob_spawn in &spawn.mobs {
-                        for _ in 0..mob_spawn.count.random() {
+                        for _ in 0..mob_spawn.count.random_by(&mut state.rng) {
                             let pos = match mob_spawn.mob.kind {
                                 MobKind::Walker => RelativePos::Isle(isle_id, Vec2::new(
-                                    gen_range(spawn.isle.bounds.clone()),
+                                    state.rng.gen_range(spawn.isle.bounds.clone()),
                                     0.0,
                                 )),
                                 MobKind::Flyer => RelativePos::Global(isle.trans.pos)
@@ -49,6 +49,7 @@ pub fn generate(state: &mut GameState, generator: &LocationGenerator001) {
                                 pos,
                                 MobMission::IsleGuard(isle_id),
                                 &mut state.gids,
+                                &mut state.rng,
                             );
                             isle.guard_count_threshold += 1;
                             ranks.insert(mob_spawn.mob.rank);
@@ -63,12 +64,12 @@ pub fn generate(state: &mut GameState, generator: &LocationGenerator001) {
     }
 }
 
-fn generate_archipelago_pos(state: &GameState, archipelago: &ArchipelagoSpawn, archipelagos: &mut Vec<Vec2>) -> Vec2 {
+fn generate_archipelago_pos(state: &mut GameState, archipelago: &ArchipelagoSpawn, archipelagos: &mut Vec<Vec2>) -> Vec2 {
     let max_attempts = 100;
     for _ in 0..max_attempts {
         let pos = Vec2::new(
-            gen_range(0.0..state.location.size.x),
-            archipelago.height_normal.random() * state.location.size.y,
+            state.rng.gen_range(0.0..state.location.size.x),
+            archipelago.height_normal.random_by(&mut state.rng) * state.location.size.y,
         );
         let has_clashes = archipelagos.iter()
             .any(|it| {
@@ -83,13 +84,13 @@ fn generate_archipelago_pos(state: &GameState, archipelago: &ArchipelagoSpawn, a
     panic!("failed to find archipelago pos after {} attempts", max_attempts)
 }
 
-fn generate_isle_pos(state: &GameState, archipelago: &ArchipelagoSpawn, group_pos: Vec2, isle_positions: &mut Vec<Vec2>) -> Vec2 {
+fn generate_isle_pos(state: &mut GameState, archipelago: &ArchipelagoSpawn, group_pos: Vec2, isle_positions: &mut Vec<Vec2>) -> Vec2 {
     let max_attempts = 100;
     let isle_size = Vec2::new(300.0, 200.0);
     for _ in 0..max_attempts {
         let pos = group_pos + Vec2::new(
-            gen_range(-0.5..0.5) * archipelago.size.x,
-            gen_range(-0.5..0.5) * archipelago.size.y,
+            state.rng.gen_range(-0.5..0.5) * archipelago.size.x,
+            state.rng.gen_range(-0.5..0.5) * archipelago.size.y,
         );
         let isles_nearby = isle_positions.iter().any(|it| {
             let distance = (*it - pos).abs();
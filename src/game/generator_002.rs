@@ -2,11 +2,9 @@ use std::collections::HashSet;
 use std::ops::Not;
 use macroquad::logging::info;
 use macroquad::prelude::Vec2;
-use macroquad::rand::{ChooseRandom};
 use crate::common::contract::{GetMut, Insert};
 use crate::common::curve::Curve;
 use crate::common::resource::Resource;
-use crate::common::unsorted::gen_range;
 use crate::game::mobs;
 use crate::GameState;
 use crate::model::def::{Isle, Mob, MobKind, MobRank};
@@ -44,12 +42,12 @@ pub fn generate(state: &mut GameState, generator: &LocationGenerator002) {
         let mut mobs: Vec<Resource<Mob>> = layer.mobs.iter()
             .flat_map(|it| (0..it.count).map(|_| it.mob.clone()))
             .collect();
-        mobs.shuffle();
+        state.rng.shuffle(&mut mobs);
         let mut isles = vec![];
         {
             let mut remaining_mobs = mobs.as_slice();
             while remaining_mobs.is_empty().not() {
-                let n = layer.mob_per_isle.random().round() as usize;
+                let n = layer.mob_per_isle.random_by(&mut state.rng).round() as usize;
                 let (rem, isle) = remaining_mobs.split_at(remaining_mobs.len().saturating_sub(n));
                 remaining_mobs = rem;
                 isles.push(isle);
@@ -59,7 +57,7 @@ pub fn generate(state: &mut GameState, generator: &LocationGenerator002) {
         {
             let mut remaining_isles = isles.as_slice();
             while remaining_isles.is_empty().not() {
-                let n = layer.isles_per_archipelago.random().round() as usize;
+                let n = layer.isles_per_archipelago.random_by(&mut state.rng).round() as usize;
                 let (rem, archipelago) = remaining_isles.split_at(remaining_isles.len().saturating_sub(n));
                 remaining_isles = rem;
                 archipelagos.push(archipelago);
@@ -79,7 +77,7 @@ fn place_objects(state: &mut GameState, archipelagos: Vec<&[&[Resource<Mob>]]>,
                 if let Some(isle_pos) = generate_isle_pos(state, group_pos, group_size, &mut isle_positions) {
                     let isle_id = state.isles.insert(IsleState {
                         order: isle_pos.y as i32,
-                        def: layer.isles.choose().unwrap().clone(),
+                        def: state.rng.choose(&layer.isles).unwrap().clone(),
                         trans: TransState {
                             pos: isle_pos,
                             velocity: Vec2::ZERO,
@@ -89,13 +87,14 @@ fn place_objects(state: &mut GameState, archipelagos: Vec<&[&[Resource<Mob>]]>,
                         course: Vec2::ONE,
                         guard_count_threshold: 0,
                         guard_rank: Default::default(),
+                        pos_prev: isle_pos,
                     });
                     let isle = state.isles.get_mut(&isle_id).unwrap();
                     let mut ranks = HashSet::new();
                     for mob in mobs.iter() {
                         let pos = match mob.kind {
                             MobKind::Walker => RelativePos::Isle(isle_id, Vec2::new(
-                                gen_range(isle.def.bounds.clone()),
+                                state.rng.gen_range(isle.def.bounds.clone()),
                                 0.0,
                             )),
                             MobKind::Flyer => RelativePos::Global(isle.trans.pos)
@@ -106,6 +105,7 @@ fn place_objects(state: &mut GameState, archipelagos: Vec<&[&[Resource<Mob>]]>,
                             pos,
                             MobMission::IsleGuard(isle_id),
                             &mut state.gids,
+                            &mut state.rng,
                         );
                         isle.guard_count_threshold += 1;
                         ranks.insert(mob.rank);
@@ -119,12 +119,12 @@ fn place_objects(state: &mut GameState, archipelagos: Vec<&[&[Resource<Mob>]]>,
     }
 }
 
-fn generate_archipelago_pos(state: &GameState, height_normal: &Curve<f32>, x_normal: &Curve<f32>, size: Vec2, archipelagos: &mut Vec<Vec2>) -> Option<Vec2> {
+fn generate_archipelago_pos(state: &mut GameState, height_normal: &Curve<f32>, x_normal: &Curve<f32>, size: Vec2, archipelagos: &mut Vec<Vec2>) -> Option<Vec2> {
     let max_attempts = 300;
     for _ in 0..max_attempts {
         let pos = Vec2::new(
-            x_normal.random() * state.location.size.x,
-            height_normal.random() * state.location.size.y,
+            x_normal.random_by(&mut state.rng) * state.location.size.x,
+            height_normal.random_by(&mut state.rng) * state.location.size.y,
         );
         let has_clashes = archipelagos.iter()
             .any(|it| {
@@ -144,12 +144,12 @@ fn generate_archipelago_pos(state: &GameState, height_normal: &Curve<f32>, x_nor
     None
 }
 
-fn generate_isle_pos(state: &GameState, group_pos: Vec2, group_size: Vec2, isle_positions: &mut Vec<Vec2>) -> Option<Vec2> {
+fn generate_isle_pos(state: &mut GameState, group_pos: Vec2, group_size: Vec2, isle_positions: &mut Vec<Vec2>) -> Option<Vec2> {
     let max_attempts = 100;
     for _ in 0..max_attempts {
         let pos = group_pos + Vec2::new(
-            gen_range(-0.5..0.5) * group_size.x,
-            gen_range(-0.5..0.5) * group_size.y,
+            state.rng.gen_range(-0.5..0.5) * group_size.x,
+            state.rng.gen_range(-0.5..0.5) * group_size.y,
         );
         let isles_nearby = isle_positions.iter().any(|it| {
             let distance = (*it - pos).abs();
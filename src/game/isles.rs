@@ -1,18 +1,21 @@
+use std::cmp::Ordering;
 use std::f32::consts::PI;
 use std::ops::{Add, Mul};
 use std::rc::Rc;
 
-use crate::{GameState, info, Vec2};
+use crate::{GameState, info, PlaneState, Vec2};
 use crate::common::angle::Angle;
 use crate::common::camera::ViewPort;
 use crate::common::contract::{GetMut, Insert, InsertSimple};
 use crate::common::frame::FrameCtx;
-use crate::common::physics;
+use crate::common::interpolation;
+use crate::common::pool::Pool;
+use crate::common::{physics, sprite, toroidal};
 use crate::common::sprite::draw_sprite;
-use crate::common::unsorted::gen_range;
 use crate::game::{generator_001, generator_002, mobs};
-use crate::model::def::{LocationContent, Mob, MobAnimation, MobKind, MobSpawn};
-use crate::model::state::{FixedSpriteClipState, IsleState, MobMission, MobPhase, RelativePos, TransState};
+use crate::model::def::{DamageKind, LocationContent, Mob, MobAnimation, MobKind, MobSpawn};
+use crate::model::state::{DamageTarget, Durable, FixedSpriteClipState, GameCommand, IsleId, IsleState, MobId, MobState, MobMission, MobPhase, RelativePos, ShopWindow, TransState, UiWindow, WeaponOwner};
+use crate::resources::constants::{CRASH_DAMAGE_PER_SPEED, CRASH_SAFE_SPEED, DOCK_BRAKING, DOCK_ENERGY_PER_SEC, DOCK_MAX_SPEED, DOCK_MAX_TILT, DOCK_REPAIR_HP_PER_SEC, ISLE_BOUNCE};
 
 pub fn init_isles(state: &mut GameState) {
     match &state.location.clone().content {
@@ -24,26 +27,177 @@ pub fn init_isles(state: &mut GameState) {
 
 pub fn draw(state: &GameState, vp: &ViewPort) {
     for (_, isle) in state.isles.iter() {
-        draw_isle(&isle, vp);
+        draw_isle(state, &isle, vp);
     }
 }
 
-pub fn draw_isle(isle: &IsleState, vp: &ViewPort) {
-    vp.port(isle.trans.pos, isle.def.scale, |ported| {
+pub fn interpolated_pos(state: &GameState, isle: &IsleState) -> Vec2 {
+    interpolation::lerp_pos(isle.pos_prev, isle.trans.pos, state.tick_alpha, state.location.size)
+}
+
+pub fn draw_isle(state: &GameState, isle: &IsleState, vp: &ViewPort) {
+    vp.port(interpolated_pos(state, isle), isle.def.scale, |ported| {
         draw_sprite(&isle.def.sprite, ported.screen_pos, |it| it.screen_scale = ported.screen_scale);
     });
 }
 
+struct IsleContact {
+    // plane position relative to the isle
+    offset: Vec2,
+    normal: Vec2,
+    penetration: f32,
+}
+
+fn find_contact(isle: &IsleState, plane: &PlaneState, location_size: Vec2) -> Option<IsleContact> {
+    let offset = toroidal::delta(isle.trans.pos, plane.trans.pos, location_size);
+    if offset.length() > sprite::world_collision_bound(&isle.def.sprite, isle.def.scale) + plane.def.collision_radius {
+        return None;
+    }
+    // the deepest circle decides, otherwise neighbour cells push the plane in different directions
+    let (d, penetration) = sprite::world_collision_circles(&isle.def.sprite, isle.def.scale)
+        .map(|circle| {
+            let d = offset - circle.center;
+            (d, circle.radius + plane.def.collision_radius - d.length())
+        })
+        .filter(|(_, penetration)| *penetration > 0.0)
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))?;
+    Some(IsleContact {
+        offset,
+        normal: d.try_normalize().unwrap_or(Vec2::new(0.0, -1.0)),
+        penetration,
+    })
+}
+
+// every plane is pushed out of isles and bounces off them, crashing if too fast.
+// the player plane that docked this tick just rests on the deck instead
+pub fn collide_planes(state: &mut GameState, dt: &FrameCtx) {
+    dock_player(state, dt);
+    for (plane_id, plane) in state.planes.iter_mut() {
+        if !matches!(plane.durable, Durable::Good { .. }) {
+            continue;
+        }
+        let docked = state.player.docked.filter(|_| state.player.plane == Some(*plane_id));
+        for (isle_id, isle) in state.isles.iter() {
+            let contact = match find_contact(isle, plane, state.location.size) {
+                Some(it) => it,
+                None => continue,
+            };
+            plane.trans.pos += contact.normal * contact.penetration;
+            let relative = plane.trans.velocity - isle.trans.velocity;
+            let impact = -relative.dot(contact.normal);
+
+            if docked == Some(*isle_id) {
+                // no bounce, and wheels brake unless the engine is stronger
+                plane.trans.velocity += contact.normal * impact.max(0.0);
+                let rolling = plane.trans.velocity - isle.trans.velocity;
+                plane.trans.velocity -= rolling.clamp_length_max(DOCK_BRAKING * dt.dt);
+                continue;
+            }
+
+            if impact <= 0.0 {
+                continue;
+            }
+            plane.trans.velocity += contact.normal * impact * (1.0 + ISLE_BOUNCE);
+            let damage = (impact - CRASH_SAFE_SPEED) * CRASH_DAMAGE_PER_SPEED;
+            if damage > 0.0 {
+                state.commands.insert_simple(GameCommand::Damage {
+                    target: DamageTarget::Plane(*plane_id),
+                    amount: damage,
+                    kind: DamageKind::Kinetic,
+                    source: WeaponOwner::Environment,
+                });
+            }
+        }
+    }
+}
+
+// slow level touchdown on top of a cleared isle docks the player plane
+fn dock_player(state: &mut GameState, dt: &FrameCtx) {
+    let was_docked = state.player.docked.take();
+    let plane = match state.player.plane.and_then(|it| state.planes.get_mut(&it)) {
+        Some(it) => it,
+        None => return,
+    };
+    if !matches!(plane.durable, Durable::Good { .. }) {
+        return;
+    }
+    state.player.docked = state.isles.iter().find_map(|(isle_id, isle)| {
+        let contact = find_contact(isle, plane, state.location.size)?;
+        let relative = plane.trans.velocity - isle.trans.velocity;
+        let landing = is_touchdown(contact.normal, plane.rot.angle.to_vec2_norm(), relative)
+            && isle.def.bounds.contains(&contact.offset.x)
+            && !is_guarded(&state.mobs, *isle_id);
+        landing.then(|| *isle_id)
+    });
+
+    if state.player.docked.is_some() {
+        if let Durable::Good { hp, .. } = &mut plane.durable {
+            *hp = state.player.hp_max.min(*hp + DOCK_REPAIR_HP_PER_SEC * dt.dt);
+        }
+        plane.energy = state.player.energy_max.min(plane.energy + DOCK_ENERGY_PER_SEC * dt.dt);
+        let shop_open = state.player.windows.iter().any(|it| matches!(it, UiWindow::Shop(_)));
+        if was_docked.is_none() && !shop_open {
+            state.player.windows.push_back(UiWindow::Shop(ShopWindow {
+                items: state.def.shop_assortment.iter().cloned().collect(),
+            }));
+        }
+    }
+}
+
+// deck is nearly flat, nose is nearly level and the plane moves with the isle
+fn is_touchdown(normal: Vec2, nose: Vec2, relative_velocity: Vec2) -> bool {
+    -normal.y > DOCK_MAX_TILT.cos()
+        && nose.y.abs() < DOCK_MAX_TILT.sin()
+        && relative_velocity.length() < DOCK_MAX_SPEED
+}
+
+// destroyed guards are not despawned right away, and inactive ones are far from the player anyway
+fn is_guarded(mobs: &Pool<MobId, MobState>, isle_id: IsleId) -> bool {
+    mobs.iter().any(|(_, mob)| mob.active
+        && matches!(mob.base.durable, Durable::Good { .. })
+        && matches!(mob.base.mission, MobMission::IsleGuard(it) if it == isle_id))
+}
+
 pub fn update(state: &mut GameState, dt: &FrameCtx) {
     for (_, isle) in state.isles.iter_mut() {
         isle.course_seconds_remaining -= dt.dt;
         if isle.course_seconds_remaining < 0.0 {
-            isle.course_change_interval_last = isle.def.course_change_interval_seconds.random();
+            isle.course_change_interval_last = isle.def.course_change_interval_seconds.random_by(&mut state.rng);
             isle.course_seconds_remaining = isle.course_change_interval_last;
-            isle.course = Angle::random().to_vec2_norm();
+            isle.course = Angle::random_by(&mut state.rng).to_vec2_norm();
         }
         let phase = isle.course_seconds_remaining / isle.course_change_interval_last;
         isle.trans.velocity = isle.course * isle.def.drift_speed * (phase.clamp(0.0, 1.0) * 2.0 * PI - PI / 2.0).sin().mul(0.5).add(0.5);
         physics::apply_velocity(&mut isle.trans, &state.location, dt.dt);
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+    use crate::game::isles::is_touchdown;
+    use crate::resources::constants::DOCK_MAX_SPEED;
+
+    const UP: Vec2 = Vec2::new(0.0, -1.0);
+    const LEVEL: Vec2 = Vec2::new(1.0, 0.0);
+
+    #[test]
+    fn slow_level_touchdown_on_deck() {
+        assert!(is_touchdown(UP, LEVEL, Vec2::new(DOCK_MAX_SPEED * 0.5, 0.0)));
+    }
+
+    #[test]
+    fn side_and_underside_are_not_decks() {
+        assert!(!is_touchdown(Vec2::new(-1.0, 0.0), LEVEL, Vec2::ZERO));
+        assert!(!is_touchdown(Vec2::new(0.0, 1.0), LEVEL, Vec2::ZERO));
+    }
+
+    #[test]
+    fn nose_must_be_level() {
+        assert!(!is_touchdown(UP, Vec2::new(1.0, -1.0).normalize(), Vec2::ZERO));
+    }
+
+    #[test]
+    fn too_fast_bounces() {
+        assert!(!is_touchdown(UP, LEVEL, Vec2::new(DOCK_MAX_SPEED * 1.5, 0.0)));
+    }
+}
@@ -0,0 +1,198 @@
+use std::cmp::Ordering;
+use macroquad::color::{Color, RED, YELLOW};
+use macroquad::prelude::{draw_circle_lines, draw_text, Vec2};
+use crate::common::camera::ViewPort;
+use crate::common::contract::Get;
+use crate::common::pool::Pool;
+use crate::common::frame::FrameCtx;
+use crate::common::toroidal;
+use crate::game::mobs;
+use crate::GameState;
+use crate::model::def::{Cannon, CannonBarrel, ProjectileMod};
+use crate::model::state::{DeviceSpec, Durable, IsleId, IsleState, LockOnState, MobId, MobState, PlayerState};
+use crate::resources::constants::{LOCK_ON_CONE, LOCK_ON_RANGE, LOCK_ON_SECONDS};
+
+// Lock-on for player homing weapons: the best mob inside the cone ahead of the nose.
+// Missiles fired before the lock is complete fly straight.
+
+pub fn update(state: &mut GameState, dt: &FrameCtx) {
+    let plane = match state.player.plane.and_then(|it| state.planes.get(&it)) {
+        Some(plane) if has_homing_weapon(&state.player) => plane,
+        _ => {
+            state.player.lock_on = None;
+            return;
+        }
+    };
+    let nose = plane.rot.angle.to_vec2_norm();
+    let in_cone: Vec<(MobId, f32)> = state.reachable_mobs.iter()
+        .filter_map(|id| {
+            let mob = state.mobs.get(id)?;
+            if !matches!(mob.base.durable, Durable::Good { .. }) {
+                return None;
+            }
+            let delta = toroidal::delta(plane.trans.pos, center(&state.isles, mob), state.location.size);
+            score(nose, delta).map(|it| (*id, it))
+        })
+        .collect();
+    state.player.lock_on = next_lock(state.player.lock_on.take(), &in_cone, dt.dt);
+}
+
+fn center(isles: &Pool<IsleId, IsleState>, mob: &MobState) -> Vec2 {
+    mob.anchor.get_pos_rel().get_abs(isles) + mob.base.def.collider_unscaled.center * mob.base.def.scale
+}
+
+// lower is better: closer is better, but off-center targets look farther. None outside the cone
+fn score(nose: Vec2, delta: Vec2) -> Option<f32> {
+    let angle = nose.angle_between(delta).abs();
+    (delta.length() < LOCK_ON_RANGE && angle < LOCK_ON_CONE).then(|| delta.length() * (1.0 + angle / LOCK_ON_CONE))
+}
+
+// current target is kept while in the cone, otherwise the lock would jump between similar candidates
+fn next_lock(current: Option<LockOnState>, in_cone: &[(MobId, f32)], dt: f32) -> Option<LockOnState> {
+    let kept = current.filter(|lock| in_cone.iter().any(|(id, _)| *id == lock.target));
+    match kept {
+        Some(mut lock) => {
+            lock.seconds += dt;
+            Some(lock)
+        }
+        None => in_cone.iter()
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+            .map(|(target, _)| LockOnState { target: *target, seconds: 0.0 }),
+    }
+}
+
+fn has_homing_weapon(player: &PlayerState) -> bool {
+    player.equipment.iter().any(|(_, device)| match &device.spec {
+        DeviceSpec::Weapon(weapon) => is_homing(&weapon.def),
+        DeviceSpec::Booster(_) => false,
+    })
+}
+
+fn is_homing(cannon: &Cannon) -> bool {
+    match &cannon.barrel {
+        CannonBarrel::Projectile(projectile) => projectile.mods.iter().any(|it| matches!(it, ProjectileMod::Homing)),
+        CannonBarrel::HitScan(_) => false,
+    }
+}
+
+pub fn draw(state: &GameState, view_port: &ViewPort) {
+    let lock = match &state.player.lock_on {
+        Some(it) => it,
+        None => return,
+    };
+    let mob = match state.mobs.get(&lock.target) {
+        Some(it) => it,
+        None => return,
+    };
+    let center = mobs::interpolated_pos(state, mob) + mob.base.def.collider_unscaled.center * mob.base.def.scale;
+    let radius = mob.base.def.collider_unscaled.radius * mob.base.def.scale * 1.5;
+    let (color, text) = if lock.is_locked() {
+        (RED, "LOCKED".to_owned())
+    } else {
+        (YELLOW, format!("LOCK {:.1}s", LOCK_ON_SECONDS - lock.seconds))
+    };
+    view_port.port(center, 1.0, |ported| {
+        let screen_radius = radius * ported.screen_scale;
+        // closes in on the target while locking
+        let progress = (lock.seconds / LOCK_ON_SECONDS).min(1.0);
+        let r = screen_radius * (2.0 - progress);
+        draw_circle_lines(ported.screen_pos.x, ported.screen_pos.y, r, 2.0, color);
+        draw_text(&text, ported.screen_pos.x + r, ported.screen_pos.y - r, 20.0, Color::new(color.r, color.g, color.b, 0.9));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use macroquad::prelude::Vec2;
+    use crate::common::contract::{Get, GetMut};
+    use crate::common::frame::FrameCtx;
+    use crate::game::lock_on::{center, is_homing, next_lock, score, update};
+    use crate::game::rpg;
+    use crate::lifecycle::headless;
+    use crate::lifecycle::headless::tests::load_game;
+    use crate::lifecycle::update::update_command_queue;
+    use crate::model::def::ImprovementSpec;
+    use crate::model::state::{DeviceSpec, FlightSettings, GameCommand, LockOnState, ProjectileHomingState, ProjectileStateMod, WeaponOwner};
+    use crate::model::state::tests::mob;
+    use crate::resources::constants::{LOCK_ON_CONE, LOCK_ON_RANGE, LOCK_ON_SECONDS, TICK_DT};
+
+    #[test]
+    fn score_only_inside_cone_and_range() {
+        assert!(score(Vec2::X, Vec2::new(500.0, 0.0)).is_some());
+        assert!(score(Vec2::X, Vec2::new(LOCK_ON_RANGE + 1.0, 0.0)).is_none());
+        assert!(score(Vec2::X, Vec2::new(-500.0, 0.0)).is_none());
+        let off_cone = Vec2::new((LOCK_ON_CONE * 1.1).cos(), (LOCK_ON_CONE * 1.1).sin()) * 500.0;
+        assert!(score(Vec2::X, off_cone).is_none());
+    }
+
+    #[test]
+    fn off_center_looks_farther() {
+        let centered = score(Vec2::X, Vec2::new(500.0, 0.0)).unwrap();
+        let aside = Vec2::new((LOCK_ON_CONE * 0.5).cos(), (LOCK_ON_CONE * 0.5).sin()) * 500.0;
+        assert!(score(Vec2::X, aside).unwrap() > centered);
+    }
+
+    #[test]
+    fn best_candidate_is_picked() {
+        let lock = next_lock(None, &[(mob(1), 300.0), (mob(2), 100.0), (mob(3), 200.0)], 0.1).unwrap();
+        assert_eq!(mob(2), lock.target);
+        assert_eq!(0.0, lock.seconds);
+    }
+
+    #[test]
+    fn current_target_is_kept_while_in_cone() {
+        let current = LockOnState { target: mob(1), seconds: 0.5 };
+        let lock = next_lock(Some(current), &[(mob(1), 300.0), (mob(2), 100.0)], 0.1).unwrap();
+        assert_eq!(mob(1), lock.target);
+        assert!((lock.seconds - 0.6).abs() < 0.0001);
+    }
+
+    #[test]
+    fn lock_restarts_when_target_leaves_cone() {
+        let current = LockOnState { target: mob(1), seconds: 0.5 };
+        let lock = next_lock(Some(current), &[(mob(2), 100.0)], 0.1).unwrap();
+        assert_eq!(mob(2), lock.target);
+        assert_eq!(0.0, lock.seconds);
+        assert!(next_lock(Some(LockOnState { target: mob(2), seconds: 0.5 }), &[], 0.1).is_none());
+    }
+
+    #[test]
+    fn locked_missile_homes_on_mob() {
+        let game = load_game();
+        let mut state = headless::new_headless_game(&game, &game.combat, 42, FlightSettings::default());
+        let skill = *state.def.rpg.iter()
+            .find(|(_, skill)| skill.levels.iter().any(|it| matches!(&it.spec, ImprovementSpec::WeaponSkill(weapon) if is_homing(&weapon.spec))))
+            .unwrap().0;
+        state.player.rpg.skill_points += 1;
+        rpg::click_skill(&mut state, &skill);
+
+        let (mob_id, target) = state.mobs.iter().next().map(|(id, it)| (*id, center(&state.isles, it))).unwrap();
+        let plane_id = state.player.plane.unwrap();
+        let plane = state.planes.get_mut(&plane_id).unwrap();
+        plane.trans.pos = target - plane.rot.angle.to_vec2_norm() * LOCK_ON_RANGE * 0.5;
+        state.reachable_mobs = vec![mob_id];
+        let dt = FrameCtx { frame: 0, dt: TICK_DT };
+        for _ in 0..=(LOCK_ON_SECONDS / TICK_DT).ceil() as usize {
+            update(&mut state, &dt);
+        }
+        assert!(state.player.lock_on.as_ref().unwrap().is_locked());
+
+        let cannon = state.player.equipment.iter()
+            .find_map(|(_, device)| match &device.spec {
+                DeviceSpec::Weapon(weapon) if is_homing(&weapon.def) => Some(weapon.def.clone()),
+                _ => None,
+            })
+            .unwrap();
+        let plane = state.planes.get(&plane_id).unwrap();
+        state.commands.push_back(GameCommand::FireCannon {
+            bal: plane.trans.clone(),
+            rot: plane.rot.clone(),
+            owner: WeaponOwner::Plane(plane_id),
+            cannon,
+            initial_angle: plane.rot.angle,
+        });
+        update_command_queue(&mut state);
+        let missile = state.projectiles.last().unwrap();
+        assert!(matches!(missile.mods[..], [ProjectileStateMod::Homing(ProjectileHomingState::Mob(id))] if id == mob_id));
+    }
+}
@@ -9,8 +9,9 @@ use crate::common::contract::{Get, InsertSimple};
 use crate::common::curve::{Curve, Point};
 use crate::common::pool::Pool;
 use crate::common::sound::PlaySound;
+use crate::common::toroidal;
 use crate::common::unsorted::{gen_range, IndexRange};
-use crate::model::state::{LootState, IsleId, SubSystems, LootId, GameProgression, LootManager, GameCommand, MobAnchor};
+use crate::model::state::{ColliderKey, LootState, IsleId, SubSystems, LootId, GameProgression, LootManager, GameCommand, GameEvent, MobAnchor};
 
 pub enum LootPos {
     Isle(IsleId, Vec2),
@@ -46,14 +47,22 @@ pub fn draw(pos: Vec2, loot: &Loot, vp: &ViewPort) {
     });
 }
 
+// should be called after colliders are updated
 pub fn update(state: &mut GameState, ctx: &FrameCtx) {
     if let Some(player) = state.player.plane.and_then(|it| state.planes.get(&it)) {
-        let keys: Vec<LootId> = state.loot.iter().map(|(it, _)| it).copied().collect();
-        for i in keys {
+        let candidates = state.colliders.query_circle(player.trans.pos, player.def.collision_radius);
+        for candidate in candidates {
+            let i = match candidate.key {
+                ColliderKey::Loot(id) => id,
+                ColliderKey::Plane(_) | ColliderKey::Mob(_) => continue,
+            };
             if let Some(loot) = state.loot.get(&i) {
-                match update_loot(&loot.def, loot.pos.get_abs(&state.isles), player, &mut state.player, &state.subsystems) {
+                match update_loot(&loot.def, loot.pos.get_abs(&state.isles), player, state.location.size) {
                     LootUpdateResult::Left => {}
-                    LootUpdateResult::Picked => { state.loot.remove(i); }
+                    LootUpdateResult::Picked => {
+                        state.events.push_back(GameEvent::LootPicked { def: loot.def.clone() });
+                        state.loot.remove(i);
+                    }
                 }
             }
         }
@@ -65,17 +74,21 @@ enum LootUpdateResult {
     Picked,
 }
 
-fn update_loot(loot: &Loot, pos: Vec2, player_plane: &PlaneState, player_state: &mut PlayerState, settings: &SubSystems) -> LootUpdateResult {
-    if (pos + loot.collider_unscaled.center).distance(player_plane.trans.pos) < player_plane.def.collision_radius + loot.collider_unscaled.radius {
-        for item in &loot.content {
+fn update_loot(loot: &Loot, pos: Vec2, player_plane: &PlaneState, loc_size: Vec2) -> LootUpdateResult {
+    if toroidal::distance(pos + loot.collider_unscaled.center, player_plane.trans.pos, loc_size) < player_plane.def.collision_radius + loot.collider_unscaled.radius {
+        return LootUpdateResult::Picked;
+    }
+    LootUpdateResult::Left
+}
+
+pub fn on_event(state: &mut GameState, event: &GameEvent) {
+    if let GameEvent::LootPicked { def } = event {
+        for item in &def.content {
             match item {
                 Item::Resource { count, resource } => {
-                    player_state.resources.get_mut(resource).unwrap().add_assign(count);
+                    state.player.resources.get_mut(resource).unwrap().add_assign(count);
                 }
             }
         }
-        loot.pick_sound.play_once(&settings.audio);
-        return LootUpdateResult::Picked;
     }
-    LootUpdateResult::Left
 }
\ No newline at end of file
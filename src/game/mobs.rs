@@ -18,22 +18,25 @@ use crate::common::angle::Angle;
 use crate::common::camera::ViewPort;
 use crate::common::contract::{Get, GetMut, InsertSimple};
 use crate::common::frame::FrameCtx;
+use crate::common::interpolation;
 use crate::common::pool::{Pool, PoolKey};
 use crate::common::resource::Resource;
 use crate::common::sprite_clip;
 use crate::common::sprite_clip::do_nothing;
-use crate::common::unsorted::{gen_range, RangeAdd, ToAngle};
-use crate::game::{cannon, durable, isles, loot, mobs};
+use crate::common::toroidal;
+use crate::common::rng::Rng;
+use crate::common::unsorted::{RangeAdd, ToAngle};
+use crate::game::{cannon, durable, isles, loot, mobs, particles};
 use crate::game::loot::LootPos;
-use crate::model::def::{Burst, CannonPodProps, Isle, Mob, MobAnimation, MobAttack, MobAttackPattern};
-use crate::model::state::{Aim, ProjectileState, Durable, FlyingMobState, Gids, IsleId, IsleState, LootState, MobAnchor, MobBaseState, MobPhase, MobState, MoveAction, RelativePos, RotState, SubSystems, SpriteClipState, TransState, WaitAnimationEndAction, WaitSecondsAction, WalkingMobState, WeaponOwner, GameCommand, MobId, DamageTarget, MobMission, BurstState};
+use crate::model::def::{Burst, CannonPodProps, Isle, Location, Mob, MobAnimation, MobAttack, MobAttackPattern};
+use crate::model::state::{Aim, ProjectileState, Durable, FlyingMobState, Gids, IsleId, IsleState, LootState, MobAnchor, MobBaseState, MobPhase, MobState, MoveAction, RelativePos, RotState, SubSystems, SpriteClipState, TransState, WaitAnimationEndAction, WaitSecondsAction, WalkingMobState, WeaponOwner, GameCommand, GameEvent, MobId, DamageTarget, MobMission, BurstState};
 use crate::model::state::Durable::{Destroyed, Good};
 use crate::model::state::MobPhase::{WaitAnimationEnd, WaitSeconds};
 use crate::model::state::WaitAnimationEndAction::{AttackFinish, AttackWindup, Die};
 use crate::model::state::WaitSecondsAction::Charge;
 use crate::resources::constants::{ANIM_END_PHASE_TIMEOUT_SEC, FLYING_SWING_ACCELERATION, FLYING_SWING_PERIOD, GRAVITY, PAIN_SECONDS};
 
-pub fn spawn_mob<I>(pool: &mut I, def: &Resource<Mob>, pos: RelativePos, mission: MobMission, gids: &mut Gids)
+pub fn spawn_mob<I>(pool: &mut I, def: &Resource<Mob>, pos: RelativePos, mission: MobMission, gids: &mut Gids, rng: &mut Rng)
     where I: InsertSimple<MobState>
 {
     pool.insert_simple(MobState {
@@ -45,13 +48,15 @@ pub fn spawn_mob<I>(pool: &mut I, def: &Resource<Mob>, pos: RelativePos, mission
             RelativePos::Global(pos) => MobAnchor::Global(FlyingMobState {
                 extra_velocity: Vec2::ZERO,
                 pos,
-                swing_phase: gen_range(0.0..1.0),
+                swing_phase: rng.gen_range(0.0..1.0),
             }),
         },
+        pos_prev: None,
+        active: false,
     });
 }
 
-fn spawn_mob_base(def: &Resource<Mob>, mission: MobMission, gids: &mut Gids) -> MobBaseState {
+pub fn spawn_mob_base(def: &Resource<Mob>, mission: MobMission, gids: &mut Gids) -> MobBaseState {
     MobBaseState {
         gid: gids.next_gid(),
         mission,
@@ -60,7 +65,6 @@ fn spawn_mob_base(def: &Resource<Mob>, mission: MobMission, gids: &mut Gids) ->
         phase: WaitSeconds { action: Idle, seconds_remaining: 0.0 },
         animation: None,
         durable: Durable::new(def.hp),
-        xp_awarded: false,
         death_initiated: false,
         animation_ended: false,
         debug_once: false,
@@ -73,13 +77,47 @@ fn spawn_mob_base(def: &Resource<Mob>, mission: MobMission, gids: &mut Gids) ->
 }
 
 
-fn is_reachable(isles: &Pool<IsleId, IsleState>, player: &PlayerState, mob: &MobState) -> bool {
-    player.camera_pos.distance(mob.anchor.get_pos_rel().get_abs(isles)) < 2000.0
+fn update_activity(state: &mut GameState, dt: &FrameCtx) {
+    let streaming = &state.def.mob_streaming;
+    let mut vanished = vec![];
+    for (id, mob) in state.mobs.iter_mut() {
+        let pos = mob.anchor.get_pos_rel().get_abs(&state.isles);
+        let distance = toroidal::distance(state.player.camera_pos, pos, state.location.size);
+        if mob.active {
+            // death should be handled (loot dropped) before the mob falls asleep
+            let dying = matches!(mob.base.durable, Destroyed(_)) && !mob.base.death_initiated;
+            if distance > streaming.deactivation_radius && !dying {
+                mob.active = false;
+                if !settle(dt, &mut mob.base, &mut state.rng) {
+                    vanished.push(*id);
+                }
+            }
+        } else if distance < streaming.activation_radius {
+            mob.active = true;
+        }
+    }
+    for id in vanished {
+        state.mobs.remove(id);
+    }
+}
+
+// inactive mobs are frozen, so they are put into a state that stays valid for any time of sleep.
+// returns false if nothing should be left of the mob
+fn settle(dt: &FrameCtx, mob: &mut MobBaseState, rng: &mut Rng) -> bool {
+    if let Destroyed(_) = mob.durable {
+        // otherwise corpse of a flier hangs in the air until the player returns
+        return false;
+    }
+    // pending attacks are dropped, and idle time is random, so mobs woke up together don't attack at once
+    let idle_seconds = mob.def.idle_seconds.random_by(rng);
+    set_phase(dt, mob, WaitSeconds { action: Idle, seconds_remaining: idle_seconds });
+    true
 }
 
 pub fn update(state: &mut GameState, dt: &FrameCtx) {
+    update_activity(state, dt);
     state.reachable_mobs = state.mobs.iter()
-        .filter(|(_, mob)| is_reachable(&state.isles, &state.player, mob))
+        .filter(|(_, mob)| mob.active)
         .map(|(id, _)| *id)
         .to_vec();
 
@@ -122,11 +160,13 @@ pub fn update(state: &mut GameState, dt: &FrameCtx) {
                 &state.player,
                 &state.planes,
                 &state.isles,
+                &state.location,
                 &mut state.projectiles,
                 &mut state.commands,
                 mob.anchor.get_pos_rel().get_abs(&state.isles),
-                |base, planes| {
-                    delegate_ai(&state.player, planes, base, &mut mob.anchor, dt, &state.isles);
+                &mut state.rng,
+                |base, planes, rng| {
+                    delegate_ai(&state.player, planes, base, &mut mob.anchor, dt, &state.isles, &state.location, rng);
                 },
                 || dead = true,
             );
@@ -139,7 +179,15 @@ pub fn update(state: &mut GameState, dt: &FrameCtx) {
                     &mut state.commands,
                     &state.loot,
                     &mob.anchor,
-                )
+                );
+                if let Destroyed(killer) = mob.base.durable {
+                    state.events.push_back(GameEvent::MobKilled { mob: *mob_id, def: mob.base.def.clone(), killer });
+                }
+                if let Some(explosion) = &mob.base.def.explosion {
+                    let center = mob.anchor.get_pos_rel().get_abs(&state.isles) + mob.base.def.collider_unscaled.center * mob.base.def.scale;
+                    let source = TransState { pos: center, velocity: Vec2::ZERO };
+                    particles::emit_explosion(explosion, &source, &mut state.particles, &mut state.debris, &mut state.rng, &state.subsystems);
+                }
             }
 
             match &mut mob.anchor {
@@ -170,10 +218,14 @@ pub fn update(state: &mut GameState, dt: &FrameCtx) {
                                 &payload,
                                 &state.subsystems,
                                 &state.isles,
+                                &state.location,
                                 &mut state.commands,
+                                &mut state.rng,
                             );
                         }
                     }
+                    // like everything else that flies
+                    spec.pos = toroidal::wrap(spec.pos, state.location.size);
                 }
             }
         }
@@ -189,15 +241,18 @@ fn update_mob_base<F, D>(
     player: &PlayerState,
     planes: &Pool<PlaneId, PlaneState>,
     isles: &Pool<IsleId, IsleState>,
+    location: &Location,
     projectiles: &mut Vec<ProjectileState>,
     commands: &mut impl InsertSimple<GameCommand>,
     pos: Vec2,
+    rng: &mut Rng,
     mut decide: F,
     mut on_dead: D,
 ) where
     F: FnMut(
         &mut MobBaseState,
         &Pool<PlaneId, PlaneState>,
+        &mut Rng,
     ),
     D: FnMut()
 {
@@ -233,7 +288,7 @@ fn update_mob_base<F, D>(
                     LayDead => {}
                     Die => set_phase(dt, mob, WaitAnimationEnd(LayDead)),
                     AttackFinish(attack) => {
-                        set_phase(dt, mob, WaitSeconds { action: Idle, seconds_remaining: attack.cooldown_sec.random() });
+                        set_phase(dt, mob, WaitSeconds { action: Idle, seconds_remaining: attack.cooldown_sec.random_by(rng) });
                         repeat = true;
                     }
                     AttackWindup(angle, attack) => {
@@ -244,11 +299,11 @@ fn update_mob_base<F, D>(
                         repeat = true;
                     }
                     AttackDeliver(aim, attack) => {
-                        let angle = resolve_aim_angle(planes, &aim, pos);
+                        let angle = resolve_aim_angle(planes, &aim, pos, location);
                         if let Some(burst) = &attack.burst {
                             set_phase_burst(dt, mob, &aim, &attack, burst, burst.rounds_in_row);
                         } else {
-                            deliver_attack(mob, player, planes, projectiles, pos, angle, &attack, settings, isles, commands);
+                            deliver_attack(mob, player, planes, projectiles, pos, angle, &attack, settings, isles, location, commands, rng);
                             set_phase(dt, mob, WaitAnimationEnd(AttackFinish(attack.clone())));
                         }
                         repeat = true;
@@ -267,12 +322,12 @@ fn update_mob_base<F, D>(
         if act {
             if let WaitSeconds { action: phase, seconds_remaining } = &mob.phase.clone() {
                 match phase {
-                    WaitSecondsAction::Idle => decide(mob, planes),
-                    WaitSecondsAction::Move { .. } => decide(mob, planes),
+                    WaitSecondsAction::Idle => decide(mob, planes, rng),
+                    WaitSecondsAction::Move { .. } => decide(mob, planes, rng),
                     WaitSecondsAction::AttackHold(aim, attack) => {
                         let attack = attack.clone();
                         if let Some(charge) = &attack.charge {
-                            let angle = resolve_aim_angle(planes, aim, pos);
+                            let angle = resolve_aim_angle(planes, aim, pos, location);
                             mob.dir = angle.to_vec2_norm().x.signum();
                             mob.charge_spent = false;
                             set_phase(dt, mob, WaitSeconds {
@@ -282,7 +337,7 @@ fn update_mob_base<F, D>(
                             repeat = true;
                         } else {
                             let aim = aim.clone();
-                            let angle = resolve_aim_angle(planes, &aim, pos);
+                            let angle = resolve_aim_angle(planes, &aim, pos, location);
                             mob.dir = angle.to_vec2_norm().x.signum();
                             set_phase(dt, mob, WaitAnimationEnd(AttackDeliver(aim, attack)));
                             repeat = true;
@@ -294,10 +349,10 @@ fn update_mob_base<F, D>(
                         repeat = true;
                     }
                     WaitSecondsAction::AttackBurst(aim, burst) => {
-                        let angle = resolve_aim_angle(planes, aim, pos);
-                        let initial_angle = angle + Angle::degrees(burst.def.cannon.spread_degrees.random());
+                        let angle = resolve_aim_angle(planes, aim, pos, location);
+                        let initial_angle = angle + Angle::degrees(burst.def.cannon.spread_degrees.random_by(rng));
                         mob.dir = angle.to_vec2_norm().x.signum();
-                        deliver_attack(mob, player, planes, projectiles, pos, initial_angle, &burst.attack, settings, isles, commands);
+                        deliver_attack(mob, player, planes, projectiles, pos, initial_angle, &burst.attack, settings, isles, location, commands, rng);
                         if burst.remaining_rounds > 0 {
                             set_phase_burst(dt, mob, aim, &burst.attack, &burst.def, burst.remaining_rounds - 1);
                         } else {
@@ -335,19 +390,22 @@ fn deliver_attack(
     attack: &Resource<MobAttack>,
     settings: &SubSystems,
     isles: &Pool<IsleId, IsleState>,
+    location: &Location,
     commands: &mut impl InsertSimple<GameCommand>,
+    rng: &mut Rng,
 ) -> bool {
     let mut connected = false;
     match &attack.pattern {
-        MobAttackPattern::Melee { connect_range, damage } => {
+        MobAttackPattern::Melee { connect_range, damage, damage_kind } => {
             let player = player.plane.and_then(|it| planes.get(&it).map(|v| (it, v)));
             if let Some((player_plane_id, player)) = player {
                 let pos = pos + mob.def.collider_unscaled.center * mob.def.scale;
-                let dir = player.trans.pos - pos;
+                let dir = toroidal::delta(pos, player.trans.pos, location.size);
                 if (dir.x > 0.0) == (angle.to_vec2_norm().x > 0.0) && dir.length() < *connect_range {
                     commands.insert_simple(GameCommand::Damage {
                         target: DamageTarget::Plane(player_plane_id),
-                        amount: damage.random(),
+                        amount: damage.random_by(rng),
+                        kind: *damage_kind,
                         source: WeaponOwner::Mob,
                     });
                     connected = true;
@@ -383,16 +441,18 @@ fn delegate_ai(
     anchor: &mut MobAnchor,
     dt: &FrameCtx,
     isles: &impl Get<IsleId, IsleState>,
+    location: &Location,
+    rng: &mut Rng,
 ) {
     let enemies = player.plane
         .map_or_else(Vec::new, |it| vec![it]);
     let phase = match anchor {
         MobAnchor::Isle(isle_id, isle_mob) => {
             let isle = isles.get(isle_id).unwrap();
-            make_decision_walker(&isle.trans, &isle.def, &enemies[..], planes, isle_mob, base_mob)
+            make_decision_walker(&isle.trans, &isle.def, &enemies[..], planes, isle_mob, base_mob, location, rng)
         }
         MobAnchor::Global(flier_mob) => {
-            make_decision_flier(&enemies[..], planes, base_mob, flier_mob, dt)
+            make_decision_flier(&enemies[..], planes, base_mob, flier_mob, dt, location, rng)
         }
     };
     if let WaitAnimationEnd(AttackWindup(aim, _)) = &phase {
@@ -401,18 +461,18 @@ fn delegate_ai(
             MobAnchor::Isle(isle_id, spec) => &mut base_mob.dir,
             MobAnchor::Global(spec) => &mut base_mob.dir,
         };
-        *dir = resolve_aim_angle(planes, aim, pos.get_abs(isles)).to_vec2_norm().x.signum();
+        *dir = resolve_aim_angle(planes, aim, pos.get_abs(isles), location).to_vec2_norm().x.signum();
     }
     set_phase(dt, base_mob, phase);
     // set_phase(base_mob, WaitSeconds(Idle, 1.0));
 }
 
-fn resolve_aim_angle(planes: &Pool<PlaneId, PlaneState>, aim: &Aim, pos: Vec2) -> Angle {
+fn resolve_aim_angle(planes: &Pool<PlaneId, PlaneState>, aim: &Aim, pos: Vec2, location: &Location) -> Angle {
     match aim {
         Aim::Angle(angle) => *angle,
         Aim::Plane { plane, fallback } => planes
             .get(&plane)
-            .map(|it| (it.trans.pos - pos).to_angle())
+            .map(|it| toroidal::delta(pos, it.trans.pos, location.size).to_angle())
             .unwrap_or(*fallback),
     }
 }
@@ -451,9 +511,9 @@ fn resolve_animation(phase: &MobPhase) -> MobAnimation {
     }
 }
 
-fn make_decision_walker(isle_trans: &TransState, isle_def: &Isle, enemies: &[PlaneId], planes: &Pool<PlaneId, PlaneState>, state: &WalkingMobState, base_state: &MobBaseState) -> MobPhase {
+fn make_decision_walker(isle_trans: &TransState, isle_def: &Isle, enemies: &[PlaneId], planes: &Pool<PlaneId, PlaneState>, state: &WalkingMobState, base_state: &MobBaseState, location: &Location, rng: &mut Rng) -> MobPhase {
     let def = base_state.def.clone();
-    let nearest_enemy = get_nearest_enemy(enemies, planes, isle_trans.pos + state.pos_local, &def);
+    let nearest_enemy = get_nearest_enemy(enemies, planes, isle_trans.pos + state.pos_local, &def, location);
 
     if let Some((distance, dir, nearest_enemy, nearest_enemy_id)) = nearest_enemy
     {
@@ -469,30 +529,30 @@ fn make_decision_walker(isle_trans: &TransState, isle_def: &Isle, enemies: &[Pla
         }
     }
 
-    match gen_range(0.0..1.0) {
+    match rng.gen_range(0.0..1.0) {
         action_rng if action_rng < 0.3 => {
             let pos_norm = {
                 let rel_pos = state.pos_local.x - isle_def.bounds.start;
                 rel_pos / (isle_def.bounds.end - isle_def.bounds.start)
             };
-            let dir = match gen_range(0.0..1.0) {
+            let dir = match rng.gen_range(0.0..1.0) {
                 dir_rng if dir_rng < 0.5 => -1.0,
                 _ => 1.0,
             };
-            WaitSeconds { action: Move(MoveAction::IsleBound { velocity_x: def.move_speed * dir }), seconds_remaining: def.move_seconds.random() }
+            WaitSeconds { action: Move(MoveAction::IsleBound { velocity_x: def.move_speed * dir }), seconds_remaining: def.move_seconds.random_by(rng) }
         }
-        _ => WaitSeconds { action: Idle, seconds_remaining: def.idle_seconds.random() }
+        _ => WaitSeconds { action: Idle, seconds_remaining: def.idle_seconds.random_by(rng) }
     }
 }
 
-fn make_decision_flier(enemies: &[PlaneId], planes: &Pool<PlaneId, PlaneState>, base_state: &MobBaseState, flier_mob: &FlyingMobState, dt: &FrameCtx) -> MobPhase {
+fn make_decision_flier(enemies: &[PlaneId], planes: &Pool<PlaneId, PlaneState>, base_state: &MobBaseState, flier_mob: &FlyingMobState, dt: &FrameCtx, location: &Location, rng: &mut Rng) -> MobPhase {
     let def = base_state.def.clone();
-    let nearest_enemy = get_nearest_enemy(enemies, planes, flier_mob.pos, &def);
+    let nearest_enemy = get_nearest_enemy(enemies, planes, flier_mob.pos, &def, location);
 
     if let Some((distance, dir, nearest_enemy, nearest_enemy_id)) = nearest_enemy
     {
         for attack in &def.attacks {
-            if gen_range(0.0..1.0) <= base_state.def.attack_chance {
+            if rng.gen_range(0.0..1.0) <= base_state.def.attack_chance {
                 if distance <= attack.trigger_range {
                     let aim = if attack.late_aim {
                         Aim::Plane { plane: nearest_enemy_id, fallback: dir.to_angle() }
@@ -510,15 +570,15 @@ fn make_decision_flier(enemies: &[PlaneId], planes: &Pool<PlaneId, PlaneState>,
         }
     }
 
-    match gen_range(0.0..1.0) {
+    match rng.gen_range(0.0..1.0) {
         action_rng if action_rng < 0.3 => {
-            let dir = match gen_range(0.0..1.0) {
+            let dir = match rng.gen_range(0.0..1.0) {
                 dir_rng if dir_rng < 0.5 => -1.0,
                 _ => 1.0,
             };
-            WaitSeconds { action: Move(MoveAction::FreeFly { velocity: Vec2::new(def.move_speed * dir, 0.0) }), seconds_remaining: def.move_seconds.random() }
+            WaitSeconds { action: Move(MoveAction::FreeFly { velocity: Vec2::new(def.move_speed * dir, 0.0) }), seconds_remaining: def.move_seconds.random_by(rng) }
         }
-        _ => WaitSeconds { action: Idle, seconds_remaining: def.idle_seconds.random() }
+        _ => WaitSeconds { action: Idle, seconds_remaining: def.idle_seconds.random_by(rng) }
     }
 }
 
@@ -527,10 +587,11 @@ fn get_nearest_enemy<'a>(
     planes: &'a Pool<PlaneId, PlaneState>,
     pos: Vec2,
     def: &Resource<Mob>,
+    location: &Location,
 ) -> Option<(f32, Vec2, &'a PlaneState, PlaneId)> {
     enemies.iter()
         .filter_map(|id| planes.get(id).map(|it| (it, *id)))
-        .map(|(it, id)| (it.trans.pos.sub(pos + def.collider_unscaled.center * def.scale), it, id))
+        .map(|(it, id)| (toroidal::delta(pos + def.collider_unscaled.center * def.scale, it.trans.pos, location.size), it, id))
         .map(|(dir, enemy, id)| (dir.length(), dir, enemy, id))
         .min_by_key(|(distance, _, _, _)| *distance as i32)
 }
@@ -541,6 +602,14 @@ fn animation_ended(mobs: &mut Pool<MobId, MobState>, index: MobId) {
     }
 }
 
+pub fn interpolated_pos(state: &GameState, mob: &MobState) -> Vec2 {
+    let pos = mob.anchor.get_pos_rel().get_abs(&state.isles);
+    match mob.pos_prev {
+        Some(prev) => interpolation::lerp_pos(prev, pos, state.tick_alpha, state.location.size),
+        None => pos,
+    }
+}
+
 impl RelativePos {
     pub fn get_abs(self, isles: &impl Get<IsleId, IsleState>) -> Vec2 {
         match self {
@@ -4,9 +4,14 @@ pub mod sky;
 pub mod bots;
 pub mod ui;
 pub mod cannon;
+pub mod debris;
+pub mod colliders;
 pub mod mobs;
 pub mod isles;
+pub mod lock_on;
 pub mod durable;
+pub mod flight_assist;
+pub mod events;
 pub mod control_guard;
 pub mod game_viewport;
 pub mod sounds;
@@ -16,3 +21,4 @@ pub mod generator_001;
 pub mod generator_002;
 pub mod rpg;
 pub mod progression;
+pub mod stats;
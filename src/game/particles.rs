@@ -1,3 +1,5 @@
+// particles and sprite clips are cosmetic: nothing in simulation reads their positions, so they use
+// global randomness and don't disturb replays. debris collides, so it's rolled by the game Rng
 use std::f32::consts::PI;
 use std::rc::Rc;
 
@@ -7,13 +9,18 @@ use macroquad::prelude::WHITE;
 use macroquad::rand::ChooseRandom;
 
 use crate::common::angle::{Angle, AsRadians};
+use crate::common::resource::Resource;
+use crate::common::rng::Rng;
 use crate::common::sound::PlaySound;
 use crate::common::sprite;
 use crate::common::unsorted::gen_range;
 use crate::GameState;
 use crate::lifecycle::draw::Stats;
 use crate::model::def::{Explosion, ParticleEmission, SpriteClip, TrailSource};
-use crate::model::state::{AudioManager, FixedSpriteClipState, MovingSpriteClipState, ParticleEmitterState, ParticlesState, RotState, SpriteClipState, SubSystems, TransState};
+use crate::model::state::{AudioManager, DebrisState, FixedSpriteClipState, MovingSpriteClipState, ParticleEmitterState, ParticlesState, RotState, SpriteClipState, SubSystems, TransState};
+
+// per second, either direction
+const DEBRIS_SPIN_RAD: f32 = PI * 2.0;
 
 pub fn emit_particles(
     emission: &ParticleEmission,
@@ -62,7 +69,7 @@ pub fn update_particles(state: &mut ParticleEmitterState, bal: &TransState, rot:
     }
 }
 
-pub fn emit_explosion(explosion: &Explosion, source: &TransState, particles: &mut ParticlesState, settings: &SubSystems) {
+pub fn emit_explosion(explosion: &Resource<Explosion>, source: &TransState, particles: &mut ParticlesState, debris: &mut Vec<DebrisState>, rng: &mut Rng, settings: &SubSystems) {
     explosion.sound.play_once(&settings.audio);
     for emission in &explosion.particles {
         for _ in 0..emission.count.random() {
@@ -71,4 +78,23 @@ pub fn emit_explosion(explosion: &Explosion, source: &TransState, particles: &mu
                            Some(velocity * emission.speed_factor), particles)
         }
     }
+    for (i, fragment) in explosion.fragments.iter().enumerate() {
+        let dir = Angle::random_by(rng);
+        debris.push(DebrisState {
+            explosion: explosion.clone(),
+            fragment: i,
+            trans: TransState {
+                pos: source.pos,
+                velocity: source.velocity + dir.to_vec2_norm() * fragment.body.initial_speed,
+            },
+            rot: RotState {
+                angle: dir,
+                ang_velocity_rad: rng.gen_range(-DEBRIS_SPIN_RAD..DEBRIS_SPIN_RAD),
+            },
+            remaining_seconds: fragment.body.seconds_to_live,
+            emission_queue: 0.0,
+            pos_prev: source.pos,
+            angle_prev: dir,
+        });
+    }
 }
@@ -9,37 +9,42 @@ use macroquad::math::{clamp, Rect};
 use macroquad::prelude::WHITE;
 
 use crate::{Game, GameState, PlaneId, PlaneState, PlayerState};
-use crate::common::{camera, physics, sprite};
+use crate::common::{camera, physics, sprite, toroidal};
 use crate::common::angle::{Angle, AsRadians};
 use crate::common::resource::Resource;
 use crate::common::camera::ViewPort;
 use crate::common::contract::{Get, Insert, InsertSimple};
 use crate::common::curve::Lerp;
 use crate::common::frame::FrameCtx;
+use crate::common::interpolation;
 use crate::common::metrics::Metrics;
 use crate::common::pool::Pool;
+use crate::common::rng::Rng;
 use crate::common::sound::{PlaySound, SoundList, StopSound};
 use crate::common::sprite::draw_sprite;
 use crate::common::unsorted::WithMut;
-use crate::game::{cannon, durable, particles, rpg};
-use crate::model::def::{Buff, BuffSpec, Explosion, Location, Plane, Sprite, Stabilization, TrailSource, TransientBallisticBody};
-use crate::model::state::{Ammo, CannonState, DeviceSpec, DeviceState, Durable, FixedSpriteClipState, GameCommand, ManualBuffAmmo, ManualBuffState, ParticleEmitterState, ParticlesState, RotState, RpgState, TransState, WeaponOwner};
+use crate::game::{cannon, durable, flight_assist, particles, rpg};
+use crate::model::def::{Buff, BuffSpec, DamageKind, Explosion, FlightModel, Location, Plane, Sprite, Stabilization, TrailSource, TransientBallisticBody};
+use crate::model::state::{Ammo, CannonState, DeviceSpec, DeviceState, Durable, FixedSpriteClipState, GameCommand, GameEvent, ManualBuffAmmo, ManualBuffState, ParticleEmitterState, ParticlesState, RotState, RpgState, TransState, WeaponOwner};
 use crate::model::state::Durable::{Destroyed, Good};
 use crate::resources::constants::{FULL_THROTTLE_ENERGY_PER_SECOND, INITIAL_ENERGY, NOMINAL_SPEED, PLANE_THRUST_NOMINAL, SECONDS_TO_RESTORE_FULL_ENERGY};
 use crate::resources::objects::objects;
 
 pub fn allocate_plane<I: Insert<PlaneId, PlaneState>>(pool: &mut I, plane: Resource<Plane>, pos: Vec2, rot: Angle, game: &Resource<Game>, player: &PlayerState) -> PlaneId {
+    // armor upgrades are for the player's airframe only, others start with their own hp
+    let hp = if plane == game.player_plane { player.hp_max } else { plane.hp };
     let mut state = PlaneState {
         trans: TransState {
             pos,
             velocity: rot.to_vec2_norm() * NOMINAL_SPEED,
         },
-        durable: Durable::new(player.hp_max),
+        durable: Durable::new(hp),
         rot: RotState {
             angle: rot,
             ang_velocity_rad: 0.0,
         },
         desired_rot: rot,
+        stall_recovery: false,
         gear: plane.default_gear,
         def: plane.clone(),
         trail: plane.trail.as_ref().map(|trail_pod| ParticleEmitterState {
@@ -54,6 +59,8 @@ pub fn allocate_plane<I: Insert<PlaneId, PlaneState>>(pool: &mut I, plane: Resou
         effective_gear: 0,
         passive_buffs: vec![],
         effective_gear_prev: None,
+        pos_prev: pos,
+        angle_prev: rot,
     };
     pool.insert(state)
 }
@@ -70,7 +77,7 @@ pub fn update_planes(state: &mut GameState, dt: &FrameCtx) {
         if let Some(lava_damage) = &state.location.lava_damage_by_height_per_sec_norm {
             let damage_per_sec = lava_damage.lerp(plane.trans.pos.y / state.location.size.y);
             if damage_per_sec > 0.0 {
-                plane.durable.accept_damage(damage_per_sec * dt.dt, WeaponOwner::Environment);
+                plane.durable.accept_damage(damage_per_sec * dt.dt, DamageKind::Lava, &plane.def.resistances, WeaponOwner::Environment);
             }
         }
         if plane.energy < state.player.energy_max {
@@ -79,17 +86,14 @@ pub fn update_planes(state: &mut GameState, dt: &FrameCtx) {
         }
         match plane.durable {
             Durable::Good { .. } => {}
-            Durable::Destroyed(_) => {
+            Durable::Destroyed(killer) => {
                 let is_player = state.player.plane.map(|it| *id == it).unwrap_or(false);
                 if is_player && state.player.god {
                     plane.durable = Durable::new(state.player.hp_max);
                 } else {
                     disposal_queue.push(*id);
                 }
-                if is_player {
-                    plane.def.death_sound.play_once(&state.subsystems.audio);
-                    plane.def.engine_sound.stop(&mut state.subsystems.audio);
-                }
+                state.events.push_back(GameEvent::PlaneDestroyed { plane: *id, def: plane.def.clone(), killer, player: is_player });
                 continue;
             }
         }
@@ -123,7 +127,7 @@ pub fn update_planes(state: &mut GameState, dt: &FrameCtx) {
         for (device_id, device) in state.player.equipment.iter_mut() {
             match &mut device.spec {
                 DeviceSpec::Weapon(weapon) => {
-                    update_plane_cannon(dt, id, weapon, &mut state.commands, &plane.trans, &plane.rot, &mut plane.energy);
+                    update_plane_cannon(dt, id, weapon, &mut state.commands, &plane.trans, &plane.rot, &mut plane.energy, &mut state.rng);
                     if let Ammo::Finite(0) = weapon.ammo {
                         plane.primary = DeviceState::weapon(CannonState::new(&plane.def.arms.primary, &plane.def.arms.primary_default, Ammo::Infinite));
                     }
@@ -215,11 +219,26 @@ pub fn update_planes(state: &mut GameState, dt: &FrameCtx) {
                     }
                 }
             }
-            physics::apply_steering(&mut plane.trans, &mut plane.rot, &steer_stabilization, plane.desired_rot, &metrics, step_dt);
-            physics::apply_thrust(metrics_argument, dt, acceleration, &plane.rot, &mut plane.trans);
-            physics::apply_gravity(&mut plane.trans, &metrics, step_dt);
-            physics::apply_drag(&mut plane.trans, &metrics, step_dt, dt);
-            physics::apply_slide(&metrics, step_dt, dt, &mut plane.trans, &plane.rot, &plane.def.stabilization.clone().slide);
+            let desired_rot = if is_player {
+                flight_assist::assisted_rot(state.player.assist, plane)
+            } else {
+                plane.desired_rot
+            };
+            physics::apply_steering(&mut plane.trans, &mut plane.rot, &steer_stabilization, desired_rot, &metrics, step_dt);
+            physics::apply_thrust(metrics_argument, dt, acceleration, plane.def.aerodynamics.max_speed, &plane.rot, &mut plane.trans);
+            match state.flight_model.resolve(plane.def.flight_model) {
+                FlightModel::Simulation => {
+                    physics::apply_gravity(&mut plane.trans, &metrics, step_dt);
+                    physics::apply_drag(&mut plane.trans, &metrics, plane.def.aerodynamics.drag, step_dt, dt);
+                    physics::apply_lift(&metrics, step_dt, &mut plane.trans, &plane.rot, &plane.def.aerodynamics);
+                    physics::apply_slide(&metrics, step_dt, dt, &mut plane.trans, &plane.rot, &plane.def.stabilization.clone().slide);
+                }
+                FlightModel::Arcade => {
+                    physics::apply_arcade_gravity(&mut plane.trans, &metrics, step_dt, acceleration, &plane.def.arcade);
+                    physics::apply_drag(&mut plane.trans, &metrics, plane.def.aerodynamics.drag, step_dt, dt);
+                    physics::apply_nose_follow(&metrics, step_dt, &mut plane.trans, &plane.rot, &plane.def.arcade);
+                }
+            }
             physics::apply_velocity(&mut plane.trans, &state.location, step_dt);
             physics::apply_rotation(&mut plane.rot, step_dt);
 
@@ -247,12 +266,17 @@ pub fn update_planes(state: &mut GameState, dt: &FrameCtx) {
                 );
             }
         }
+        metrics.record("speed", plane.trans.velocity.length());
+        metrics.record("energy", plane.energy);
+        if let Durable::Good { hp, .. } = plane.durable {
+            metrics.record("hp", hp);
+        }
         metrics.set_enabled(false);
     }
     for id in disposal_queue {
         if let Some(plane) = state.planes.remove(id) {
             if let Some(explosion) = &plane.def.explosion {
-                particles::emit_explosion(explosion, &plane.trans, &mut state.particles, &state.subsystems);
+                particles::emit_explosion(explosion, &plane.trans, &mut state.particles, &mut state.debris, &mut state.rng, &state.subsystems);
             }
         }
     }
@@ -276,7 +300,7 @@ fn update_booster(booster: &mut ManualBuffState, dt: &FrameCtx, active_booster:
     }
 }
 
-fn update_plane_cannon(dt: &FrameCtx, id: &PlaneId, cannon: &mut CannonState, commands: &mut impl InsertSimple<GameCommand>, trans: &TransState, state: &RotState, energy: &mut f32) {
+fn update_plane_cannon(dt: &FrameCtx, id: &PlaneId, cannon: &mut CannonState, commands: &mut impl InsertSimple<GameCommand>, trans: &TransState, state: &RotState, energy: &mut f32, rng: &mut Rng) {
     let mut pod = trans.clone();
     let offset = pod_offset(cannon, state);
     pod.pos += offset;
@@ -288,6 +312,7 @@ fn update_plane_cannon(dt: &FrameCtx, id: &PlaneId, cannon: &mut CannonState, co
         dt,
         commands,
         energy,
+        rng,
     );
 }
 
@@ -322,11 +347,13 @@ fn test3() {
 
 pub fn draw_planes(state: &GameState, view_port: &ViewPort) {
     for (_, plane) in state.planes.iter() {
-        view_port.port(plane.trans.pos, 1.0, |ported| {
+        let pos = interpolation::lerp_pos(plane.pos_prev, plane.trans.pos, state.tick_alpha, state.location.size);
+        let angle = interpolation::lerp_angle(plane.angle_prev, plane.rot.angle, state.tick_alpha);
+        view_port.port(pos, 1.0, |ported| {
             draw_sprite(&plane.def.sprite, ported.screen_pos, |it| {
                 it.screen_scale = ported.screen_scale;
-                it.angle = plane.rot.angle;
-                it.flip_y = PlaneState::is_plane_flip_y(plane.rot.angle);
+                it.angle = angle;
+                it.flip_y = PlaneState::is_plane_flip_y(angle);
                 it.material = durable::pain_option(state, &plane.durable);
             });
         });
@@ -337,7 +364,7 @@ pub fn find_near_planes(state: &GameState) -> Vec<(Vec2, f32)> {
     let mut distances: Vec<(Vec2, f32)> = state.bots.iter()
         .filter_map(|it| state.planes.get(&it.plane))
         .map(|it| {
-            let dir = it.trans.pos - state.player.camera_pos;
+            let dir = toroidal::delta(state.player.camera_pos, it.trans.pos, state.location.size);
             (dir, dir.length())
         })
         .collect();
@@ -1,7 +1,7 @@
 use crate::common::pool::Pool;
 use crate::GameState;
 use crate::model::def::GameProgressCtx;
-use crate::model::state::{JournalStatePage, JournalWindow, LootId, LootState, UiWindow};
+use crate::model::state::{GameEvent, JournalStatePage, JournalWindow, LootId, LootState, UiWindow};
 
 pub fn update(state: &mut GameState) {
     for rule in state.location.progression.iter() {
@@ -11,14 +11,21 @@ pub fn update(state: &mut GameState) {
                 for flag in &rule.output_flags {
                     state.progression.flags.insert(*flag);
                 }
-                if let Some(entry) = &rule.journal_entry {
-                    state.journal.push(JournalStatePage {
-                        lines: entry.clone()
-                    });
-                    state.player.windows.pop_back();
-                    state.player.windows.push_back(UiWindow::Journal(JournalWindow { page: state.journal.len() - 1 }));
-                }
+                state.events.push_back(GameEvent::ObjectiveCompleted {
+                    objective: rule.objective,
+                    journal_entry: rule.journal_entry.clone(),
+                });
             }
         }
     }
+}
+
+pub fn on_event(state: &mut GameState, event: &GameEvent) {
+    if let GameEvent::ObjectiveCompleted { journal_entry: Some(entry), .. } = event {
+        state.journal.push(JournalStatePage {
+            lines: entry.clone()
+        });
+        state.player.windows.pop_back();
+        state.player.windows.push_back(UiWindow::Journal(JournalWindow { page: state.journal.len() - 1 }));
+    }
 }
\ No newline at end of file
@@ -5,31 +5,18 @@ use crate::{GameState, Plane, PlaneState, PlayerState};
 use crate::common::resource::Resource;
 use crate::common::sound::PlaySound;
 use crate::model::def::{BonusSpec, Cannon, Improvement, ImprovementId, ImprovementSpec, ImprovementTitle, PlaneBuff, PlaneWeapon};
-use crate::model::state::{Ammo, CannonState, DeviceId, DeviceSpec, DeviceState, Durable, EquipmentBinding, ManualBuffAmmo, ManualBuffState, UiWindow, WeaponOwner};
+use crate::model::state::{Ammo, CannonState, DeviceId, DeviceSpec, DeviceState, Durable, EquipmentBinding, GameEvent, ManualBuffAmmo, ManualBuffState, UiWindow, WeaponOwner};
 use crate::resources::constants::{next_level_xp, XP_MUL};
 
-pub fn update(state: &mut GameState) {
-    let rpg = &mut state.player.rpg;
-    let next_level_xp = next_level_xp(rpg.level);
-    if rpg.xp >= next_level_xp {
-        rpg.level += 1;
-        rpg.skill_points += 1;
-        state.def.sound_level_up.play_once(&state.subsystems.audio);
-    }
-    for (_, mob) in state.mobs.iter_mut() {
-        if let Durable::Destroyed(killer) = mob.base.durable {
-            if !mob.base.xp_awarded {
-                match killer {
-                    WeaponOwner::Plane(plane) => {
-                        if Some(plane) == state.player.plane {
-                            state.player.rpg.xp += (mob.base.def.xp_reward as f32 * XP_MUL) as u32;
-                        }
-                    }
-                    WeaponOwner::Mob => {}
-                    WeaponOwner::Environment => {}
-                }
-                mob.base.xp_awarded = true;
-                mob.base.def.death_sound.play_once(&state.subsystems.audio);
+pub fn on_event(state: &mut GameState, event: &GameEvent) {
+    if let GameEvent::MobKilled { def, killer: WeaponOwner::Plane(plane), .. } = event {
+        if Some(*plane) == state.player.plane {
+            let rpg = &mut state.player.rpg;
+            rpg.xp += (def.xp_reward as f32 * XP_MUL) as u32;
+            while rpg.xp >= next_level_xp(rpg.level) {
+                rpg.level += 1;
+                rpg.skill_points += 1;
+                state.events.push_back(GameEvent::LevelUp { level: rpg.level });
             }
         }
     }
@@ -101,7 +88,7 @@ pub fn weapon_to_device(plane: &Resource<Plane>, weapon: &PlaneWeapon) -> Device
         spec: DeviceSpec::Weapon(CannonState::new(
             &resource.arms.primary,
             &weapon.spec,
-            Ammo::Energy { energy_per_shot: weapon.energy_per_shot },
+            weapon.ammo,
         )),
         binding: None,
         order: weapon.order,
@@ -12,26 +12,25 @@ use crate::common::unsorted::{RectExtOps, WithMut};
 use crate::common::camera::ViewPort;
 use crate::common::sprite::{draw_sprite};
 use crate::model::state::BackgroundObjectState;
-use crate::rand::{ChooseRandom, gen_range};
 use crate::resources::constants::LOGIC_RESOLUTION;
 
 pub fn init_clouds(state: &mut GameState) {
     for cloud in &state.location.background_objects {
-        let count = cloud.count.lerp(gen_range(0.0, 1.0));
+        let count = cloud.count.lerp(state.rng.gen_range(0.0..1.0));
         for _ in 0..count {
-            let z = cloud.z.lerp(gen_range(0.0, 1.0));
+            let z = cloud.z.lerp(state.rng.gen_range(0.0..1.0));
             let furthest_size = state.location.size;
             let pos = Vec2::new(
-                unsorted::gen_range(0.0..furthest_size.x),
-                cloud.height_normal.clone()
-                    .map(|it| it.random() * furthest_size.y)
-                    .unwrap_or_else(|| unsorted::gen_range(0.0..furthest_size.y))
-                ,
+                state.rng.gen_range(0.0..furthest_size.x),
+                match &cloud.height_normal {
+                    Some(it) => it.random_by(&mut state.rng) * furthest_size.y,
+                    None => state.rng.gen_range(0.0..furthest_size.y),
+                },
             );
             state.background_objects.push(BackgroundObjectState {
-                sprite: cloud.sprite.choose().unwrap().clone(),
+                sprite: state.rng.choose(&cloud.sprite).unwrap().clone(),
                 pos: pos.extend(z),
-                size: cloud.size.lerp(gen_range(0.0, 1.0)),
+                size: cloud.size.lerp(state.rng.gen_range(0.0..1.0)),
                 material: cloud.material.clone(),
             });
         }
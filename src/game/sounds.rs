@@ -2,6 +2,7 @@ use macroquad::audio::set_sound_volume;
 use crate::common::contract::Get;
 use crate::common::sound::{PlaySound, SoundList, StopSound};
 use crate::GameState;
+use crate::model::state::GameEvent;
 
 pub fn on_start(state: &mut GameState) {
     if let Some(plane) = state.player.plane {
@@ -24,7 +25,26 @@ pub fn on_pause(state: &mut GameState, pause: bool) {
 }
 
 pub fn on_volume_change(state: &mut GameState) {
+    if state.subsystems.audio.muted {
+        return;
+    }
     for sound in &state.subsystems.audio.looped {
         set_sound_volume(sound.def.sound, sound.def.base_volume * sound.instance_volume * state.subsystems.audio.volume.get());
     }
-}
\ No newline at end of file
+}
+
+pub fn on_event(state: &mut GameState, event: &GameEvent) {
+    let audio = &mut state.subsystems.audio;
+    match event {
+        GameEvent::MobKilled { def, .. } => def.death_sound.play_once(audio),
+        GameEvent::PlaneDestroyed { def, player: true, .. } => {
+            def.death_sound.play_once(audio);
+            def.engine_sound.stop(audio);
+        }
+        GameEvent::PlaneDestroyed { .. } => {}
+        GameEvent::LootPicked { def } => def.pick_sound.play_once(audio),
+        GameEvent::LevelUp { .. } => state.def.sound_level_up.play_once(audio),
+        GameEvent::ObjectiveCompleted { .. } => {}
+        GameEvent::DamageDealt { .. } => {}
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{GameState, PlaneId};
+use crate::model::state::{DamageTarget, GameEvent, WeaponOwner};
+
+pub fn on_event(state: &mut GameState, event: &GameEvent) {
+    let player = state.player.plane;
+    let stats = &mut state.stats;
+    match event {
+        GameEvent::MobKilled { killer, .. } => {
+            if is_player(*killer, player) {
+                stats.mobs_killed += 1;
+            }
+        }
+        GameEvent::PlaneDestroyed { player: true, .. } => stats.deaths += 1,
+        GameEvent::PlaneDestroyed { .. } => {}
+        GameEvent::LootPicked { .. } => stats.loot_picked += 1,
+        GameEvent::LevelUp { .. } => {}
+        GameEvent::ObjectiveCompleted { .. } => stats.objectives_completed += 1,
+        GameEvent::DamageDealt { target, amount, source, .. } => {
+            if is_player(*source, player) {
+                stats.damage_dealt += amount;
+            }
+            if let DamageTarget::Plane(target) = target {
+                if Some(*target) == player {
+                    stats.damage_taken += amount;
+                }
+            }
+        }
+    }
+}
+
+fn is_player(owner: WeaponOwner, player: Option<PlaneId>) -> bool {
+    match owner {
+        WeaponOwner::Plane(plane) => Some(plane) == player,
+        WeaponOwner::Mob | WeaponOwner::Environment => false,
+    }
+}
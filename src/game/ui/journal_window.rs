@@ -7,7 +7,7 @@ use rust_macroquad_ui::primitives::{height, horizontal_content, single_content,
 use rust_macroquad_ui::primitives::mouse::on_click;
 use rust_macroquad_ui::primitives::node::{Node, node};
 use crate::{GameState, ui};
-use crate::model::state::{JournalWindow, UiEvent};
+use crate::model::state::{GameStats, JournalWindow, UiEvent};
 use crate::ui::modal_windows::window_content_line;
 
 pub fn show_window(state: &GameState, window: &JournalWindow) -> Node<UiEvent> {
@@ -26,6 +26,7 @@ pub fn show_window(state: &GameState, window: &JournalWindow) -> Node<UiEvent> {
                                 )
                                 .unwrap_or_else(|| width_node(0.0)),
                             stretch_vertical(),
+                            window_content_line(&stats_line(&state.stats)),
                             horizontal_node([
                                 horizontal_node(state.journal.iter()
                                     .enumerate()
@@ -35,4 +36,10 @@ pub fn show_window(state: &GameState, window: &JournalWindow) -> Node<UiEvent> {
                         ]),
             )
         ))
-}
\ No newline at end of file
+}
+fn stats_line(stats: &GameStats) -> String {
+    format!(
+        "Killed: {}   Deaths: {}   Loot: {}   Objectives: {}   Damage dealt: {:.0}   Damage taken: {:.0}",
+        stats.mobs_killed, stats.deaths, stats.loot_picked, stats.objectives_completed, stats.damage_dealt, stats.damage_taken,
+    )
+}
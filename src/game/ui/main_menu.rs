@@ -1,5 +1,6 @@
 use std::collections::VecDeque;
-use rust_macroquad_ui::basic_composites::node_factories::{stretch_around_node, vertical_node};
+use rust_macroquad_ui::basic_composites::label::label;
+use rust_macroquad_ui::basic_composites::node_factories::{height_node, stretch_around_node, vertical_node};
 use rust_macroquad_ui::basic_composites::stretch::StretchSide::{StretchHorizontal, StretchVertical};
 use rust_macroquad_ui::common::to_vec::ToVec;
 use rust_macroquad_ui::primitives::layers;
@@ -8,29 +9,54 @@ use rust_macroquad_ui::UILayer;
 
 use crate::{Game, ui};
 use crate::common::resource::Resource;
-use crate::model::state::AppStateEvent;
+use crate::lifecycle::saves;
+use crate::model::state::{AppStateEvent, AssistLevel, FlightModelSetting};
 use crate::ui::new_hud::PanelStyle;
 use crate::ui::panel;
 
-pub fn main_menu(events: &mut VecDeque<AppStateEvent>, def: &Resource<Game>, allow_show_intro: bool) {
+pub fn main_menu(events: &mut VecDeque<AppStateEvent>, def: &Resource<Game>, allow_show_intro: bool, allow_save: bool, seed: Option<u64>, flight_model: FlightModelSetting, assist: AssistLevel) {
+    let mut save_items = vec![];
+    if allow_save {
+        for slot in saves::SLOTS {
+            save_items.push((format!("Save to {}", saves::slot_title(slot)), AppStateEvent::Save { slot }));
+        }
+    }
+    let mut load_items = vec![];
+    for slot in [saves::AUTOSAVE].into_iter().chain(saves::SLOTS) {
+        if let Some(time) = saves::slot_time(slot) {
+            load_items.push((format!("Load {} ({})", saves::slot_title(slot), time), AppStateEvent::Load { slot }));
+        }
+    }
+    let flight_model_item = format!("Flight Model: {}", flight_model.title());
+    let assist_item = format!("Flight Assist: {}", assist.title());
     let mut items = vec![];
     items.push(("Start Training Flight", AppStateEvent::NewGame { location: def.training.clone() }));
     items.push(("Start Combat Mission", AppStateEvent::NewGame { location: def.combat.clone() }));
+    for (text, event) in save_items.iter().chain(load_items.iter()) {
+        items.push((text.as_str(), event.clone()));
+    }
+    items.push((flight_model_item.as_str(), AppStateEvent::SwitchFlightModel));
+    items.push((assist_item.as_str(), AppStateEvent::SwitchFlightAssist));
     if allow_show_intro {
         items.push(("Intro", AppStateEvent::Intro));
     }
     items.push(("Quit", AppStateEvent::Quit));
-    generic_menu(events, items);
+    generic_menu(events, items, seed.map(|it| format!("Seed: {}", it)));
 }
 
-pub fn generic_menu(events: &mut VecDeque<AppStateEvent>, options: Vec<(&str, AppStateEvent)>) {
+pub fn generic_menu(events: &mut VecDeque<AppStateEvent>, options: Vec<(&str, AppStateEvent)>, footer: Option<String>) {
     let style = PanelStyle { margin: 16.0 };
+    let mut content = options.into_iter()
+        .map(|(text, event)| ui::button(text, event, AppStateEvent::hover))
+        .to_vec();
+    if let Some(footer) = footer {
+        content.push(height_node(16.0));
+        content.push(stretch_around_node([StretchHorizontal], label(footer, ui::text_style())));
+    }
     let node: Node<AppStateEvent> = node()
         .set(layers([
             stretch_around_node([StretchHorizontal, StretchVertical], vertical_node([
-                panel(vertical_node(options.into_iter()
-                    .map(|(text, event)| ui::button(text, event, AppStateEvent::hover))
-                    .to_vec()), style),
+                panel(vertical_node(content), style),
             ])),
         ]));
     let mut layer = UILayer::new(1.0, node);
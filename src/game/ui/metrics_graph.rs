@@ -0,0 +1,54 @@
+use macroquad::color::{Color, WHITE, YELLOW};
+use macroquad::prelude::{draw_line, draw_rectangle, draw_text};
+use crate::common::camera::ViewPort;
+use crate::common::metrics::{HISTORY_TICKS, MetricChannel};
+use crate::GameState;
+use crate::resources::constants::LOGIC_RESOLUTION;
+
+const PANEL_WIDTH: f32 = 320.0;
+const PANEL_HEIGHT: f32 = 56.0;
+const MARGIN: f32 = 6.0;
+const TOP: f32 = 96.0;
+const FONT_SIZE: f32 = 16.0;
+
+pub fn draw_metrics_graph(state: &GameState, view_port: &ViewPort) {
+    let scale = view_port.view_scale;
+    let history = state.metrics.history();
+    let x = (LOGIC_RESOLUTION.0 - PANEL_WIDTH - MARGIN) * scale;
+    for (i, channel) in history.channels.iter().enumerate() {
+        let y = (TOP + i as f32 * (PANEL_HEIGHT + MARGIN)) * scale;
+        draw_channel(channel, x, y, PANEL_WIDTH * scale, PANEL_HEIGHT * scale, scale);
+    }
+}
+
+fn draw_channel(channel: &MetricChannel, x: f32, y: f32, width: f32, height: f32, scale: f32) {
+    draw_rectangle(x, y, width, height, Color::new(0.0, 0.0, 0.0, 0.6));
+
+    let text = match (channel.latest(), channel.range()) {
+        (Some(latest), Some((min, max))) => format!("{}: {:.3} [{:.3} .. {:.3}]", channel.title(), latest, min, max),
+        _ => channel.title(),
+    };
+    draw_text(&text, x + 4.0 * scale, y + FONT_SIZE * scale, FONT_SIZE * scale, WHITE);
+
+    let (min, max) = match channel.range() {
+        Some(range) => range,
+        None => return,
+    };
+    // flat lines go in the middle
+    let span = if max - min > f32::EPSILON { max - min } else { 1.0 };
+    let base = if max - min > f32::EPSILON { min } else { min - 0.5 };
+    let step = width / (HISTORY_TICKS - 1) as f32;
+    // newest sample at the right edge
+    let first = HISTORY_TICKS - channel.samples.len();
+    let to_screen = |i: usize, value: f32| {
+        (x + (first + i) as f32 * step, y + height - (value - base) / span * height)
+    };
+    for (i, (a, b)) in channel.samples.iter().zip(channel.samples.iter().skip(1)).enumerate() {
+        if !a.is_finite() || !b.is_finite() {
+            continue;
+        }
+        let (x1, y1) = to_screen(i, *a);
+        let (x2, y2) = to_screen(i + 1, *b);
+        draw_line(x1, y1, x2, y2, 1.0 * scale, YELLOW);
+    }
+}
@@ -2,6 +2,7 @@ use macroquad::prelude::{draw_circle, draw_line, draw_rectangle};
 use std::collections::HashMap;
 use macroquad::color::RED;
 use crate::common::camera::ViewPort;
+use crate::common::toroidal;
 use crate::{GameState, Vec2};
 use crate::common::unsorted::{ColorOps, ToColor};
 use crate::model::state::{Durable, MobMission};
@@ -44,7 +45,7 @@ pub fn draw_minimap(state: &GameState, view_port: &ViewPort) {
         if isle.guard_count_threshold == 0 {
             continue;
         }
-        let screen_pos = to_screen(isle.trans.pos);
+        let screen_pos = to_screen(toroidal::wrap(isle.trans.pos, state.location.size));
         let guards = *guards_by_isle.get(isle_id).unwrap_or(&0);
         let color = match guards {
             0 => "#00BA00".to_color(),
@@ -59,7 +60,7 @@ pub fn draw_minimap(state: &GameState, view_port: &ViewPort) {
     }
     let frame_1 = to_screen(state.location.size);
 
-    let cam_screen_pos = to_screen(state.player.camera_pos);
+    let cam_screen_pos = to_screen(toroidal::wrap(state.player.camera_pos, state.location.size));
     draw_line(
         frame_0.x,
         cam_screen_pos.y,
@@ -1,5 +1,8 @@
 pub mod minimap;
+pub mod notifications;
 pub mod legacy_hud;
+pub mod metrics_graph;
+pub mod perf_overlay;
 pub mod new_hud;
 pub mod shop_window;
 mod res_indicator;
@@ -16,6 +19,11 @@ use crate::common::resource::Resource;
 use crate::common::unsorted::{ColorOps, ToColor};
 use crate::model::state::{AppStateEvent, UiEvent};
 use crate::{AppState, DrawState, Game, GameState, MenuState, MouseButton, start, ui};
+use crate::cli::CliArgs;
+use crate::common::rng;
+use crate::lifecycle::{replay, saves};
+use crate::lifecycle::replay::ReplayEvent;
+use macroquad::logging::{error, info};
 use macroquad::color::{RED, WHITE};
 use macroquad::prelude::BLACK;
 use macroquad::prelude::Vec4Swizzles;
@@ -48,19 +56,19 @@ use crate::game::ui::new_hud::{HudStyle, PanelStyle};
 use crate::game_viewport::create_viewport;
 use crate::resources::constants::LOGIC_RESOLUTION;
 
-pub fn do_ui(mut state: AppState, draw_state: &DrawState, def: &Resource<Game>) -> AppState {
+pub fn do_ui(mut state: AppState, draw_state: &DrawState, def: &Resource<Game>, args: &CliArgs) -> AppState {
     let mut events = VecDeque::new();
 
     match &mut state {
         AppState::Title { menu } => {
-            main_menu::main_menu(&mut events, def, true);
+            main_menu::main_menu(&mut events, def, true, false, None, start::flight_model_pref().get(), start::flight_assist_pref().get());
         }
         AppState::Game { game } => {
             draw_game_state(game, draw_state);
         }
         AppState::GameMenu { game, menu } => {
             draw_game_state(game, draw_state);
-            main_menu::main_menu(&mut events, def, false);
+            main_menu::main_menu(&mut events, def, false, !replay::is_playing(game), Some(game.seed), start::flight_model_pref().get(), start::flight_assist_pref().get());
         }
         AppState::Intro => {
             let mut text = vec![
@@ -95,12 +103,59 @@ pub fn do_ui(mut state: AppState, draw_state: &DrawState, def: &Resource<Game>)
     while let Some(event) = events.pop_front() {
         match event {
             AppStateEvent::NewGame { location } => {
-                let game = start::new_game(def, &location);
+                let mut game = start::new_game(def, &location, args.seed.unwrap_or_else(rng::random_seed), start::flight_settings_from_prefs());
+                if let Some(path) = &args.record {
+                    replay::start_recording(&mut game, path);
+                }
                 return AppState::Game { game };
             }
+            AppStateEvent::Save { slot } => {
+                if let AppState::GameMenu { game, .. } = state {
+                    match saves::save(&game, slot) {
+                        Ok(()) => info!("[SAVE] saved to {}", slot),
+                        Err(err) => error!("[SAVE] {}", err),
+                    }
+                    return AppState::Game { game };
+                }
+            }
+            AppStateEvent::Load { slot } => {
+                match saves::load(def, slot, start::flight_settings_from_prefs()) {
+                    Ok(game) => return AppState::Game { game },
+                    Err(err) => error!("[SAVE] failed to load {}: {}", slot, err),
+                }
+            }
             AppStateEvent::Quit => {
+                if let AppState::GameMenu { game, .. } = &state {
+                    if !replay::is_playing(game) {
+                        if let Err(err) = saves::save(game, saves::AUTOSAVE) {
+                            error!("[SAVE] {}", err);
+                        }
+                    }
+                }
                 exit(0);
             }
+            AppStateEvent::SwitchFlightModel => {
+                let mut pref = start::flight_model_pref();
+                let flight_model = pref.get().next();
+                pref.set(flight_model);
+                if let AppState::GameMenu { game, .. } = &mut state {
+                    if !replay::is_playing(game) {
+                        replay::record(game, ReplayEvent::FlightModel(flight_model));
+                        game.flight_model = flight_model;
+                    }
+                }
+            }
+            AppStateEvent::SwitchFlightAssist => {
+                let mut pref = start::flight_assist_pref();
+                let assist = pref.get().next();
+                pref.set(assist);
+                if let AppState::GameMenu { game, .. } = &mut state {
+                    if !replay::is_playing(game) {
+                        replay::record(game, ReplayEvent::Assist(assist));
+                        game.player.assist = assist;
+                    }
+                }
+            }
             AppStateEvent::Hover(_) => {}
             AppStateEvent::Intro => {
                 return AppState::Intro;
@@ -143,6 +198,13 @@ fn draw_game_state(state: &mut GameState, draw_state: &DrawState) {
     if let Some(player_plane) = player_plane {
         new_hud::draw_hud_new(state, player_plane, style);
     }
+    notifications::draw_notifications(state, view_port);
+    if state.show_metrics {
+        metrics_graph::draw_metrics_graph(state, view_port);
+    }
+    if state.show_perf {
+        perf_overlay::draw_perf_overlay(view_port);
+    }
     modal_windows::draw_modal_windows(state, view_port, style);
 }
 
@@ -14,6 +14,8 @@ use crate::game::rpg;
 use crate::game::ui::{header_style, panel, shop_window, improvements_window, text_style, WINDOW_HEADER_SPACING, equipment_window};
 use crate::game::ui::new_hud::HudStyle;
 use crate::GameState;
+use crate::lifecycle::replay;
+use crate::lifecycle::replay::ReplayEvent;
 use crate::model::def::GameProgressCtx;
 use crate::model::state::{EquipmentWindow, JournalWindow, UiEvent, UiWindow, WindowsAction};
 use crate::ui::journal_window;
@@ -85,6 +87,7 @@ pub fn draw_modal_windows(state: &mut GameState, view_port: &ViewPort, style: Hu
                     }
                 }
                 UiEvent::ClickSkillsItem(skill_id) => {
+                    replay::record(state, ReplayEvent::Skill(*skill_id));
                     rpg::click_skill(state, skill_id);
                 }
                 UiEvent::Hover(_) => {}
@@ -95,6 +98,7 @@ pub fn draw_modal_windows(state: &mut GameState, view_port: &ViewPort, style: Hu
                 }
                 UiEvent::EquipmentClickBinding(binding) => {
                     if let Some(UiWindow::Equipment(EquipmentWindow { selected_item: Some(selected_item) })) = state.player.windows.back().cloned() {
+                        replay::record(state, ReplayEvent::Bind(selected_item, *binding));
                         rpg::bind_equipment(state, &selected_item, binding);
                     }
                 }
@@ -109,6 +109,11 @@ fn stats(plane: &PlaneState, hud_style: HudStyle, player: &PlayerState) -> Node<
                       label(format!("{:.0}° AOA", attack_angle.to_deg()), hud_text()).pad(margin(hud_style.hud_panels.margin)),
                   ),
                   throttle(plane, &player, hud_style),
+                  info_raw(match (player.docked.is_some(), plane.stall_recovery) {
+                      (true, _) => "Docked: repairing".to_owned(),
+                      (false, true) => "Assist: STALL".to_owned(),
+                      (false, false) => format!("Assist: {}", player.assist.title()),
+                  }, hud_style),
               ]))
               .pad(no_stretch(NoStretchMode::Both)),
           hud_style.hud_panels,
@@ -152,7 +157,7 @@ fn info_raw<S: Into<String>>(text: S, style: HudStyle) -> Node<UiEvent> {
     target
 }
 
-fn indicator<V: Into<f64>, S: Into<String>>(current: V, max: V, style: PanelStyle, text: S, color: Color) -> Node<UiEvent> {
+pub fn indicator<V: Into<f64>, S: Into<String>>(current: V, max: V, style: PanelStyle, text: S, color: Color) -> Node<UiEvent> {
     let current = current.into();
     let max = max.into();
     let rel_value = f64::clamp(current / max, 0.0, 1.0);
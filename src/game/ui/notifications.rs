@@ -0,0 +1,42 @@
+use macroquad::color::{BLACK, WHITE};
+use crate::common::camera::ViewPort;
+use crate::GameState;
+use crate::game::ui::legacy_hud;
+use crate::model::def::Item;
+use crate::model::state::{GameEvent, NotificationState};
+use crate::resources::constants::TICK_DT;
+
+const VISIBLE_SEC: f32 = 3.0;
+const MAX_COUNT: usize = 4;
+
+pub fn on_event(state: &mut GameState, event: &GameEvent) {
+    let text = match event {
+        GameEvent::LevelUp { level } => format!("Level {}", level),
+        GameEvent::LootPicked { def } => def.content.iter()
+            .map(|it| match it {
+                Item::Resource { count, resource } => format!("+{} {:?}", count, resource),
+            })
+            .collect::<Vec<_>>()
+            .join(", "),
+        GameEvent::ObjectiveCompleted { objective: Some(objective), .. } => format!("Completed: {}", objective),
+        _ => return,
+    };
+    state.notifications.push_back(NotificationState { text, tick: state.tick });
+    while state.notifications.len() > MAX_COUNT {
+        state.notifications.pop_front();
+    }
+}
+
+pub fn draw_notifications(state: &GameState, view_port: &ViewPort) {
+    let visible = state.notifications.iter()
+        .filter(|it| (state.tick - it.tick) as f32 * TICK_DT < VISIBLE_SEC);
+    for (i, notification) in visible.enumerate() {
+        legacy_hud::draw_text_center_x(
+            &notification.text,
+            (120.0 + i as f32 * 32.0) * view_port.view_scale,
+            28.0,
+            &[(BLACK, 2.0, 2.0), (WHITE, 0.0, 0.0)],
+            view_port.view_scale,
+        );
+    }
+}
@@ -0,0 +1,42 @@
+use macroquad::color::{Color, RED, WHITE};
+use macroquad::prelude::{draw_rectangle, draw_text};
+use crate::common::camera::ViewPort;
+use crate::common::perf;
+
+const LEFT: f32 = 16.0;
+const TOP: f32 = 96.0;
+const WIDTH: f32 = 300.0;
+const ROW_HEIGHT: f32 = 18.0;
+const INDENT: f32 = 16.0;
+
+pub fn draw_perf_overlay(view_port: &ViewPort) {
+    let scale = view_port.view_scale;
+    let breakdown = perf::perf_breakdown();
+    let rows = breakdown.rows.len() + 1;
+    draw_rectangle(
+        LEFT * scale,
+        TOP * scale,
+        WIDTH * scale,
+        (rows as f32 + 0.5) * ROW_HEIGHT * scale,
+        Color::new(0.0, 0.0, 0.0, 0.6),
+    );
+
+    let mut y = TOP + ROW_HEIGHT;
+    let mut draw_row = |depth: usize, name: &str, value: String, color: Color| {
+        let x = LEFT + 4.0 + depth as f32 * INDENT;
+        draw_text(name, x * scale, y * scale, ROW_HEIGHT * scale, color);
+        draw_text(&value, (LEFT + WIDTH - 110.0) * scale, y * scale, ROW_HEIGHT * scale, color);
+        y += ROW_HEIGHT;
+    };
+
+    let title = if perf::perf_is_capturing() { "frame (capturing)" } else { "frame" };
+    draw_row(0, title, format!("{:.2}ms", breakdown.frame_ms), if perf::perf_is_capturing() { RED } else { WHITE });
+    for row in &breakdown.rows {
+        let value = if row.calls > 1.0 {
+            format!("{:.2}ms x{:.1}", row.ms, row.calls)
+        } else {
+            format!("{:.2}ms", row.ms)
+        };
+        draw_row(row.depth + 1, row.name, value, WHITE);
+    }
+}
@@ -43,6 +43,9 @@ pub fn shop_window(shop: &ShopWindow, player: &PlayerState) -> Node<UiEvent> {
                     Ammo::Energy { energy_per_shot } => {
                         format!("{}: {} ({} energy/shot)", slot, weapon.title, energy_per_shot)
                     }
+                    Ammo::Heat { .. } => {
+                        format!("{}: {} (heat)", slot, weapon.title)
+                    }
                 }
             }
             Obtainable::HP { title, .. } => {
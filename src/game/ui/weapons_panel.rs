@@ -7,24 +7,48 @@ use rust_macroquad_ui::common::to_vec::ToVec;
 use rust_macroquad_ui::primitives::{height, vertical_content, width};
 use crate::{GameState, PlaneState};
 use crate::game::ui;
-use crate::game::ui::new_hud::HudStyle;
+use crate::common::unsorted::ToColor;
+use crate::game::ui::new_hud::{HudStyle, indicator};
 use crate::game::ui::panel;
-use crate::model::state::{Ammo, DeviceSpec, DeviceState, EquipmentBinding, ManualBuffAmmo, UiEvent};
+use crate::model::state::{Ammo, CannonState, DeviceSpec, DeviceState, EquipmentBinding, ManualBuffAmmo, UiEvent};
 
-fn device(device: &DeviceState, binding: EquipmentBinding) -> Node<UiEvent> {
+fn device(device: &DeviceState, binding: EquipmentBinding, hud_style: HudStyle) -> Node<UiEvent> {
     let style = ui::hud_weapon_style();
     match &device.spec {
-        DeviceSpec::Weapon(weapon) => {
-            label(weapon.def.title, style)
-        }
+        DeviceSpec::Weapon(weapon) => match weapon.ammo {
+            Ammo::Heat { .. } => vertical_node([
+                label(weapon.def.title, style),
+                heat_bar(weapon, hud_style),
+            ]),
+            _ => label(weapon.def.title, style),
+        },
         DeviceSpec::Booster(booster) => {
             label(booster.def.title, style)
         }
     }
 }
 
+fn heat_bar(weapon: &CannonState, hud_style: HudStyle) -> Node<UiEvent> {
+    let (text, color) = if weapon.is_overheated() {
+        ("OVERHEAT".to_owned(), "#F20".to_color())
+    } else {
+        (format!("Heat {:.0}%", weapon.heat * 100.0), "#C80".to_color())
+    };
+    indicator(weapon.heat, 1.0, hud_style.hud_panels, text, color)
+}
+
+fn uses_heat(device: &DeviceState) -> bool {
+    match &device.spec {
+        DeviceSpec::Weapon(weapon) => matches!(weapon.ammo, Ammo::Heat { .. }),
+        DeviceSpec::Booster(_) => false,
+    }
+}
+
 pub fn weapons_panel(state: &GameState, plane: &PlaneState, hud_style: HudStyle) -> Node<UiEvent> {
-    if true {
+    // the panel is shown only when there is a heat bar to show
+    let any_heat = state.player.equipment.iter()
+        .any(|(_, it)| it.binding.is_some() && uses_heat(it));
+    if !any_heat {
         return node()
             .set(height(0.0))
             .set(width(0.0));
@@ -33,7 +57,7 @@ pub fn weapons_panel(state: &GameState, plane: &PlaneState, hud_style: HudStyle)
     let style = ui::text_style();
     for (_, d) in state.player.equipment.iter() {
         if let Some(binding) = d.binding {
-            children.push(device(d, binding));
+            children.push(device(d, binding, hud_style));
         }
     }
     panel(
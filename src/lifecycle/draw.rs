@@ -11,12 +11,13 @@ use macroquad::ui::{root_ui, Skin};
 use crate::common::camera::ViewPort;
 use crate::common::contract::Get;
 use crate::common::fps::FpsCounter;
+use crate::common::perf::perf_scope;
 use crate::common::pool::{Pool, PoolKey};
 use crate::common::sprite_clip;
 use crate::common::sprite_clip::{SpriteDrawer, SpriteDrawingItem, SpriteDrawingOption};
 use crate::common::sprite_clip::SpriteDrawingOption::{Empty, FlipX, Material, Rot, Scale};
 use crate::common::unsorted::{ColorOps, RectExtOps, ToColor};
-use crate::game::{cannon, control_guard, durable, game_viewport, isles, loot, mobs, plane, sky, ui};
+use crate::game::{cannon, control_guard, debris, durable, game_viewport, isles, lock_on, loot, mobs, plane, sky, ui};
 use crate::{AppState, debug, GameState};
 use crate::game::ui::legacy_hud;
 use crate::model::def::{HoldEffect, MaterialInstance, MobAttackPattern};
@@ -46,10 +47,10 @@ pub fn draw_game_state(state: &mut GameState, draw_state: &mut DrawState) {
     let view_port = &game_viewport::create_viewport(state);
     draw_state.fps_counter.update_fps();
 
-    sky::draw_sky(state, view_port);
+    perf_scope("sky", || sky::draw_sky(state, view_port));
 
     let mut stats = Stats { drawn_clouds: 0 };
-    sky::draw_clouds(state, &mut stats, view_port);
+    perf_scope("clouds", || sky::draw_clouds(state, &mut stats, view_port));
 
     let sprite_drawer = SpriteDrawer {
         stats: &mut stats,
@@ -91,8 +92,8 @@ pub fn draw_game_state(state: &mut GameState, draw_state: &mut DrawState) {
 
     let mut isles: Vec<(&IsleId, &IsleState)> = state.isles.iter().collect();
     isles.sort_by_key(|(_, isle)| isle.order);
-    for (isle_id, isle) in isles {
-        isles::draw_isle(isle, view_port);
+    perf_scope("isles", || for (isle_id, isle) in isles {
+        isles::draw_isle(state, isle, view_port);
         if let Some(mobs) = mobs.lying.get(isle_id) {
             draw_mobs(state, mobs, &sprite_drawer, view_port);
         }
@@ -102,35 +103,42 @@ pub fn draw_game_state(state: &mut GameState, draw_state: &mut DrawState) {
                 loot::draw(loot.pos.get_abs(&state.isles), &loot.def, &view_port);
             }
         }
-    }
+    });
 
-    draw_mobs(state, &mobs.flying, &sprite_drawer, view_port);
+    perf_scope("mobs", || draw_mobs(state, &mobs.flying, &sprite_drawer, view_port));
 
     for loot in &loot.flying {
         loot::draw(loot.pos.get_abs(&state.isles), &loot.def, &view_port);
     }
 
-    sprite_drawer.draw(&state.particles.fixed, |it| Some(SpriteDrawingItem {
-        pos: it.pos,
-        clip: &it.clip,
-        options: [Scale(it.scale), Rot(it.rot)],
-    }));
-    sprite_drawer.draw(&state.particles.moving, |it| Some(SpriteDrawingItem {
-        pos: it.pos,
-        clip: &it.clip,
-        options: [Scale(it.scale), Rot(it.rot)],
-    }));
-    plane::draw_planes(state, &view_port);
-    sprite_drawer.draw(&state.projectiles, |projectile| projectile.exhaust_clip.as_ref().map(|exhaust_clip| {
-        SpriteDrawingItem {
-            pos: projectile.trans.pos,
-            clip: exhaust_clip,
-            options: [
-                Rot(projectile.rot.angle),
-            ],
-        }
-    }));
-    cannon::draw_projectiles(state, &view_port);
+    perf_scope("particles", || {
+        sprite_drawer.draw(&state.particles.fixed, |it| Some(SpriteDrawingItem {
+            pos: it.pos,
+            clip: &it.clip,
+            options: [Scale(it.scale), Rot(it.rot)],
+        }));
+        sprite_drawer.draw(&state.particles.moving, |it| Some(SpriteDrawingItem {
+            pos: it.pos,
+            clip: &it.clip,
+            options: [Scale(it.scale), Rot(it.rot)],
+        }));
+    });
+    perf_scope("debris", || debris::draw(state, &view_port));
+    perf_scope("planes", || plane::draw_planes(state, &view_port));
+    perf_scope("projectiles", || {
+        sprite_drawer.draw(&state.projectiles, |projectile| projectile.exhaust_clip.as_ref().map(|exhaust_clip| {
+            let (pos, angle) = cannon::interpolated_trans(state, projectile);
+            SpriteDrawingItem {
+                pos,
+                clip: exhaust_clip,
+                options: [
+                    Rot(angle),
+                ],
+            }
+        }));
+        cannon::draw_projectiles(state, &view_port);
+    });
+    lock_on::draw(state, &view_port);
     control_guard::draw(state, &stats, draw_state, &view_port);
     if state.show_colliders {
         let draw_collider = |center: Vec2, radius: f32| {
@@ -177,7 +185,7 @@ fn draw_mobs(state: &GameState, mobs: &Vec<&MobState>, sprite_drawer: &SpriteDra
                 debug!("animation =  {:?}, frame: {:?}", mob.base.animation, clip.frame);
             }
             SpriteDrawingItem {
-                pos: mob.anchor.get_pos_rel().get_abs(&state.isles),
+                pos: mobs::interpolated_pos(state, mob),
                 clip,
                 options: [
                     FlipX(mob.base.get_dir() < 0.0),
@@ -201,7 +209,7 @@ fn draw_mobs(state: &GameState, mobs: &Vec<&MobState>, sprite_drawer: &SpriteDra
                         if let Some(effect) = &attack.hold_effect {
                             match effect {
                                 HoldEffect::Circle(effect) => {
-                                    view_port.port(mob.anchor.get_pos_rel().get_abs(&state.isles), 1.0, |ported| {
+                                    view_port.port(mobs::interpolated_pos(state, mob), 1.0, |ported| {
                                         let offset = match &attack.pattern {
                                             MobAttackPattern::Melee { .. } => Vec2::ZERO,
                                             MobAttackPattern::Distant { cannon } =>
@@ -0,0 +1,117 @@
+use std::any::TypeId;
+use macroquad::logging::{error, info};
+use macroquad::material::Material;
+use macroquad::prelude::{Image, Texture2D, Vec2};
+use crate::common::backend::Backend;
+use crate::common::contract::Get;
+use crate::common::frame::FrameCtx;
+use crate::common::perf::perf_frame_end;
+use crate::common::resource::Resource;
+use crate::common::rng;
+use crate::game::game_viewport;
+use crate::GameState;
+use crate::lifecycle::{replay, start, update};
+use crate::lifecycle::replay::ReplayPlayer;
+use crate::model::def::{Game, GameSound, Location};
+use crate::model::state::{Durable, FlightSettings};
+use crate::resources::constants::TICK_DT;
+
+// there is no window in headless mode, so there is no GL or audio context either: textures are
+// empty (only their size is known), sounds and shaders are not loaded at all
+#[derive(Debug)]
+pub struct HeadlessBackend;
+
+impl Backend for HeadlessBackend {
+    fn load_texture(&self, _image: &Image) -> Texture2D {
+        Texture2D::empty()
+    }
+
+    fn can_load(&self, resource_type: TypeId) -> bool {
+        resource_type != TypeId::of::<GameSound>() && resource_type != TypeId::of::<Material>()
+    }
+}
+
+pub fn new_headless_game(def: &Resource<Game>, location: &Resource<Location>, seed: u64, flight: FlightSettings) -> GameState {
+    let mut state = start::new_game(def, location, seed, flight);
+    // sounds are not loaded by HeadlessBackend
+    state.subsystems.audio.muted = true;
+    state
+}
+
+pub fn simulate(state: &mut GameState, ticks: u64) {
+    for frame in 0..ticks {
+        let dt = FrameCtx {
+            frame: frame as i64,
+            dt: TICK_DT,
+        };
+        let view_port = match replay::screen_size(state) {
+            Some(screen_size) => game_viewport::create_viewport_for_screen(state, screen_size),
+            None => game_viewport::create_virtual_viewport(state),
+        };
+        update::update_game_state(state, &dt, &view_port);
+        update::update_command_queue(state);
+        // each tick is a frame for the profiler, otherwise its scopes pile up
+        perf_frame_end();
+    }
+}
+
+pub fn run(def: &Resource<Game>, ticks: u64, seed: Option<u64>, flight: FlightSettings, replay_player: Option<ReplayPlayer>, metrics_csv: Option<&str>) {
+    let mut state = match replay_player {
+        Some(player) => {
+            let mut state = replay::new_replay_game(def, player);
+            state.subsystems.audio.muted = true;
+            state
+        }
+        None => new_headless_game(def, &def.combat, seed.unwrap_or_else(rng::random_seed), flight),
+    };
+    let metrics_csv = metrics_csv.filter(|path| match state.metrics.stream_csv(path) {
+        Ok(()) => true,
+        Err(err) => {
+            error!("[METRICS] {}", err);
+            false
+        }
+    });
+    simulate(&mut state, ticks);
+    report(&state, ticks);
+    if let Some(path) = metrics_csv {
+        match state.metrics.finish_csv() {
+            Ok(()) => info!("[METRICS] exported to {}", path),
+            Err(err) => error!("[METRICS] {}", err),
+        }
+    }
+}
+
+pub fn report(state: &GameState, ticks: u64) {
+    let mobs_total = state.mobs.iter().count();
+    let mobs_destroyed = state.mobs.iter()
+        .filter(|(_, it)| matches!(it.base.durable, Durable::Destroyed(_)))
+        .count();
+    let player_alive = state.player.plane
+        .and_then(|it| state.planes.get(&it))
+        .is_some();
+    info!("[HEADLESS] seed: {}", state.seed);
+    info!("[HEADLESS] ticks: {}, simulated seconds: {:.1}", ticks, ticks as f32 * TICK_DT);
+    info!("[HEADLESS] player alive: {}, deaths: {}", player_alive, state.player.death_count);
+    info!("[HEADLESS] level: {}, xp: {}", state.player.rpg.level, state.player.rpg.xp);
+    info!("[HEADLESS] mobs destroyed: {} of {}", mobs_destroyed, mobs_total);
+    info!("[HEADLESS] resources: {:?}", state.player.resources);
+    info!("[HEADLESS] stats: {:?}", state.stats);
+}
+
+#[cfg(test)]
+pub mod tests {
+    use futures::FutureExt;
+    use crate::common::resource::{Resource, ResourceGet, ResourceManager, ResourceManagerRc};
+    use crate::lifecycle::headless::HeadlessBackend;
+    use crate::model::def::Game;
+    use crate::resources::games::game_001;
+    use crate::resources::registry;
+
+    pub fn load_game() -> Resource<Game> {
+        let rm = ResourceManager::new(Box::new(HeadlessBackend), |_, _| async {}.boxed_local());
+        registry::register_all(&rm);
+        let game = game_001.get(&rm);
+        futures::executor::block_on(rm.poll_tasks());
+        game
+    }
+}
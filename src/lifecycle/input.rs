@@ -4,6 +4,7 @@ use macroquad::input::{is_key_down, is_key_released, is_mouse_button_down, is_mo
 use std::process::exit;
 use chrono::Utc;
 use macroquad::audio::set_sound_volume;
+use macroquad::logging::{error, info};
 use macroquad::prelude::{is_key_pressed, is_mouse_button_released, screen_height, screen_width};
 use macroquad::prelude::Vec2;
 use macroquad::math::clamp;
@@ -13,15 +14,22 @@ use crate::{AppState, GameState, KeyCode, MenuState, MouseButton, start};
 use crate::common::angle::AsRadians;
 use crate::common::camera::ViewPort;
 use crate::common::contract::{Get, GetMut};
+use crate::common::perf;
 use crate::common::sound::{PlaySound, SoundList};
+use crate::common::toroidal;
 use crate::common::unsorted::{IndexRange, ToAngle};
 use crate::game::{control_guard, sounds};
 use crate::game_viewport::create_viewport;
+use crate::lifecycle::replay;
+use crate::lifecycle::replay::ReplayEvent;
 use crate::KeyCode::{A, D, M, S, W};
 use crate::model::state::Durable::Destroyed;
 use crate::model::state::{Ammo, CannonState, DeviceSpec, DeviceState, EquipmentBinding, EquipmentWindow, JournalWindow, ShopWindow, UiWindow, WeaponOwner};
 use crate::resources::constants::{DEV, SPECTATOR_SPEED};
 
+// 5 seconds at 60 fps
+const PERF_CAPTURE_FRAMES: usize = 300;
+
 static NUMBER_KEYS: &[KeyCode] = &[
     KeyCode::Key0,
     KeyCode::Key1,
@@ -41,7 +49,10 @@ pub fn process_input(mut app: AppState) -> AppState {
             AppState::Title { menu }
         }
         AppState::Game { mut game } => {
-            process_game_input(&mut game);
+            // replayed session gets controls from the file
+            if !replay::is_playing(&game) {
+                process_game_input(&mut game);
+            }
             if is_key_pressed(KeyCode::Escape) && game.player.windows.pop_back().is_none() {
                 AppState::GameMenu { game, menu: MenuState {} }
             } else {
@@ -77,15 +88,8 @@ fn process_game_input(state: &mut GameState) {
     }
     if is_key_pressed(KeyCode::Space) {
         if state.player.plane.and_then(|it| state.planes.get(&it)).is_none() {
-            state.player.death_count += 1;
-            // state.player.resources.clear();
-            for (_, device) in state.player.equipment.iter_mut() {
-                match &mut device.spec {
-                    DeviceSpec::Weapon(w) => { w.trigger = false; }
-                    DeviceSpec::Booster(w) => { w.trigger = false; }
-                }
-            }
-            start::spawn_player_plane(state);
+            replay::record(state, ReplayEvent::Respawn);
+            start::respawn_player_plane(state);
         } else if DEV {
             state.paused = !state.paused;
         }
@@ -95,6 +99,23 @@ fn process_game_input(state: &mut GameState) {
         let fname = Utc::now().format("%Y%m%d_%H%M%S%f.png").to_string();
         image.export_png(fname.as_str());
     }
+    if is_key_pressed(KeyCode::F5) {
+        state.show_metrics = !state.show_metrics;
+    }
+    if is_key_pressed(KeyCode::F6) {
+        let fname = Utc::now().format("metrics_%Y%m%d_%H%M%S.csv").to_string();
+        match state.metrics.export_csv(fname.as_str()) {
+            Ok(()) => info!("[METRICS] exported to {}", fname),
+            Err(err) => error!("[METRICS] {}", err),
+        }
+    }
+    if is_key_pressed(KeyCode::F7) {
+        state.show_perf = !state.show_perf;
+    }
+    if is_key_pressed(KeyCode::F8) {
+        let fname = Utc::now().format("trace_%Y%m%d_%H%M%S.json").to_string();
+        perf::perf_capture(fname, PERF_CAPTURE_FRAMES);
+    }
 
     let view_port = &create_viewport(state);
     control_guard::process_input(state, view_port);
@@ -187,7 +208,7 @@ fn process_game_input(state: &mut GameState) {
 
             if DEV && is_key_pressed(KeyCode::H) {
                 let closest_mob = state.mobs.iter_mut()
-                    .map(|(_, mob)| (mob.anchor.get_pos_rel().get_abs(&state.isles) - plane.trans.pos, mob))
+                    .map(|(_, mob)| (toroidal::delta(plane.trans.pos, mob.anchor.get_pos_rel().get_abs(&state.isles), state.location.size), mob))
                     .min_by_key(|(dir, mob)| dir.length() as i32);
                 if let Some((dir, mob)) = closest_mob {
                     mob.base.debug = !mob.base.debug;
@@ -200,7 +221,7 @@ fn process_game_input(state: &mut GameState) {
                     mob.base.debug_once = false;
                 }
                 let closest_mob = state.mobs.iter_mut()
-                    .map(|(_, mob)| (mob.anchor.get_pos_rel().get_abs(&state.isles) - plane.trans.pos, mob))
+                    .map(|(_, mob)| (toroidal::delta(plane.trans.pos, mob.anchor.get_pos_rel().get_abs(&state.isles), state.location.size), mob))
                     .min_by_key(|(dir, mob)| dir.length() as i32);
                 if let Some((dir, mob)) = closest_mob {
                     mob.base.debug_once = true;
@@ -50,6 +50,7 @@ fn generic_clip_split(
                 on_end
             } => SpriteClip {
                 frames: load_sprites_from_files(
+                    rm,
                     data,
                     [sprite_mods, sprite_mod].concat(),
                 ).await,
@@ -66,6 +67,7 @@ fn generic_clip_split(
                 region
             } => SpriteClip {
                 frames: load_sprites_from_sheet(
+                    &rm,
                     data,
                     sheet_size,
                     region,
@@ -3,3 +3,7 @@ pub mod start;
 pub mod update;
 pub mod input;
 pub mod loading;
+pub mod headless;
+pub mod pacing;
+pub mod replay;
+pub mod saves;
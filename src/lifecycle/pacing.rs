@@ -0,0 +1,28 @@
+use std::thread;
+use std::time::{Duration, Instant};
+use crate::conf::FramePacing;
+
+pub struct FramePacer {
+    pacing: FramePacing,
+    last_sync: Instant,
+}
+
+impl FramePacer {
+    pub fn new(pacing: FramePacing) -> FramePacer {
+        FramePacer {
+            pacing,
+            last_sync: Instant::now(),
+        }
+    }
+
+    pub fn wait(&mut self) {
+        if let FramePacing::Sleep { max_fps } = self.pacing {
+            let target_duration = Duration::from_secs_f64(1.0 / max_fps);
+            let actual_duration = self.last_sync.elapsed();
+            if actual_duration < target_duration {
+                thread::sleep(target_duration - actual_duration);
+            }
+        }
+        self.last_sync = Instant::now();
+    }
+}
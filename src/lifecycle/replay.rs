@@ -0,0 +1,359 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::rc::Rc;
+use macroquad::logging::{error, info};
+use macroquad::prelude::{screen_height, screen_width, Vec2};
+use crate::common::angle::AsRadians;
+use crate::common::contract::{Get, GetMut};
+use crate::common::resource::Resource;
+use crate::game::rpg;
+use crate::GameState;
+use crate::lifecycle::{start, update};
+use crate::model::def::{Game, ImprovementId, Location};
+use crate::model::state::{AssistLevel, DeviceId, DeviceSpec, EquipmentBinding, FlightModelSetting, FlightSettings, WindowsAction};
+
+// Replay is a plain text file, one record per line:
+//   seed <seed>
+//   location <combat|training>
+//   flight_model <per_plane|simulation|arcade>
+//   assist <off|damping|full>
+//   screen <width> <height>
+//   e <tick> <event> [args]                        - player action done right before the tick
+//   t <tick> <rot> <gear> [<device>:<0|1> ...]     - player controls as they were at the start of the tick
+// Everything else is derived from the seed, so the same build reproduces the session exactly.
+// Spectator movement of god mode is not recorded.
+
+#[derive(Clone, Debug)]
+pub enum ReplayMode {
+    Off,
+    Recording(Rc<RefCell<ReplayRecorder>>),
+    Playing(Rc<RefCell<ReplayPlayer>>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ReplayEvent {
+    Respawn,
+    Buy(usize),
+    Skill(ImprovementId),
+    Bind(DeviceId, Option<EquipmentBinding>),
+    FlightModel(FlightModelSetting),
+    Assist(AssistLevel),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct TickInput {
+    desired_rot: f32,
+    gear: usize,
+    triggers: Vec<(DeviceId, bool)>,
+}
+
+#[derive(Debug)]
+pub struct ReplayRecorder {
+    out: BufWriter<File>,
+}
+
+#[derive(Debug)]
+pub struct ReplayPlayer {
+    seed: u64,
+    location: String,
+    flight_model: FlightModelSetting,
+    assist: AssistLevel,
+    screen_size: Vec2,
+    events: VecDeque<(u64, ReplayEvent)>,
+    ticks: VecDeque<(u64, TickInput)>,
+}
+
+pub fn start_recording(state: &mut GameState, path: &str) {
+    let file = match File::create(path) {
+        Ok(file) => file,
+        Err(err) => {
+            error!("[REPLAY] failed to create {}: {}", path, err);
+            return;
+        }
+    };
+    let mut out = BufWriter::new(file);
+    if let Err(err) = write_header(&mut out, state) {
+        error!("[REPLAY] failed to write {}: {}", path, err);
+        return;
+    }
+    info!("[REPLAY] recording to {}", path);
+    state.replay = ReplayMode::Recording(Rc::new(RefCell::new(ReplayRecorder { out })));
+}
+
+pub fn load(path: &str) -> Result<ReplayPlayer, String> {
+    let file = File::open(path).map_err(|err| format!("failed to open {}: {}", path, err))?;
+    let mut player = ReplayPlayer {
+        seed: 0,
+        location: "combat".to_owned(),
+        // replays recorded before the setting existed
+        flight_model: FlightModelSetting::PerPlane,
+        assist: AssistLevel::Off,
+        screen_size: Vec2::ZERO,
+        events: Default::default(),
+        ticks: Default::default(),
+    };
+    for (i, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.map_err(|err| format!("failed to read {}: {}", path, err))?;
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let parsed = match tokens.as_slice() {
+            [] => Some(()),
+            ["seed", seed] => seed.parse().ok().map(|it| player.seed = it),
+            ["location", location] => {
+                player.location = location.to_string();
+                Some(())
+            }
+            ["flight_model", flight_model] => flight_model.parse().ok().map(|it| player.flight_model = it),
+            ["assist", assist] => assist.parse().ok().map(|it| player.assist = it),
+            ["screen", w, h] => w.parse().ok().zip(h.parse().ok()).map(|(w, h)| player.screen_size = Vec2::new(w, h)),
+            ["e", tick, event @ ..] => tick.parse().ok().zip(parse_event(event)).map(|it| player.events.push_back(it)),
+            ["t", tick, input @ ..] => tick.parse().ok().zip(parse_tick_input(input)).map(|it| player.ticks.push_back(it)),
+            _ => None,
+        };
+        if parsed.is_none() {
+            return Err(format!("{}:{}: malformed line: {}", path, i + 1, line));
+        }
+    }
+    Ok(player)
+}
+
+pub fn new_replay_game(def: &Resource<Game>, player: ReplayPlayer) -> GameState {
+    let location = if player.location == "training" { &def.training } else { &def.combat };
+    let flight = FlightSettings { flight_model: player.flight_model, assist: player.assist };
+    let mut state = start::new_game(def, location, player.seed, flight);
+    info!("[REPLAY] playing {} ticks, seed {}", player.ticks.back().map(|(tick, _)| tick + 1).unwrap_or(0), player.seed);
+    state.replay = ReplayMode::Playing(Rc::new(RefCell::new(player)));
+    state
+}
+
+pub fn is_playing(state: &GameState) -> bool {
+    matches!(state.replay, ReplayMode::Playing(_))
+}
+
+// replayed session must see the same screen as recorded, because off-screen player projectiles expire
+pub fn screen_size(state: &GameState) -> Option<Vec2> {
+    match &state.replay {
+        ReplayMode::Playing(player) => Some(player.borrow().screen_size),
+        _ => None,
+    }
+}
+
+pub fn record(state: &GameState, event: ReplayEvent) {
+    if let ReplayMode::Recording(recorder) = &state.replay {
+        let line = format!("e {} {}", state.tick, format_event(&event));
+        recorder.borrow_mut().write_line(line);
+    }
+}
+
+pub fn before_tick(state: &mut GameState) {
+    match state.replay.clone() {
+        ReplayMode::Off => {}
+        ReplayMode::Recording(recorder) => {
+            if let Some(input) = capture_tick_input(state) {
+                let line = format!("t {} {}", state.tick, format_tick_input(&input));
+                recorder.borrow_mut().write_line(line);
+            }
+        }
+        ReplayMode::Playing(player) => {
+            loop {
+                let event = {
+                    let mut player = player.borrow_mut();
+                    match player.events.front() {
+                        Some((tick, _)) if *tick <= state.tick => player.events.pop_front().map(|(_, it)| it),
+                        _ => None,
+                    }
+                };
+                match event {
+                    None => break,
+                    Some(event) => apply_event(state, event),
+                }
+            }
+            let input = {
+                let mut player = player.borrow_mut();
+                while matches!(player.ticks.front(), Some((tick, _)) if *tick < state.tick) {
+                    player.ticks.pop_front();
+                }
+                match player.ticks.front() {
+                    Some((tick, _)) if *tick == state.tick => player.ticks.pop_front().map(|(_, it)| it),
+                    _ => None,
+                }
+            };
+            if let Some(input) = input {
+                apply_tick_input(state, &input);
+            }
+            let finished = {
+                let player = player.borrow();
+                player.ticks.is_empty() && player.events.is_empty()
+            };
+            if finished {
+                info!("[REPLAY] finished at tick {}", state.tick);
+                state.replay = ReplayMode::Off;
+            }
+        }
+    }
+}
+
+impl ReplayRecorder {
+    fn write_line(&mut self, line: String) {
+        // flushed every time, because game usually ends with exit() and nothing is dropped
+        if let Err(err) = writeln!(self.out, "{}", line).and_then(|_| self.out.flush()) {
+            error!("[REPLAY] failed to write: {}", err);
+        }
+    }
+}
+
+fn write_header(out: &mut impl Write, state: &GameState) -> io::Result<()> {
+    writeln!(out, "seed {}", state.seed)?;
+    writeln!(out, "location {}", location_name(&state.def, &state.location))?;
+    writeln!(out, "flight_model {}", state.flight_model)?;
+    writeln!(out, "assist {}", state.player.assist)?;
+    writeln!(out, "screen {} {}", screen_width(), screen_height())?;
+    out.flush()
+}
+
+fn location_name(def: &Resource<Game>, location: &Resource<Location>) -> &'static str {
+    if *location == def.training { "training" } else { "combat" }
+}
+
+fn capture_tick_input(state: &GameState) -> Option<TickInput> {
+    let plane = state.player.plane.and_then(|it| state.planes.get(&it))?;
+    let mut triggers: Vec<(DeviceId, bool)> = state.player.equipment.iter()
+        .map(|(id, device)| (*id, match &device.spec {
+            DeviceSpec::Weapon(v) => v.trigger,
+            DeviceSpec::Booster(v) => v.trigger,
+        }))
+        .collect();
+    triggers.sort();
+    Some(TickInput {
+        desired_rot: plane.desired_rot.to_rad(),
+        gear: plane.gear,
+        triggers,
+    })
+}
+
+fn apply_tick_input(state: &mut GameState, input: &TickInput) {
+    if let Some(plane) = state.player.plane.and_then(|it| state.planes.get_mut(&it)) {
+        plane.desired_rot = input.desired_rot.as_radians();
+        plane.gear = input.gear;
+    }
+    for (id, value) in &input.triggers {
+        if let Some(device) = state.player.equipment.get_mut(id) {
+            match &mut device.spec {
+                DeviceSpec::Weapon(v) => v.trigger = *value,
+                DeviceSpec::Booster(v) => v.trigger = *value,
+            }
+        }
+    }
+}
+
+fn apply_event(state: &mut GameState, event: ReplayEvent) {
+    match event {
+        ReplayEvent::Respawn => start::respawn_player_plane(state),
+        ReplayEvent::Buy(index) => {
+            if let Some(item) = state.def.shop_assortment.get(index).cloned() {
+                state.ui_commands.push_back(WindowsAction::Buy(item));
+                update::update_command_queue(state);
+            }
+        }
+        ReplayEvent::Skill(skill_id) => rpg::click_skill(state, &skill_id),
+        ReplayEvent::Bind(device, binding) => rpg::bind_equipment(state, &device, &binding),
+        ReplayEvent::FlightModel(flight_model) => state.flight_model = flight_model,
+        ReplayEvent::Assist(assist) => state.player.assist = assist,
+    }
+}
+
+fn format_event(event: &ReplayEvent) -> String {
+    match event {
+        ReplayEvent::Respawn => "respawn".to_owned(),
+        ReplayEvent::Buy(index) => format!("buy {}", index),
+        ReplayEvent::Skill(skill_id) => format!("skill {}", skill_id.0),
+        ReplayEvent::Bind(device, binding) => format!("bind {} {}", device.0, match binding {
+            None => "none".to_owned(),
+            Some(binding) => format!("{:?}", binding),
+        }),
+        ReplayEvent::FlightModel(flight_model) => format!("flight_model {}", flight_model),
+        ReplayEvent::Assist(assist) => format!("assist {}", assist),
+    }
+}
+
+fn parse_event(tokens: &[&str]) -> Option<ReplayEvent> {
+    match tokens {
+        ["respawn"] => Some(ReplayEvent::Respawn),
+        ["buy", index] => index.parse().ok().map(ReplayEvent::Buy),
+        ["skill", skill_id] => skill_id.parse().ok().map(|it| ReplayEvent::Skill(ImprovementId(it))),
+        ["bind", device, "none"] => device.parse().ok().map(|it| ReplayEvent::Bind(DeviceId(it), None)),
+        ["bind", device, binding] => {
+            let binding = enum_iterator::all::<EquipmentBinding>().find(|it| format!("{:?}", it) == *binding)?;
+            device.parse().ok().map(|it| ReplayEvent::Bind(DeviceId(it), Some(binding)))
+        }
+        ["flight_model", flight_model] => flight_model.parse().ok().map(ReplayEvent::FlightModel),
+        ["assist", assist] => assist.parse().ok().map(ReplayEvent::Assist),
+        _ => None,
+    }
+}
+
+fn format_tick_input(input: &TickInput) -> String {
+    let mut line = format!("{} {}", input.desired_rot, input.gear);
+    for (id, value) in &input.triggers {
+        line += &format!(" {}:{}", id.0, if *value { 1 } else { 0 });
+    }
+    line
+}
+
+fn parse_tick_input(tokens: &[&str]) -> Option<TickInput> {
+    match tokens {
+        [rot, gear, triggers @ ..] => {
+            let triggers = triggers.iter()
+                .map(|it| {
+                    let (id, value) = it.split_once(':')?;
+                    Some((DeviceId(id.parse().ok()?), value == "1"))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(TickInput {
+                desired_rot: rot.parse().ok()?,
+                gear: gear.parse().ok()?,
+                triggers,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::lifecycle::replay::{format_event, format_tick_input, parse_event, parse_tick_input, ReplayEvent, TickInput};
+    use crate::model::def::{FlightModel, ImprovementId};
+    use crate::model::state::{AssistLevel, DeviceId, EquipmentBinding, FlightModelSetting};
+
+    #[test]
+    fn events_round_trip() {
+        let events = [
+            ReplayEvent::Respawn,
+            ReplayEvent::Buy(3),
+            ReplayEvent::Skill(ImprovementId(7)),
+            ReplayEvent::Bind(DeviceId(2), Some(EquipmentBinding::_4)),
+            ReplayEvent::Bind(DeviceId(2), None),
+            ReplayEvent::FlightModel(FlightModelSetting::Forced(FlightModel::Arcade)),
+            ReplayEvent::Assist(AssistLevel::Full),
+        ];
+        for event in events {
+            let line = format_event(&event);
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            assert_eq!(parse_event(&tokens), Some(event));
+        }
+    }
+
+    #[test]
+    fn tick_input_round_trip() {
+        let input = TickInput {
+            desired_rot: -1.2345678,
+            gear: 2,
+            triggers: vec![(DeviceId(1), true), (DeviceId(4), false)],
+        };
+        let line = format_tick_input(&input);
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        assert_eq!(parse_tick_input(&tokens), Some(input));
+    }
+}
@@ -0,0 +1,700 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::str::FromStr;
+use chrono::{DateTime, Local};
+use ini::{Ini, Properties};
+use macroquad::prelude::Vec2;
+use crate::common::angle::AsRadians;
+use crate::common::contract::{Get, GetMut, Insert};
+use crate::common::resource::Resource;
+use crate::common::rng::Rng;
+use crate::game::{bots, mobs, sky};
+use crate::GameState;
+use crate::lifecycle::start;
+use crate::model::def::{Buff, BuffSpec, Cannon, CannonPodProps, Game, ImprovementId, ImprovementSpec, Isle, Location, LocationContent, Loot, Mob, Obtainable, ProgressFlag, GameResource};
+use crate::model::state::{Ammo, CannonState, DeviceOrder, DeviceSpec, DeviceState, Durable, EquipmentBinding, FlightSettings, FlyingMobState, IsleId, IsleState, JournalStatePage, LootState, ManualBuffAmmo, ManualBuffState, MobAnchor, MobMission, MobState, RelativePos, TransState, WalkingMobState};
+
+// Save is an ini file with a section per object. Definitions are referred by their resource id
+// (for example "cannon_rail_player" or "shop_001.jet_nitro"), so saves survive the changes of
+// content order. Loading fails if the save refers an id that is not in the game anymore.
+// Transient things (projectiles, particles, rays, clouds) are not saved. Mobs start from idle phase
+// after loading, and destroyed ones are dropped.
+
+const SAVES_DIR: &str = "saves";
+const SAVE_VERSION: u32 = 1;
+
+pub const SLOTS: [&str; 3] = ["slot1", "slot2", "slot3"];
+pub const AUTOSAVE: &str = "autosave";
+
+pub fn slot_title(slot: &str) -> String {
+    match slot {
+        AUTOSAVE => "Autosave".to_owned(),
+        _ => format!("Slot {}", slot.trim_start_matches("slot")),
+    }
+}
+
+pub fn slot_time(slot: &str) -> Option<String> {
+    let modified = fs::metadata(slot_path(slot)).and_then(|it| it.modified()).ok()?;
+    Some(DateTime::<Local>::from(modified).format("%Y-%m-%d %H:%M").to_string())
+}
+
+pub fn save(state: &GameState, slot: &str) -> Result<(), String> {
+    let ini = write_game(state, &Catalog::new(&state.def)?)?;
+    let path = slot_path(slot);
+    // written aside first, so crash during autosave doesn't destroy the previous one
+    let tmp_path = path.with_extension("tmp");
+    fs::create_dir_all(SAVES_DIR)
+        .and_then(|_| ini.write_to_file(&tmp_path))
+        .and_then(|_| fs::rename(&tmp_path, &path))
+        .map_err(|err| format!("failed to write {}: {}", path.display(), err))
+}
+
+pub fn load(def: &Resource<Game>, slot: &str, flight: FlightSettings) -> Result<GameState, String> {
+    let path = slot_path(slot);
+    let ini = Ini::load_from_file(&path).map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+    read_game(&ini, def, &Catalog::new(def)?, flight)
+}
+
+fn slot_path(slot: &str) -> PathBuf {
+    PathBuf::from(SAVES_DIR).join(format!("{}.sav", slot))
+}
+
+//==================================================================================================
+
+struct Table<T> {
+    entries: Vec<(String, Resource<T>)>,
+}
+
+struct Catalog {
+    locations: Table<Location>,
+    cannons: Table<Cannon>,
+    pods: Table<CannonPodProps>,
+    buffs: Table<Buff>,
+    buff_specs: Table<BuffSpec>,
+    isles: Table<Isle>,
+    mobs: Table<Mob>,
+    loot: Table<Loot>,
+}
+
+impl<T> Table<T> {
+    fn new() -> Self {
+        Table { entries: vec![] }
+    }
+
+    // the same definition may be reachable by different paths, it's added once. definitions without
+    // id are skipped: they can't be saved, and keying them by their path would silently load
+    // something else after the content changes
+    fn add(&mut self, value: &Resource<T>) -> Result<(), String> {
+        if self.entries.iter().any(|(_, it)| it == value) {
+            return Ok(());
+        }
+        if let Some(id) = value.id() {
+            if self.entries.iter().any(|(it, _)| *it == id) {
+                return Err(format!("definition id is not unique: {}", id));
+            }
+            self.entries.push((id, value.clone()));
+        }
+        Ok(())
+    }
+
+    fn key_of(&self, value: &Resource<T>) -> Result<String, String> {
+        match self.entries.iter().find(|(_, it)| it == value) {
+            Some((key, _)) => Ok(key.clone()),
+            None if value.id().is_none() => Err("definition has no id".to_owned()),
+            None => Err("definition is not reachable from the game definition".to_owned()),
+        }
+    }
+
+    fn get(&self, key: &str) -> Result<Resource<T>, String> {
+        self.entries.iter()
+            .find(|(it, _)| it == key)
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| format!("unknown definition: {}", key))
+    }
+}
+
+impl Catalog {
+    fn new(def: &Resource<Game>) -> Result<Catalog, String> {
+        let mut catalog = Catalog {
+            locations: Table::new(),
+            cannons: Table::new(),
+            pods: Table::new(),
+            buffs: Table::new(),
+            buff_specs: Table::new(),
+            isles: Table::new(),
+            mobs: Table::new(),
+            loot: Table::new(),
+        };
+        let plane = &def.player_plane;
+        catalog.cannons.add(&plane.arms.primary_default)?;
+        catalog.pods.add(&plane.arms.primary)?;
+        catalog.pods.add(&plane.arms.secondary)?;
+        for cannon in &def.advanced_weapons.items {
+            catalog.cannons.add(cannon)?;
+        }
+        for lot in &def.shop_assortment {
+            match &lot.item {
+                Obtainable::Weapon { weapon, .. } => catalog.cannons.add(weapon)?,
+                Obtainable::Consumable { def, .. } | Obtainable::Passive { def } => catalog.buffs.add(def)?,
+                Obtainable::HP { .. } | Obtainable::PassiveReset { .. } => {}
+            }
+        }
+        for (_, skill) in def.rpg.iter() {
+            for it in &skill.levels {
+                match &it.spec {
+                    ImprovementSpec::WeaponSkill(weapon) => catalog.cannons.add(&weapon.spec)?,
+                    ImprovementSpec::BuffSkill(buff) => catalog.buffs.add(&buff.spec)?,
+                    ImprovementSpec::Passive { spec } => catalog.buff_specs.add(spec)?,
+                    ImprovementSpec::Bonus { .. } => {}
+                }
+            }
+        }
+        for location in [&def.combat, &def.training] {
+            catalog.locations.add(location)?;
+            if let Some(weapon) = &location.default_weapon {
+                catalog.cannons.add(&weapon.spec)?;
+            }
+            match &location.content {
+                LocationContent::Generator001(generator) => {
+                    for archipelago in &generator.isles {
+                        for spawn in &archipelago.isles {
+                            for mob in &spawn.mobs {
+                                catalog.add_mob(&mob.mob)?;
+                            }
+                            catalog.isles.add(&spawn.isle)?;
+                        }
+                    }
+                }
+                LocationContent::Generator002(generator) => {
+                    for layer in &generator.layers {
+                        for isle in &layer.isles {
+                            catalog.isles.add(isle)?;
+                        }
+                        for mob in &layer.mobs {
+                            catalog.add_mob(&mob.mob)?;
+                        }
+                    }
+                }
+            }
+        }
+        Ok(catalog)
+    }
+
+    fn add_mob(&mut self, mob: &Resource<Mob>) -> Result<(), String> {
+        for chance in &mob.loot_chances {
+            self.loot.add(&chance.loot)?;
+        }
+        self.mobs.add(mob)
+    }
+}
+
+//==================================================================================================
+
+fn write_game(state: &GameState, catalog: &Catalog) -> Result<Ini, String> {
+    let mut ini = Ini::new();
+
+    let mut game = Properties::new();
+    game.insert("version", SAVE_VERSION.to_string());
+    game.insert("location", catalog.locations.key_of(&state.location)?);
+    game.insert("seed", state.seed.to_string());
+    game.insert("rng", state.rng.state().to_string());
+    game.insert("tick", state.tick.to_string());
+    game.insert("prev_gid", state.gids.prev_gid.to_string());
+    game.insert("flags", join(state.progression.flags.iter().map(|it| format!("{:?}", it))));
+    let journal = state.journal.iter()
+        .map(|page| state.location.progression.iter()
+            .position(|rule| rule.journal_entry.as_ref() == Some(&page.lines))
+            .ok_or_else(|| "journal page doesn't match any progression rule".to_owned()))
+        .collect::<Result<Vec<_>, _>>()?;
+    game.insert("journal", join(journal.iter()));
+    put(&mut ini, "game", game);
+
+    let player = &state.player;
+    let mut props = Properties::new();
+    props.insert("camera_pos", vec2_to_str(player.camera_pos));
+    props.insert("death_count", player.death_count.to_string());
+    props.insert("hp_max", player.hp_max.to_string());
+    props.insert("energy_max", player.energy_max.to_string());
+    props.insert("thrust_tech_level", player.thrust_tech_level.to_string());
+    props.insert("xp", player.rpg.xp.to_string());
+    props.insert("level", player.rpg.level.to_string());
+    props.insert("skill_points", player.rpg.skill_points.to_string());
+    let mut skills: Vec<_> = player.rpg.skills.iter().collect();
+    skills.sort();
+    props.insert("skills", join(skills.iter().map(|(id, level)| format!("{}:{}", id.0, level))));
+    let mut resources: Vec<_> = player.resources.iter().map(|(res, count)| format!("{:?}:{}", res, count)).collect();
+    resources.sort();
+    props.insert("resources", join(resources.iter()));
+    put(&mut ini, "player", props);
+
+    if let Some(plane) = player.plane.and_then(|it| state.planes.get(&it)) {
+        if let Durable::Good { hp, .. } = plane.durable {
+            let mut props = Properties::new();
+            props.insert("pos", vec2_to_str(plane.trans.pos));
+            props.insert("velocity", vec2_to_str(plane.trans.velocity));
+            props.insert("angle", plane.rot.angle.to_rad().to_string());
+            props.insert("ang_velocity", plane.rot.ang_velocity_rad.to_string());
+            props.insert("desired_rot", plane.desired_rot.to_rad().to_string());
+            props.insert("gear", plane.gear.to_string());
+            props.insert("hp", hp.to_string());
+            props.insert("energy", plane.energy.to_string());
+            write_device(&mut props, "primary.", &plane.primary.spec, catalog)?;
+            if let Some(secondary) = &plane.secondary {
+                write_device(&mut props, "secondary.", &secondary.spec, catalog)?;
+            }
+            if let Some(buff) = &plane.passive_buff {
+                props.insert("passive_buff", catalog.buffs.key_of(buff)?);
+            }
+            let passive_buffs = plane.passive_buffs.iter().map(|it| catalog.buff_specs.key_of(it)).collect::<Result<Vec<_>, _>>()?;
+            props.insert("passive_buffs", join(passive_buffs.iter()));
+            let active_buffs = plane.active_buffs.iter().map(|it| catalog.buffs.key_of(it)).collect::<Result<Vec<_>, _>>()?;
+            props.insert("active_buffs", join(active_buffs.iter()));
+            put(&mut ini, "plane", props);
+        }
+    }
+
+    let mut devices: Vec<_> = player.equipment.iter().collect();
+    devices.sort_by_key(|(id, _)| **id);
+    for (i, (_, device)) in devices.into_iter().enumerate() {
+        let mut props = Properties::new();
+        props.insert("binding", match device.binding {
+            None => "none".to_owned(),
+            Some(binding) => format!("{:?}", binding),
+        });
+        props.insert("order", device.order.0.to_string());
+        write_device(&mut props, "", &device.spec, catalog)?;
+        put(&mut ini, &format!("device.{}", i), props);
+    }
+
+    let mut isle_indices = HashMap::new();
+    for (i, (id, isle)) in state.isles.iter().enumerate() {
+        isle_indices.insert(*id, i);
+        let mut props = Properties::new();
+        props.insert("def", catalog.isles.key_of(&isle.def)?);
+        props.insert("order", isle.order.to_string());
+        props.insert("pos", vec2_to_str(isle.trans.pos));
+        props.insert("velocity", vec2_to_str(isle.trans.velocity));
+        props.insert("course", vec2_to_str(isle.course));
+        props.insert("course_change_interval_last", isle.course_change_interval_last.to_string());
+        props.insert("course_seconds_remaining", isle.course_seconds_remaining.to_string());
+        props.insert("guard_count_threshold", isle.guard_count_threshold.to_string());
+        if let Some(rank) = isle.guard_rank {
+            props.insert("guard_rank", rank.to_string());
+        }
+        put(&mut ini, &format!("isle.{}", i), props);
+    }
+    let isle_index = |id: &IsleId| isle_indices.get(id)
+        .map(|it| it.to_string())
+        .ok_or_else(|| "reference to missing isle".to_owned());
+
+    let alive_mobs = state.mobs.iter().filter_map(|(_, mob)| match mob.base.durable {
+        Durable::Good { hp, .. } => Some((mob, hp)),
+        Durable::Destroyed(_) => None,
+    });
+    for (i, (mob, hp)) in alive_mobs.enumerate() {
+        let mut props = Properties::new();
+        props.insert("def", catalog.mobs.key_of(&mob.base.def)?);
+        props.insert("gid", mob.base.gid.to_string());
+        match &mob.base.mission {
+            MobMission::IsleGuard(isle) => props.insert("mission", isle_index(isle)?),
+        }
+        props.insert("hp", hp.to_string());
+        props.insert("dir", mob.base.dir.to_string());
+        props.insert("charge_spent", mob.base.charge_spent.to_string());
+        match &mob.anchor {
+            MobAnchor::Isle(isle, walking) => {
+                props.insert("isle", isle_index(isle)?);
+                props.insert("pos", vec2_to_str(walking.pos_local));
+            }
+            MobAnchor::Global(flying) => {
+                props.insert("pos", vec2_to_str(flying.pos));
+                props.insert("extra_velocity", vec2_to_str(flying.extra_velocity));
+                props.insert("swing_phase", flying.swing_phase.to_string());
+            }
+        }
+        put(&mut ini, &format!("mob.{}", i), props);
+    }
+
+    for (i, (_, loot)) in state.loot.iter().enumerate() {
+        let mut props = Properties::new();
+        props.insert("def", catalog.loot.key_of(&loot.def)?);
+        match &loot.pos {
+            RelativePos::Isle(isle, pos) => {
+                props.insert("isle", isle_index(isle)?);
+                props.insert("pos", vec2_to_str(*pos));
+            }
+            RelativePos::Global(pos) => props.insert("pos", vec2_to_str(*pos)),
+        }
+        put(&mut ini, &format!("loot.{}", i), props);
+    }
+
+    Ok(ini)
+}
+
+fn write_device(props: &mut Properties, prefix: &str, spec: &DeviceSpec, catalog: &Catalog) -> Result<(), String> {
+    let key = |name: &str| format!("{}{}", prefix, name);
+    match spec {
+        DeviceSpec::Weapon(cannon) => {
+            props.insert(key("kind"), "weapon");
+            props.insert(key("cannon"), catalog.cannons.key_of(&cannon.def)?);
+            props.insert(key("pod"), catalog.pods.key_of(&cannon.pod)?);
+            props.insert(key("recovery"), cannon.recovery_seconds.to_string());
+            props.insert(key("ammo"), match cannon.ammo {
+                Ammo::Infinite => "infinite".to_owned(),
+                Ammo::Finite(count) => format!("finite:{}", count),
+                Ammo::Energy { energy_per_shot } => format!("energy:{}", energy_per_shot),
+                Ammo::Heat { heat_per_shot, cooling_per_sec, lockout_sec } => format!("heat:{}:{}:{}", heat_per_shot, cooling_per_sec, lockout_sec),
+            });
+            if let Ammo::Heat { .. } = cannon.ammo {
+                props.insert(key("heat"), cannon.heat.to_string());
+                props.insert(key("overheat"), cannon.overheat_seconds.to_string());
+            }
+        }
+        DeviceSpec::Booster(booster) => {
+            props.insert(key("kind"), "booster");
+            props.insert(key("buff"), catalog.buffs.key_of(&booster.def)?);
+            props.insert(key("reserve"), match booster.reserve {
+                ManualBuffAmmo::Hard { reserve_sec } => format!("hard:{}", reserve_sec),
+                ManualBuffAmmo::Energy { energy_per_second } => format!("energy:{}", energy_per_second),
+            });
+        }
+    }
+    Ok(())
+}
+
+//==================================================================================================
+
+struct Section<'a> {
+    name: &'a str,
+    props: &'a Properties,
+}
+
+impl<'a> Section<'a> {
+    fn str(&self, key: &str) -> Result<&'a str, String> {
+        self.props.get(key).ok_or_else(|| format!("[{}] {} is missing", self.name, key))
+    }
+
+    fn get<T: FromStr>(&self, key: &str) -> Result<T, String> {
+        let value = self.str(key)?;
+        value.parse().map_err(|_| format!("[{}] {} is invalid: {}", self.name, key, value))
+    }
+
+    fn vec2(&self, key: &str) -> Result<Vec2, String> {
+        let value = self.str(key)?;
+        parse_vec2(value).ok_or_else(|| format!("[{}] {} is invalid: {}", self.name, key, value))
+    }
+
+    fn list(&self, key: &str) -> Result<Vec<&'a str>, String> {
+        Ok(self.str(key)?.split(',').filter(|it| !it.is_empty()).collect())
+    }
+
+    fn index(&self) -> &'a str {
+        self.name.split_once('.').map(|(_, index)| index).unwrap_or("")
+    }
+}
+
+fn section<'a>(ini: &'a Ini, name: &'a str) -> Result<Section<'a>, String> {
+    ini.section(Some(name))
+        .map(|props| Section { name, props })
+        .ok_or_else(|| format!("[{}] is missing", name))
+}
+
+fn sections<'a>(ini: &'a Ini, kind: &str) -> Vec<Section<'a>> {
+    let prefix = format!("{}.", kind);
+    ini.iter()
+        .filter_map(|(name, props)| name
+            .filter(|it| it.starts_with(&prefix))
+            .map(|name| Section { name, props }))
+        .collect()
+}
+
+fn read_game(ini: &Ini, def: &Resource<Game>, catalog: &Catalog, flight: FlightSettings) -> Result<GameState, String> {
+    let game = section(ini, "game")?;
+    let version: u32 = game.get("version")?;
+    if version != SAVE_VERSION {
+        return Err(format!("unsupported save version: {}", version));
+    }
+    let location = catalog.locations.get(game.str("location")?)?;
+    let mut state = start::new_empty_game(def, &location, game.get("seed")?, flight);
+    sky::init_clouds(&mut state);
+    bots::init_bots(&mut state);
+
+    for flag in game.list("flags")? {
+        let flag = enum_iterator::all::<ProgressFlag>()
+            .find(|it| format!("{:?}", it) == flag)
+            .ok_or_else(|| format!("unknown progress flag: {}", flag))?;
+        state.progression.flags.insert(flag);
+    }
+    for rule in game.list("journal")? {
+        let lines = rule.parse::<usize>().ok()
+            .and_then(|it| state.location.progression.get(it))
+            .and_then(|it| it.journal_entry.clone())
+            .ok_or_else(|| format!("unknown journal entry: {}", rule))?;
+        state.journal.push(JournalStatePage { lines });
+    }
+
+    let player = section(ini, "player")?;
+    state.player.camera_pos = player.vec2("camera_pos")?;
+    state.player.camera_pos_prev = state.player.camera_pos;
+    state.player.death_count = player.get("death_count")?;
+    state.player.hp_max = player.get("hp_max")?;
+    state.player.energy_max = player.get("energy_max")?;
+    state.player.thrust_tech_level = player.get("thrust_tech_level")?;
+    state.player.rpg.xp = player.get("xp")?;
+    state.player.rpg.level = player.get("level")?;
+    state.player.rpg.skill_points = player.get("skill_points")?;
+    for it in player.list("skills")? {
+        let (id, level) = it.split_once(':')
+            .and_then(|(id, level)| Some((id.parse().ok()?, level.parse().ok()?)))
+            .ok_or_else(|| format!("invalid skill: {}", it))?;
+        state.player.rpg.skills.insert(ImprovementId(id), level);
+    }
+    for it in player.list("resources")? {
+        let (res, count) = it.split_once(':')
+            .and_then(|(res, count)| Some((
+                enum_iterator::all::<GameResource>().find(|r| format!("{:?}", r) == res)?,
+                count.parse().ok()?,
+            )))
+            .ok_or_else(|| format!("invalid resource: {}", it))?;
+        state.player.resources.insert(res, count);
+    }
+
+    for device in sections(ini, "device") {
+        let binding = match device.str("binding")? {
+            "none" => None,
+            binding => Some(enum_iterator::all::<EquipmentBinding>()
+                .find(|it| format!("{:?}", it) == binding)
+                .ok_or_else(|| format!("[{}] unknown binding: {}", device.name, binding))?),
+        };
+        state.player.equipment.insert(DeviceState {
+            spec: read_device(&device, "", catalog)?,
+            binding,
+            order: DeviceOrder(device.get("order")?),
+        });
+    }
+
+    if let Ok(plane) = section(ini, "plane") {
+        start::spawn_player_plane(&mut state);
+        let primary = read_device(&plane, "primary.", catalog)?;
+        let secondary = match plane.props.contains_key("secondary.kind") {
+            true => Some(read_device(&plane, "secondary.", catalog)?),
+            false => None,
+        };
+        let passive_buff = match plane.props.get("passive_buff") {
+            Some(key) => Some(catalog.buffs.get(key)?),
+            None => None,
+        };
+        let passive_buffs = plane.list("passive_buffs")?.into_iter().map(|it| catalog.buff_specs.get(it)).collect::<Result<Vec<_>, _>>()?;
+        let active_buffs = plane.list("active_buffs")?.into_iter().map(|it| catalog.buffs.get(it)).collect::<Result<Vec<_>, _>>()?;
+        let me = state.player.plane.and_then(|it| state.planes.get_mut(&it)).unwrap();
+        me.trans.pos = plane.vec2("pos")?;
+        me.trans.velocity = plane.vec2("velocity")?;
+        me.rot.angle = plane.get::<f32>("angle")?.as_radians();
+        me.rot.ang_velocity_rad = plane.get("ang_velocity")?;
+        me.desired_rot = plane.get::<f32>("desired_rot")?.as_radians();
+        me.gear = plane.get("gear")?;
+        me.durable = Durable::new(plane.get("hp")?);
+        me.energy = plane.get("energy")?;
+        me.primary = DeviceState::new(primary);
+        me.secondary = secondary.map(DeviceState::new);
+        me.passive_buff = passive_buff;
+        me.passive_buffs = passive_buffs;
+        me.active_buffs = active_buffs;
+        me.pos_prev = me.trans.pos;
+        me.angle_prev = me.rot.angle;
+    }
+
+    let mut isle_ids: HashMap<&str, IsleId> = HashMap::new();
+    for isle in sections(ini, "isle") {
+        let pos = isle.vec2("pos")?;
+        let id = state.isles.insert(IsleState {
+            order: isle.get("order")?,
+            def: catalog.isles.get(isle.str("def")?)?,
+            trans: TransState {
+                pos,
+                velocity: isle.vec2("velocity")?,
+            },
+            course: isle.vec2("course")?,
+            course_change_interval_last: isle.get("course_change_interval_last")?,
+            course_seconds_remaining: isle.get("course_seconds_remaining")?,
+            guard_count_threshold: isle.get("guard_count_threshold")?,
+            guard_rank: match isle.props.get("guard_rank") {
+                Some(_) => Some(isle.get("guard_rank")?),
+                None => None,
+            },
+            pos_prev: pos,
+        });
+        isle_ids.insert(isle.index(), id);
+    }
+    let isle_id = |section: &Section, key: &str| -> Result<IsleId, String> {
+        let index = section.str(key)?;
+        isle_ids.get(index).copied().ok_or_else(|| format!("[{}] reference to missing isle: {}", section.name, index))
+    };
+
+    for mob in sections(ini, "mob") {
+        let def = catalog.mobs.get(mob.str("def")?)?;
+        let mut base = mobs::spawn_mob_base(&def, MobMission::IsleGuard(isle_id(&mob, "mission")?), &mut state.gids);
+        base.gid = mob.get("gid")?;
+        base.durable = Durable::new(mob.get("hp")?);
+        base.dir = mob.get("dir")?;
+        base.charge_spent = mob.get("charge_spent")?;
+        let anchor = match mob.props.contains_key("isle") {
+            true => MobAnchor::Isle(isle_id(&mob, "isle")?, WalkingMobState {
+                pos_local: mob.vec2("pos")?,
+            }),
+            false => MobAnchor::Global(FlyingMobState {
+                pos: mob.vec2("pos")?,
+                extra_velocity: mob.vec2("extra_velocity")?,
+                swing_phase: mob.get("swing_phase")?,
+            }),
+        };
+        state.mobs.insert(MobState { base, anchor, pos_prev: None, active: false });
+    }
+
+    for loot in sections(ini, "loot") {
+        let pos = match loot.props.contains_key("isle") {
+            true => RelativePos::Isle(isle_id(&loot, "isle")?, loot.vec2("pos")?),
+            false => RelativePos::Global(loot.vec2("pos")?),
+        };
+        state.loot.insert(LootState {
+            def: catalog.loot.get(loot.str("def")?)?,
+            pos,
+        });
+    }
+
+    state.tick = game.get("tick")?;
+    state.gids.prev_gid = game.get("prev_gid")?;
+    state.rng = Rng::new(game.get("rng")?);
+    Ok(state)
+}
+
+fn read_device(section: &Section, prefix: &str, catalog: &Catalog) -> Result<DeviceSpec, String> {
+    let key = |name: &str| format!("{}{}", prefix, name);
+    let invalid = |name: &str, value: &str| format!("[{}] {} is invalid: {}", section.name, key(name), value);
+    match section.str(&key("kind"))? {
+        "weapon" => {
+            let ammo = section.str(&key("ammo"))?;
+            let ammo = match ammo.split_once(':') {
+                None if ammo == "infinite" => Ammo::Infinite,
+                Some(("finite", count)) => Ammo::Finite(count.parse().map_err(|_| invalid("ammo", ammo))?),
+                Some(("energy", energy)) => Ammo::Energy { energy_per_shot: energy.parse().map_err(|_| invalid("ammo", ammo))? },
+                Some(("heat", heat)) => {
+                    let values: Vec<f32> = heat.split(':')
+                        .map(|it| it.parse().map_err(|_| invalid("ammo", ammo)))
+                        .collect::<Result<_, _>>()?;
+                    match values[..] {
+                        [heat_per_shot, cooling_per_sec, lockout_sec] => Ammo::Heat { heat_per_shot, cooling_per_sec, lockout_sec },
+                        _ => return Err(invalid("ammo", ammo)),
+                    }
+                }
+                _ => return Err(invalid("ammo", ammo)),
+            };
+            let mut cannon = CannonState::new(
+                &catalog.pods.get(section.str(&key("pod"))?)?,
+                &catalog.cannons.get(section.str(&key("cannon"))?)?,
+                ammo,
+            );
+            cannon.recovery_seconds = section.get(&key("recovery"))?;
+            if let Ammo::Heat { .. } = cannon.ammo {
+                cannon.heat = section.get(&key("heat"))?;
+                cannon.overheat_seconds = section.get(&key("overheat"))?;
+            }
+            Ok(DeviceSpec::Weapon(cannon))
+        }
+        "booster" => {
+            let reserve = section.str(&key("reserve"))?;
+            let reserve = match reserve.split_once(':') {
+                Some(("hard", sec)) => ManualBuffAmmo::Hard { reserve_sec: sec.parse().map_err(|_| invalid("reserve", reserve))? },
+                Some(("energy", energy)) => ManualBuffAmmo::Energy { energy_per_second: energy.parse().map_err(|_| invalid("reserve", reserve))? },
+                _ => return Err(invalid("reserve", reserve)),
+            };
+            Ok(DeviceSpec::Booster(ManualBuffState::new(catalog.buffs.get(section.str(&key("buff"))?)?, reserve)))
+        }
+        kind => Err(invalid("kind", kind)),
+    }
+}
+
+//==================================================================================================
+
+fn put(ini: &mut Ini, name: &str, props: Properties) {
+    ini.entry(Some(name.to_owned())).or_insert(props);
+}
+
+fn join<T: ToString>(items: impl Iterator<Item=T>) -> String {
+    items.map(|it| it.to_string()).collect::<Vec<_>>().join(",")
+}
+
+fn vec2_to_str(v: Vec2) -> String {
+    format!("{} {}", v.x, v.y)
+}
+
+fn parse_vec2(s: &str) -> Option<Vec2> {
+    let (x, y) = s.split_once(' ')?;
+    Some(Vec2::new(x.parse().ok()?, y.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use ini::Ini;
+    use crate::lifecycle::headless;
+    use crate::lifecycle::headless::tests::load_game;
+    use crate::lifecycle::saves::{Catalog, read_game, section, SAVE_VERSION, write_game};
+    use crate::model::def::{ImprovementSpec, Obtainable};
+    use crate::model::state::FlightSettings;
+
+    fn to_text(ini: &Ini) -> String {
+        let mut buf = vec![];
+        ini.write_to(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn saved_definitions_have_unique_ids() {
+        let game = load_game();
+        let catalog = Catalog::new(&game).unwrap();
+        assert!(catalog.pods.key_of(&game.player_plane.arms.primary).is_ok());
+        assert!(catalog.pods.key_of(&game.player_plane.arms.secondary).is_ok());
+        for lot in game.shop_assortment.iter() {
+            match &lot.item {
+                Obtainable::Weapon { weapon, .. } => assert!(catalog.cannons.key_of(weapon).is_ok()),
+                Obtainable::Consumable { def, .. } | Obtainable::Passive { def } => assert!(catalog.buffs.key_of(def).is_ok()),
+                Obtainable::HP { .. } | Obtainable::PassiveReset { .. } => {}
+            }
+        }
+        for (_, skill) in game.rpg.iter() {
+            for level in &skill.levels {
+                match &level.spec {
+                    ImprovementSpec::WeaponSkill(weapon) => assert!(catalog.cannons.key_of(&weapon.spec).is_ok()),
+                    ImprovementSpec::BuffSkill(buff) => assert!(catalog.buffs.key_of(&buff.spec).is_ok()),
+                    ImprovementSpec::Passive { spec } => assert!(catalog.buff_specs.key_of(spec).is_ok()),
+                    ImprovementSpec::Bonus { .. } => {}
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn save_round_trip() {
+        let game = load_game();
+        let catalog = Catalog::new(&game).unwrap();
+        let mut state = headless::new_headless_game(&game, &game.combat, 42, FlightSettings::default());
+        headless::simulate(&mut state, 120);
+
+        let saved = write_game(&state, &catalog).unwrap();
+        assert_eq!(section(&saved, "game").unwrap().get::<u32>("version").unwrap(), SAVE_VERSION);
+
+        let loaded = read_game(&saved, &game, &catalog, FlightSettings::default()).unwrap();
+        assert_eq!(to_text(&write_game(&loaded, &catalog).unwrap()), to_text(&saved));
+    }
+
+    #[test]
+    fn other_version_is_rejected() {
+        let game = load_game();
+        let catalog = Catalog::new(&game).unwrap();
+        let state = headless::new_headless_game(&game, &game.combat, 42, FlightSettings::default());
+        let mut saved = write_game(&state, &catalog).unwrap();
+        saved.with_section(Some("game")).set("version", (SAVE_VERSION + 1).to_string());
+        assert!(read_game(&saved, &game, &catalog, FlightSettings::default()).is_err());
+    }
+}
@@ -9,17 +9,20 @@ use macroquad::rand::{rand, RandomRange};
 
 use crate::{GameState, Plane, PlaneId, PlayerState};
 use crate::common::angle::AsRadians;
+use crate::common::colliders::CollisionField;
 use crate::common::contract::GetMut;
 use crate::common::enum_maps::{new_enum_map, new_enum_map_async};
 use crate::common::pool::Pool;
 use crate::common::prefs::Pref;
 use crate::common::resource::{Resource, ResourceGet, ResourceManager, ResourceManagerRc};
+use crate::common::rng::Rng;
 use crate::common::unsorted;
 use crate::common::sound::PlaySound;
 use crate::resources::objects::objects::plane001;
-use crate::game::{bots, isles, mobs, plane, rpg, sky, sounds};
+use crate::lifecycle::replay::ReplayMode;
+use crate::game::{bots, colliders, isles, mobs, plane, rpg, sky, sounds};
 use crate::model::def::{BackgroundObject, Game, ProgressFlag, Location, MaterialInstance};
-use crate::model::state::{AudioManager, BackgroundObjectState, BotState, GameProgression, Gids, LootManager, ParticlesState, RpgState, SubSystems, WeaponSelector};
+use crate::model::state::{AssistLevel, AudioManager, BackgroundObjectState, BotState, DeviceSpec, FlightModelSetting, FlightSettings, GameProgression, Gids, LootManager, ParticlesState, RpgState, SubSystems, WeaponSelector};
 use crate::rand::{ChooseRandom, gen_range};
 use crate::resources::constants::INITIAL_ENERGY;
 use crate::resources::materials::pain::pain_material;
@@ -27,14 +30,36 @@ use crate::resources::objects::arms::cannon_gatling;
 use crate::resources::objects::locations::location001::location001;
 use crate::resources::objects::objects;
 
-pub fn new_game(def: &Resource<Game>, location: &Resource<Location>) -> GameState {
-    let mut state = GameState {
+pub fn new_game(def: &Resource<Game>, location: &Resource<Location>, seed: u64, flight: FlightSettings) -> GameState {
+    let mut state = new_empty_game(def, location, seed, flight);
+    spawn_player_plane(&mut state);
+    if let Some(weapon) = &state.location.default_weapon {
+        rpg::give_equipment(&mut state.player, rpg::weapon_to_device(&def.player_plane, weapon))
+    }
+    // me.body.velocity = Vec2::ZERO;
+    // me.gear = 0;
+    // me.body.rot = 15.0 / 180.0 * PI;
+    // me.body.rot = 75.0 / 180.0 * PI;
+    // me.body.rot = (0.0 / 180.0 * PI).as_radians();
+    sky::init_clouds(&mut state);
+
+    bots::init_bots(&mut state);
+
+    isles::init_isles(&mut state);
+
+    state
+}
+
+// no player, no world. used as a base for generated and loaded games
+pub fn new_empty_game(def: &Resource<Game>, location: &Resource<Location>, seed: u64, flight: FlightSettings) -> GameState {
+    GameState {
         paused: false,
         location: location.clone(),
         player: PlayerState {
             steering: false,
             plane: None,
             camera_pos: Default::default(),
+            camera_pos_prev: Default::default(),
             god: false,
             resources: new_enum_map(|it| 0),
             windows: Default::default(),
@@ -48,7 +73,10 @@ pub fn new_game(def: &Resource<Game>, location: &Resource<Location>) -> GameStat
             death_count: 0,
             hp_max: def.player_plane.hp,
             energy_max: INITIAL_ENERGY,
-            thrust_tech_level: 0
+            thrust_tech_level: 0,
+            assist: flight.assist,
+            docked: None,
+            lock_on: None,
         },
         def: def.clone(),
         planes: Pool::new(),
@@ -60,9 +88,12 @@ pub fn new_game(def: &Resource<Game>, location: &Resource<Location>) -> GameStat
         rays: vec![],
         bots: vec![],
         projectiles: vec![],
+        debris: vec![],
         metrics: Default::default(),
         unpause_one_frame: false,
         show_colliders: false,
+        show_metrics: false,
+        show_perf: false,
         isles: Pool::new(),
         mobs: Pool::new(),
         loot: Pool::new(),
@@ -72,33 +103,55 @@ pub fn new_game(def: &Resource<Game>, location: &Resource<Location>) -> GameStat
         subsystems: SubSystems {
             audio: AudioManager {
                 volume: Pref::new("audio.volume", 1.0),
+                muted: false,
                 looped: vec![],
                 cool_down_sec: Default::default()
             },
             loot: LootManager { accumulators: Default::default() }
         },
         commands: Default::default(),
+        events: Default::default(),
         ui_commands: Default::default(),
         progression: GameProgression { flags: Default::default() },
         journal: vec![],
-        reachable_mobs: vec![]
-    };
-    spawn_player_plane(&mut state);
-    if let Some(weapon) = &state.location.default_weapon {
-        rpg::give_equipment(&mut state.player, rpg::weapon_to_device(&def.player_plane, weapon))
+        notifications: Default::default(),
+        stats: Default::default(),
+        reachable_mobs: vec![],
+        colliders: CollisionField::new(colliders::CELL_SIZE),
+        seed,
+        rng: Rng::new(seed),
+        tick_alpha: 1.0,
+        tick: 0,
+        replay: ReplayMode::Off,
+        flight_model: flight.flight_model,
     }
-    // me.body.velocity = Vec2::ZERO;
-    // me.gear = 0;
-    // me.body.rot = 15.0 / 180.0 * PI;
-    // me.body.rot = 75.0 / 180.0 * PI;
-    // me.body.rot = (0.0 / 180.0 * PI).as_radians();
-    sky::init_clouds(&mut state);
+}
 
-    bots::init_bots(&mut state);
+pub fn flight_model_pref() -> Pref<FlightModelSetting> {
+    Pref::new("physics.flight_model", FlightSettings::default().flight_model)
+}
 
-    isles::init_isles(&mut state);
+pub fn flight_assist_pref() -> Pref<AssistLevel> {
+    Pref::new("controls.flight_assist", FlightSettings::default().assist)
+}
 
-    state
+pub fn flight_settings_from_prefs() -> FlightSettings {
+    FlightSettings {
+        flight_model: flight_model_pref().get(),
+        assist: flight_assist_pref().get(),
+    }
+}
+
+pub fn respawn_player_plane(state: &mut GameState) {
+    state.player.death_count += 1;
+    // state.player.resources.clear();
+    for (_, device) in state.player.equipment.iter_mut() {
+        match &mut device.spec {
+            DeviceSpec::Weapon(w) => { w.trigger = false; }
+            DeviceSpec::Booster(w) => { w.trigger = false; }
+        }
+    }
+    spawn_player_plane(state);
 }
 
 pub fn spawn_player_plane(mut state: &mut GameState) {
@@ -5,13 +5,20 @@ use crate::{GameState, PlaneState, Vec2};
 use crate::common::camera::ViewPort;
 use crate::common::contract::{Get, GetMut, InsertSimple};
 use crate::common::frame::FrameCtx;
+use crate::common::perf::perf_scope;
 use crate::common::sprite_clip;
 use crate::common::unsorted::gen_range;
-use crate::game::{bots, cannon, plane, particles, mobs, durable, control_guard, loot, isles, rpg, progression};
+use crate::lifecycle::replay;
+use crate::lifecycle::replay::ReplayEvent;
+use crate::game::{bots, cannon, colliders, debris, events, lock_on, plane, particles, mobs, durable, control_guard, loot, isles, rpg, progression};
 use crate::model::def::{Obtainable, DeviceSlot, Collider, CollisionCircle};
-use crate::model::state::{ManualBuffState, CannonState, DamageTarget, DeviceState, Durable, GameCommand, WindowsAction, MobState, ManualBuffAmmo, LootState};
+use crate::model::state::{ManualBuffState, CannonState, DamageTarget, DeviceState, Durable, GameCommand, WindowsAction, MobState, ManualBuffAmmo, LootState, GameEvent};
 
 pub fn update_game_state(state: &mut GameState, dt: &FrameCtx, vp: &ViewPort) {
+    replay::before_tick(state);
+
+    remember_prev_tick(state);
+
     state.metrics.clear();
 
     progression::update(state);
@@ -33,32 +40,40 @@ pub fn update_game_state(state: &mut GameState, dt: &FrameCtx, vp: &ViewPort) {
         state.pos += state.velocity * dt.dt;
     }
 
-    isles::update(state, dt);
+    perf_scope("isles", || isles::update(state, dt));
+
+    perf_scope("mobs", || mobs::update(state, dt));
+
+    perf_scope("bots", || bots::update_bots(state));
+
+    perf_scope("planes", || plane::update_planes(state, dt));
 
-    mobs::update(state, dt);
+    perf_scope("isle_contact", || isles::collide_planes(state, dt));
 
-    loot::update(state, dt);
+    perf_scope("lock_on", || lock_on::update(state, dt));
 
-    bots::update_bots(state);
+    perf_scope("colliders", || colliders::update(state));
 
-    plane::update_planes(state, dt);
+    perf_scope("loot", || loot::update(state, dt));
 
-    rpg::update(state);
+    perf_scope("debris", || debris::update(state, dt));
 
-    cannon::update_projectiles(state, dt, vp);
+    perf_scope("projectiles", || cannon::update_projectiles(state, dt, vp));
 
-    while let Some(event) = state.commands.pop_front() {
+    perf_scope("commands", || while let Some(event) = state.commands.pop_front() {
         match event {
-            GameCommand::Damage { target, amount, source } => {
+            GameCommand::Damage { target, amount, kind, source } => {
                 match target {
-                    DamageTarget::Mob(target) => {
-                        if let Some(target) = state.mobs.get_mut(&target) {
-                            target.base.durable.accept_damage(amount, source);
+                    DamageTarget::Mob(id) => {
+                        let mob = state.mobs.get_mut(&id);
+                        if let Some(amount) = mob.and_then(|mob| mob.base.durable.accept_damage(amount, kind, &mob.base.def.resistances, source)) {
+                            state.events.push_back(GameEvent::DamageDealt { target, amount, kind, source });
                         }
                     }
-                    DamageTarget::Plane(target) => {
-                        if let Some(target) = state.planes.get_mut(&target) {
-                            target.durable.accept_damage(amount, source);
+                    DamageTarget::Plane(id) => {
+                        let plane = state.planes.get_mut(&id);
+                        if let Some(amount) = plane.and_then(|plane| plane.durable.accept_damage(amount, kind, &plane.def.resistances, source)) {
+                            state.events.push_back(GameEvent::DamageDealt { target, amount, kind, source });
                         }
                     }
                 }
@@ -77,8 +92,9 @@ pub fn update_game_state(state: &mut GameState, dt: &FrameCtx, vp: &ViewPort) {
                     &state.planes,
                     &state.isles,
                     &state.mobs,
-                    &state.reachable_mobs,
+                    &state.colliders,
                     &mut state.commands,
+                    &mut state.rng,
                 );
             }
             GameCommand::NewRay(ray) => state.rays.push(ray),
@@ -89,7 +105,7 @@ pub fn update_game_state(state: &mut GameState, dt: &FrameCtx, vp: &ViewPort) {
                 })
             }
         }
-    }
+    });
 
     durable::update(state, dt);
 
@@ -100,12 +116,43 @@ pub fn update_game_state(state: &mut GameState, dt: &FrameCtx, vp: &ViewPort) {
             state.player.camera_pos = plane.trans.pos;
         }
     }
+
+    perf_scope("events", || events::dispatch(state));
+
+    state.metrics.end_tick(state.tick);
+
+    state.tick += 1;
+}
+
+fn remember_prev_tick(state: &mut GameState) {
+    state.player.camera_pos_prev = state.player.camera_pos;
+    for (_, plane) in state.planes.iter_mut() {
+        plane.pos_prev = plane.trans.pos;
+        plane.angle_prev = plane.rot.angle;
+    }
+    for projectile in &mut state.projectiles {
+        projectile.pos_prev = projectile.trans.pos;
+        projectile.angle_prev = projectile.rot.angle;
+    }
+    for debris in &mut state.debris {
+        debris.pos_prev = debris.trans.pos;
+        debris.angle_prev = debris.rot.angle;
+    }
+    for (_, isle) in state.isles.iter_mut() {
+        isle.pos_prev = isle.trans.pos;
+    }
+    for (_, mob) in state.mobs.iter_mut() {
+        mob.pos_prev = Some(mob.anchor.get_pos_rel().get_abs(&state.isles));
+    }
 }
 
 pub fn update_command_queue(state: &mut GameState) {
     while let Some(action) = state.ui_commands.pop_front() {
         match action {
             WindowsAction::Buy(item) => {
+                if let Some(index) = state.def.shop_assortment.iter().position(|it| *it == item) {
+                    replay::record(state, ReplayEvent::Buy(index));
+                }
                 state.player.windows.pop_back();
                 if let Some(player) = state.player.plane.and_then(|it| state.planes.get_mut(&it)) {
                     match &item.item {
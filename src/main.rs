@@ -17,21 +17,29 @@ use futures::{AsyncReadExt, FutureExt};
 use macroquad::audio::PlaySoundParams;
 use macroquad::prelude::*;
 
-use lifecycle::{draw, input, start, update};
+use lifecycle::{draw, headless, input, replay, saves, start, update};
 use lifecycle::draw::DrawState;
+use lifecycle::headless::HeadlessBackend;
+use lifecycle::pacing::FramePacer;
+use lifecycle::replay::ReplayPlayer;
 use model::def::Plane;
 use model::state::{GameState, PlaneId, PlaneState, PlayerState};
 use resources::constants::MAX_FPS;
 
+use crate::cli::CliArgs;
 use crate::common::frame::FrameCtx;
-use crate::common::perf::{perf_report, perf_task};
-use crate::common::resource::{ResourceGet, ResourceManager, ResourceManagerRc};
+use crate::conf::{AppConf, FramePacing};
+use crate::common::backend::WindowBackend;
+use crate::common::hot_reload;
+use crate::common::perf::{perf_frame_end, perf_report, perf_scope, perf_task};
+use crate::common::resource::{Resource, ResourceGet, ResourceManager, ResourceManagerRc};
 use crate::game::{game_viewport, ui};
 use crate::game::sounds::on_pause;
 use crate::model::def::Game;
-use crate::model::state::{AppState, MenuState};
-use crate::resources::constants::{DT_MAX, DT_MIN};
+use crate::model::state::{AppState, FlightSettings, MenuState};
+use crate::resources::constants::{AUTOSAVE_INTERVAL_SEC, MAX_TICKS_PER_FRAME, TICK_DT};
 use crate::resources::games::game_001;
+use crate::resources::{content, registry};
 
 mod game;
 mod common;
@@ -39,23 +47,35 @@ mod lifecycle;
 mod resources;
 pub mod model;
 mod conf;
+mod cli;
 
 fn main() {
     perf_task("program started");
-    macroquad::Window::from_config(conf::deal_with_config_file(Conf {
-        window_title: "Forbidden Islands".to_owned(),
-        window_width: 1366,
-        window_height: 768,
-        fullscreen: true,
-        ..Default::default()
-    }), async_main());
+    let args = cli::parse_args();
+    if let Some(ticks) = args.headless_ticks {
+        // no window, so macroquad is not involved at all (see HeadlessBackend)
+        futures::executor::block_on(headless_main(args, ticks));
+        perf_task("main thread done");
+        return;
+    }
+    let conf = conf::deal_with_config_file(AppConf {
+        window: Conf {
+            window_title: "Forbidden Islands".to_owned(),
+            window_width: 1366,
+            window_height: 768,
+            fullscreen: true,
+            ..Default::default()
+        },
+        pacing: FramePacing::Sleep { max_fps: MAX_FPS },
+    });
+    macroquad::Window::from_config(conf.window, async_main(args, conf.pacing));
     perf_task("main thread done")
 }
 
-async fn async_main() {
+async fn async_main(args: CliArgs, pacing: FramePacing) {
     next_frame().await;
     perf_task("async_main entered");
-    let rm = ResourceManager::new(|enqueued, completed| {
+    let rm = ResourceManager::new(Box::new(WindowBackend), |enqueued, completed| {
         async move {
             if is_key_pressed(KeyCode::Escape) {
                 exit(0);
@@ -78,54 +98,56 @@ async fn async_main() {
 
     let mut app = AppState::Intro;
 
-    let game = game_001.get(&rm);
-
-    rm.poll_tasks().await;
-
-    perf_task("GameState constructed");
+    let game = load_game(&rm, &args).await;
+    let replay_player = load_replay(&args);
 
-    rm.perform_debug_checks();
+    if let Some(player) = replay_player {
+        app = AppState::Game { game: replay::new_replay_game(&game, player) };
+    }
 
     let mut draw_state = DrawState::new();
 
 
     perf_report();
 
-    let target_duration = Duration::from_secs_f64(1.0 / MAX_FPS);
+    let mut pacer = FramePacer::new(pacing);
 
-    let mut last_sync = std::time::Instant::now();
-        get_time();
-
-    let mut frame = 0i64;
+    let mut tick = 0i64;
+    let mut tick_accumulator = 0.0f32;
     let mut paused_prev = false;
     loop {
-        let dt = FrameCtx {
-            dt: get_frame_time().clamp(DT_MIN, DT_MAX),
-            frame,
-        };
+        // the only place where nothing refers resources, so they can be replaced
+        rm.reload_changed().await;
+
+        let frame_time = get_frame_time().min(TICK_DT * MAX_TICKS_PER_FRAME as f32);
 
-        app = input::process_input(app);
+        app = perf_scope("input", || input::process_input(app));
 
         let mut paused = true;
-        match &mut app {
+        perf_scope("update", || match &mut app {
             AppState::Title { .. } => {}
             AppState::Game { game, .. } => {
                 if !game.paused {
-                    let view_port = game_viewport::create_viewport(game);
                     if game.player.windows.is_empty() {
                         paused = false;
-                        update::update_game_state(game, &dt, &view_port);
+                        tick_accumulator += frame_time;
+                        while tick_accumulator >= TICK_DT {
+                            tick_accumulator -= TICK_DT;
+                            run_tick(game, &mut tick);
+                        }
+                        game.tick_alpha = tick_accumulator / TICK_DT;
                     } else if game.unpause_one_frame {
-                        update::update_game_state(game, &dt, &view_port);
+                        run_tick(game, &mut tick);
                         paused = false;
                         game.unpause_one_frame = false;
+                        game.tick_alpha = 1.0;
                     }
                     update::update_command_queue(game);
                 }
             }
             AppState::GameMenu { .. } => {}
             AppState::Intro => {}
-        };
+        });
         if paused != paused_prev {
             match &mut app {
                 AppState::Title { .. } => {}
@@ -137,23 +159,66 @@ async fn async_main() {
             paused_prev = paused;
         }
 
-        draw::draw_state(&mut app, &mut draw_state);
+        perf_scope("draw", || draw::draw_state(&mut app, &mut draw_state));
 
-        app = ui::do_ui(app, &draw_state, &game);
+        app = perf_scope("ui", || ui::do_ui(app, &draw_state, &game, &args));
 
-        let mut now: Instant;
-        loop {
-            now = Instant::now();
-            let actual_duration = now - last_sync;
-            if actual_duration >= target_duration {
-                break;
-            }
-            let rem =  target_duration - actual_duration;
-        }
-        last_sync = now;
+        pacer.wait();
+
+        perf_frame_end();
 
         next_frame().await;
+    }
+}
 
-        frame += 1;
+async fn headless_main(args: CliArgs, ticks: u64) {
+    let rm = ResourceManager::new(Box::new(HeadlessBackend), |_, _| async {}.boxed_local());
+    let game = load_game(&rm, &args).await;
+    let replay_player = load_replay(&args);
+    let defaults = FlightSettings::default();
+    let flight = FlightSettings {
+        flight_model: args.flight_model.unwrap_or(defaults.flight_model),
+        assist: args.assist.unwrap_or(defaults.assist),
+    };
+    headless::run(&game, ticks, args.seed, flight, replay_player, args.metrics_csv.as_deref());
+}
+
+async fn load_game(rm: &ResourceManager, args: &CliArgs) -> Resource<Game> {
+    if args.hot_reload {
+        hot_reload::enable();
+    }
+    registry::register_all(rm);
+    content::load_all(rm);
+    let game = game_001.get(rm);
+
+    rm.poll_tasks().await;
+
+    perf_task("GameState constructed");
+
+    rm.perform_debug_checks();
+    game
+}
+
+fn load_replay(args: &CliArgs) -> Option<ReplayPlayer> {
+    args.replay.as_ref().and_then(|path| match replay::load(path) {
+        Ok(player) => Some(player),
+        Err(err) => {
+            error!("[REPLAY] {}", err);
+            None
+        }
+    })
+}
+
+fn run_tick(game: &mut GameState, tick: &mut i64) {
+    let view_port = match replay::screen_size(game) {
+        Some(screen_size) => game_viewport::create_viewport_for_screen(game, screen_size),
+        None => game_viewport::create_viewport(game),
+    };
+    perf_scope("tick", || update::update_game_state(game, &FrameCtx { frame: *tick, dt: TICK_DT }, &view_port));
+    *tick += 1;
+    if game.tick % (AUTOSAVE_INTERVAL_SEC / TICK_DT) as u64 == 0 && !replay::is_playing(game) {
+        if let Err(err) = perf_scope("autosave", || saves::save(game, saves::AUTOSAVE)) {
+            error!("[SAVE] {}", err);
+        }
     }
 }
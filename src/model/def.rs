@@ -14,7 +14,7 @@ use crate::common::angle::Angle;
 use crate::common::resource::Resource;
 
 use crate::common::curve::Curve;
-use crate::common::pool::{Pool, PoolKey};
+use crate::common::pool::{Pool, PoolKey, Slot};
 use crate::game::generator_001::LocationGenerator001;
 use crate::game::generator_002::LocationGenerator002;
 use crate::{GameState, PlayerState};
@@ -29,6 +29,10 @@ pub struct Sprite {
     pub angle: Angle,
     pub region: SpriteRegion,
     pub collision_circle_normalized: Option<(Vec2, f32)>,
+    // in texture pixels from the top left corner. empty unless loaded with load_sprite_with_collision
+    pub collision_circles: Vec<CollisionCircle>,
+    // in texture pixels around the origin, covers all collision_circles
+    pub collision_bound: f32,
 }
 
 #[derive(Debug)]
@@ -43,10 +47,15 @@ pub struct SpriteRegion {
 pub struct Plane {
     pub sprite: Resource<Sprite>,
     pub hp: f32,
+    pub resistances: Resistances,
     pub gears: Vec<Gear>,
     pub default_gear: usize,
     pub trail: Option<Resource<TrailSource>>,
     pub stabilization: Resource<Stabilization>,
+    pub aerodynamics: Aerodynamics,
+    pub flight_model: FlightModel,
+    pub arcade: ArcadeFlight,
+    pub assist: FlightAssist,
     pub explosion: Option<Resource<Explosion>>,
     pub arms: PlaneArms,
     pub collision_radius: f32,
@@ -69,6 +78,50 @@ pub struct PlaneArms {
     pub secondary: Resource<CannonPodProps>,
 }
 
+#[derive(Debug)]
+pub struct Aerodynamics {
+    // dV = drag * speed^2 * dt
+    pub drag: f32,
+    // acceleration at NOMINAL_SPEED with the best attack angle, grows with speed^2
+    pub lift: f32,
+    // normalized by stall_angle
+    pub lift_by_attack: Curve<f32>,
+    // radians. no lift beyond it
+    pub stall_angle: f32,
+    pub max_speed: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlightModel {
+    // gravity, drag, slide - as is
+    Simulation,
+    // "Hollywood fake": velocity follows the nose, no slide, gravity only at low throttle
+    Arcade,
+}
+
+// tuning of FlightModel::Arcade
+#[derive(Debug)]
+pub struct ArcadeFlight {
+    // time for velocity to turn most of the way (1 - 1/e) to the nose direction
+    pub inertia_sec: f32,
+    // normalized by PLANE_THRUST_NOMINAL
+    pub gravity_by_thrust: Curve<f32>,
+}
+
+// tuning of game::flight_assist
+#[derive(Debug)]
+pub struct FlightAssist {
+    // PD controller over the angle between the nose and desired_rot
+    pub kp: f32,
+    // seconds. angular velocity is subtracted from the angle, so the turn stops in time instead of overshooting
+    pub kd: f32,
+    // nose-down recovery starts below stall_speed and ends above recovery_speed
+    pub stall_speed: f32,
+    pub recovery_speed: f32,
+    // below horizon, in the current direction
+    pub recovery_dive: Angle,
+}
+
 #[derive(Debug)]
 pub struct Stabilization {
     pub slide: SlideStabilization,
@@ -173,6 +226,7 @@ pub struct HitScan {
 #[derive(Debug)]
 pub struct HitScanAction {
     pub damage: Curve<f32>,
+    pub damage_kind: DamageKind,
     pub range: f32,
     pub collider_thickness: f32,
 }
@@ -196,6 +250,7 @@ pub struct Projectile {
     pub collision_radius: f32,
     pub splash_damage: Option<SplashDamage>,
     pub damage: Curve<f32>,
+    pub damage_kind: DamageKind,
     pub explosion: Option<ExplosionSource>,
     pub acceleration: Option<f32>,
     pub stabilization: Option<Resource<Stabilization>>,
@@ -215,8 +270,13 @@ pub enum ProjectileMod {
 #[derive(Debug)]
 pub struct SplashDamage {
     pub damage: Curve<f32>,
+    pub damage_kind: DamageKind,
     pub radius: f32,
     pub damage_factor_by_distance_norm: Curve<f32>,
+    // hurting others of the owner's faction: mobs for mob missiles, bots and the player for plane rockets
+    pub friendly_fire: bool,
+    // plane rockets hurting the plane that fired them
+    pub self_damage: bool,
 }
 
 #[derive(Debug)]
@@ -307,6 +367,7 @@ pub struct Mob {
     pub idle_seconds: Curve<f32>,
     pub collider_unscaled: CollisionCircle,
     pub hp: f32,
+    pub resistances: Resistances,
     pub attacks: Vec<Resource<MobAttack>>,
     pub pod: CannonPodProps,
     pub flier_aggro_distance: f32,
@@ -317,6 +378,7 @@ pub struct Mob {
     pub xp_reward: u32,
     pub death_sound: Option<Resource<GameSound>>,
     pub pain_sound: Option<Resource<GameSound>>,
+    pub explosion: Option<Resource<Explosion>>,
     pub burst: Option<u16>,
     pub attack_chance: f32,
 }
@@ -329,7 +391,7 @@ pub enum MobKind {
     Flyer,
 }
 
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, Sequence)]
 pub enum ProgressFlag {
     DeathTutorialShown,
     LevelUpTutorialShown,
@@ -391,7 +453,7 @@ pub struct MobCharge {
 
 #[derive(Debug)]
 pub enum MobAttackPattern {
-    Melee { connect_range: f32, damage: Curve<f32> },
+    Melee { connect_range: f32, damage: Curve<f32>, damage_kind: DamageKind },
     Distant { cannon: Resource<Cannon> },
 }
 
@@ -421,6 +483,15 @@ pub struct Explosion {
 pub struct ExplosionFragment {
     pub body: TransientBallisticBody,
     pub trail: ParticleEmitter,
+    // fragment burns out on the first plane it hits
+    pub damage: Option<FragmentDamage>,
+}
+
+#[derive(Debug)]
+pub struct FragmentDamage {
+    pub amount: f32,
+    pub kind: DamageKind,
+    pub radius: f32,
 }
 
 #[derive(Debug)]
@@ -483,18 +554,28 @@ pub struct Game {
     pub player_plane: Resource<Plane>,
     pub standard_pain_material: Resource<MaterialInstance>,
     pub advanced_weapons: WeaponSelector,
-    pub shop_assortment: Vec<Resource<ShopLot>>,
-    pub rpg: Pool<ImprovementId, Improvement>,
+    pub shop_assortment: Resource<Vec<Resource<ShopLot>>>,
+    pub rpg: Resource<Pool<ImprovementId, Improvement>>,
     pub sound_level_up: Option<Resource<GameSound>>,
     pub sound_skill_up: Option<Resource<GameSound>>,
+    pub mob_streaming: MobStreaming,
+}
+
+#[derive(Debug, Clone)]
+pub struct MobStreaming {
+    // mobs closer to the camera than this are simulated
+    pub activation_radius: f32,
+    // should be bigger than activation radius, so mobs on the border don't toggle every tick
+    pub deactivation_radius: f32,
 }
 
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug, Ord, PartialOrd)]
-pub struct ImprovementId(i64);
+// number, because it is written into saves and replays
+pub struct ImprovementId(pub i64);
 
 impl PoolKey for ImprovementId {
-    fn initial() -> Self { ImprovementId(1) }
-    fn next(&self) -> Self { ImprovementId(self.0 + 1) }
+    fn from_slot(slot: Slot) -> Self { ImprovementId(slot.to_raw()) }
+    fn slot(&self) -> Slot { Slot::from_raw(self.0) }
 }
 
 #[derive(Debug)]
@@ -505,6 +586,18 @@ pub struct Improvement {
     pub description: [&'static str; 3],
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Sequence)]
+pub enum DamageKind {
+    Kinetic,
+    Energy,
+    Explosive,
+    Fire,
+    Lava,
+}
+
+// share of the damage absorbed by kind: 1.0 is immunity, negative is a weakness. missing kinds pass in full
+pub type Resistances = HashMap<DamageKind, f32>;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Sequence)]
 pub enum ImprovementCategory {
     Passives,
@@ -540,7 +633,7 @@ pub enum ImprovementSpec {
 #[derive(Debug, Clone, PartialEq)]
 pub struct PlaneWeapon {
     pub spec: Resource<Cannon>,
-    pub energy_per_shot: f32,
+    pub ammo: Ammo,
     pub order: DeviceOrder,
 }
 
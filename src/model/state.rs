@@ -1,8 +1,10 @@
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
 use std::hash::Hash;
 use std::ops::Mul;
 use std::rc::Rc;
+use std::str::FromStr;
 use enum_iterator::Sequence;
 
 use macroquad::audio::Sound;
@@ -15,15 +17,19 @@ use macroquad::telemetry::Frame;
 use macroquad::texture::Texture2D;
 
 use crate::common::angle::Angle;
+use crate::common::colliders::CollisionField;
 use crate::common::curve::Curve;
 use crate::common::metrics::Metrics;
-use crate::common::pool::{Pool, PoolKey};
+use crate::common::pool::{Pool, PoolKey, Slot};
 use crate::common::prefs::Pref;
 use crate::common::resource::Resource;
+use crate::common::rng::Rng;
 use crate::common::unsorted::{ModifyColor, ToColor};
 use crate::{Game};
 use crate::common::camera::ViewPort;
-use crate::model::def::{Buff, BuffSpec, Cannon, CannonPodProps, CircleEffect, CollisionCircle, CollisionRay, GameResource, GameSound, HitScanRay, Isle, Location, Loot, MaterialInstance, Mob, MobAnimation, MobAttack, MobRank, Plane, Projectile, ShopLot, ImprovementId, Sprite, SpriteClip, TrailSource, PlaneWeapon, PlaneBuff, ProgressFlag, Burst};
+use crate::lifecycle::replay::ReplayMode;
+use crate::resources::constants::LOCK_ON_SECONDS;
+use crate::model::def::{Buff, BuffSpec, Cannon, CannonPodProps, CircleEffect, CollisionCircle, CollisionRay, DamageKind, Explosion, ExplosionFragment, FlightModel, GameResource, GameSound, HitScanRay, Isle, Location, Loot, MaterialInstance, Mob, MobAnimation, MobAttack, MobRank, Plane, Projectile, ShopLot, ImprovementId, Sprite, SpriteClip, TrailSource, PlaneWeapon, PlaneBuff, ProgressFlag, Burst};
 
 #[derive(Clone, Debug)]
 pub enum AppState {
@@ -43,8 +49,12 @@ pub enum AppState {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum AppStateEvent {
     NewGame { location: Resource<Location> },
+    Save { slot: &'static str },
+    Load { slot: &'static str },
     Quit,
     Intro,
+    SwitchFlightModel,
+    SwitchFlightAssist,
     Hover(Box<AppStateEvent>),
 }
 
@@ -69,19 +79,107 @@ pub struct GameState {
     pub rays: Vec<RayState>,
     pub bots: Vec<BotState>,
     pub projectiles: Vec<ProjectileState>,
+    pub debris: Vec<DebrisState>,
     pub metrics: Rc<Metrics>,
     pub unpause_one_frame: bool,
     pub show_colliders: bool,
+    pub show_metrics: bool,
+    pub show_perf: bool,
     pub isles: Pool<IsleId, IsleState>,
     pub mobs: Pool<MobId, MobState>,
     pub loot: Pool<LootId, LootState>,
     pub gids: Gids,
     pub subsystems: SubSystems,
     pub commands: VecDeque<GameCommand>,
+    // published during the tick, consumed by subscribers at the end of it (see game::events)
+    pub events: VecDeque<GameEvent>,
     pub ui_commands: VecDeque<WindowsAction>,
     pub progression: GameProgression,
     pub journal: Vec<JournalStatePage>,
+    pub notifications: VecDeque<NotificationState>,
+    pub stats: GameStats,
     pub reachable_mobs: Vec<MobId>,
+    pub colliders: CollisionField<ColliderKey>,
+    pub seed: u64,
+    pub rng: Rng,
+    // how far between previous and current tick the frame is drawn
+    pub tick_alpha: f32,
+    // number of simulated ticks since the game started
+    pub tick: u64,
+    pub replay: ReplayMode,
+    pub flight_model: FlightModelSetting,
+}
+
+// global choice from the menu. affects simulation, so written into replays
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FlightModelSetting {
+    PerPlane,
+    Forced(FlightModel),
+}
+
+impl FlightModelSetting {
+    pub fn resolve(self, plane_default: FlightModel) -> FlightModel {
+        match self {
+            FlightModelSetting::PerPlane => plane_default,
+            FlightModelSetting::Forced(it) => it,
+        }
+    }
+
+    pub fn next(self) -> FlightModelSetting {
+        match self {
+            FlightModelSetting::PerPlane => FlightModelSetting::Forced(FlightModel::Simulation),
+            FlightModelSetting::Forced(FlightModel::Simulation) => FlightModelSetting::Forced(FlightModel::Arcade),
+            FlightModelSetting::Forced(FlightModel::Arcade) => FlightModelSetting::PerPlane,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            FlightModelSetting::PerPlane => "Per Plane",
+            FlightModelSetting::Forced(FlightModel::Simulation) => "Simulation",
+            FlightModelSetting::Forced(FlightModel::Arcade) => "Arcade",
+        }
+    }
+}
+
+impl Display for FlightModelSetting {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FlightModelSetting::PerPlane => write!(f, "per_plane"),
+            FlightModelSetting::Forced(FlightModel::Simulation) => write!(f, "simulation"),
+            FlightModelSetting::Forced(FlightModel::Arcade) => write!(f, "arcade"),
+        }
+    }
+}
+
+// player's flight choices the game starts with: from the prefs in the game, from the command line
+// in headless mode, from the file when replaying
+#[derive(Clone, Copy, Debug)]
+pub struct FlightSettings {
+    pub flight_model: FlightModelSetting,
+    pub assist: AssistLevel,
+}
+
+impl Default for FlightSettings {
+    fn default() -> Self {
+        FlightSettings {
+            flight_model: FlightModelSetting::PerPlane,
+            assist: AssistLevel::Off,
+        }
+    }
+}
+
+impl FromStr for FlightModelSetting {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "per_plane" => Ok(FlightModelSetting::PerPlane),
+            "simulation" => Ok(FlightModelSetting::Forced(FlightModel::Simulation)),
+            "arcade" => Ok(FlightModelSetting::Forced(FlightModel::Arcade)),
+            _ => Err(format!("unknown flight model: {}", s)),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -124,12 +222,40 @@ pub enum EquipmentBinding {
 
 #[derive(Clone, Debug)]
 pub enum GameCommand {
-    Damage { amount: f32, source: WeaponOwner, target: DamageTarget },
+    Damage { amount: f32, kind: DamageKind, source: WeaponOwner, target: DamageTarget },
     FireCannon { bal: TransState, rot: RotState, owner: WeaponOwner, cannon: Resource<Cannon>, initial_angle: Angle },
     NewRay(RayState),
     Drop(Resource<Loot>, RelativePos),
 }
 
+// things that already happened. unlike commands, they don't change the world themselves
+#[derive(Clone, Debug)]
+pub enum GameEvent {
+    MobKilled { mob: MobId, def: Resource<Mob>, killer: WeaponOwner },
+    PlaneDestroyed { plane: PlaneId, def: Resource<Plane>, killer: WeaponOwner, player: bool },
+    LootPicked { def: Resource<Loot> },
+    LevelUp { level: u16 },
+    ObjectiveCompleted { objective: Option<&'static str>, journal_entry: Option<Vec<&'static str>> },
+    // amount after resistances
+    DamageDealt { target: DamageTarget, amount: f32, kind: DamageKind, source: WeaponOwner },
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct GameStats {
+    pub mobs_killed: u32,
+    pub deaths: u32,
+    pub loot_picked: u32,
+    pub objectives_completed: u32,
+    pub damage_dealt: f32,
+    pub damage_taken: f32,
+}
+
+#[derive(Clone, Debug)]
+pub struct NotificationState {
+    pub text: String,
+    pub tick: u64,
+}
+
 #[derive(Clone, Debug)]
 pub enum WindowsAction {
     Buy(Resource<ShopLot>),
@@ -141,6 +267,29 @@ pub enum DamageTarget {
     Plane(PlaneId),
 }
 
+impl DamageTarget {
+    pub fn faction(&self) -> Faction {
+        match self {
+            DamageTarget::Mob(_) => Faction::Mobs,
+            DamageTarget::Plane(_) => Faction::Planes,
+        }
+    }
+}
+
+// the player and bots are on the same side
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Faction {
+    Planes,
+    Mobs,
+}
+
+#[derive(Clone, Debug, Copy, Eq, PartialEq)]
+pub enum ColliderKey {
+    Plane(PlaneId),
+    Mob(MobId),
+    Loot(LootId),
+}
+
 #[derive(Clone, Debug)]
 pub struct SubSystems {
     pub audio: AudioManager,
@@ -161,6 +310,7 @@ pub struct LoopedSoundState {
 #[derive(Clone, Debug)]
 pub struct AudioManager {
     pub volume: Pref<f32>,
+    pub muted: bool,
     pub looped: Vec<LoopedSoundState>,
     pub cool_down_sec: RefCell<HashMap<Resource<GameSound>, f32>>,
 }
@@ -171,11 +321,11 @@ pub struct Gids {
 }
 
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
-pub struct IsleId(i64);
+pub struct IsleId(Slot);
 
 impl PoolKey for IsleId {
-    fn initial() -> Self { IsleId(1) }
-    fn next(&self) -> Self { IsleId(self.0 + 1) }
+    fn from_slot(slot: Slot) -> Self { IsleId(slot) }
+    fn slot(&self) -> Slot { self.0 }
 }
 
 #[derive(Clone, Debug)]
@@ -185,11 +335,11 @@ pub struct ParticlesState {
 }
 
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug)]
-pub struct PlaneId(i64);
+pub struct PlaneId(Slot);
 
 impl PoolKey for PlaneId {
-    fn initial() -> Self { PlaneId(1) }
-    fn next(&self) -> Self { PlaneId(self.0 + 1) }
+    fn from_slot(slot: Slot) -> Self { PlaneId(slot) }
+    fn slot(&self) -> Slot { self.0 }
 }
 
 #[derive(Clone, Debug)]
@@ -198,6 +348,8 @@ pub struct PlaneState {
     pub trans: TransState,
     pub rot: RotState,
     pub desired_rot: Angle,
+    // see game::flight_assist
+    pub stall_recovery: bool,
     pub gear: usize,
     pub effective_gear: usize,
     pub effective_gear_prev: Option<usize>,
@@ -209,6 +361,8 @@ pub struct PlaneState {
     pub durable: Durable,
     pub active_buffs: Vec<Resource<Buff>>,
     pub energy: f32,
+    pub pos_prev: Vec2,
+    pub angle_prev: Angle,
 }
 
 #[derive(Clone, Debug)]
@@ -230,11 +384,12 @@ pub struct DeviceState {
 pub struct DeviceOrder(pub i32);
 
 #[derive(Copy, Clone, Hash, Eq, PartialEq, Debug, Ord, PartialOrd)]
-pub struct DeviceId(i64);
+// number, because it is written into replays
+pub struct DeviceId(pub i64);
 
 impl PoolKey for DeviceId {
-    fn initial() -> Self { DeviceId(1) }
-    fn next(&self) -> Self { DeviceId(self.0 + 1) }
+    fn from_slot(slot: Slot) -> Self { DeviceId(slot.to_raw()) }
+    fn slot(&self) -> Slot { Slot::from_raw(self.0) }
 }
 
 impl DeviceState {
@@ -274,6 +429,9 @@ pub struct CannonState {
     pub recovery_seconds: f32,
     pub trigger: bool,
     pub ammo: Ammo,
+    // share of the overheat threshold, only used by Ammo::Heat
+    pub heat: f32,
+    pub overheat_seconds: f32,
 }
 
 #[derive(Clone, Debug)]
@@ -304,6 +462,8 @@ pub enum Ammo {
     Infinite,
     Finite(u32),
     Energy { energy_per_shot: f32 },
+    // weapon locks for lockout_sec when heat reaches 1.0
+    Heat { heat_per_shot: f32, cooling_per_sec: f32, lockout_sec: f32 },
 }
 
 impl CannonState {
@@ -314,8 +474,14 @@ impl CannonState {
             recovery_seconds: 0.0,
             trigger: false,
             ammo,
+            heat: 0.0,
+            overheat_seconds: 0.0,
         }
     }
+
+    pub fn is_overheated(&self) -> bool {
+        self.overheat_seconds > 0.0
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -337,6 +503,17 @@ pub enum WeaponOwner {
     Environment,
 }
 
+impl WeaponOwner {
+    // environment is on nobody's side
+    pub fn faction(&self) -> Option<Faction> {
+        match self {
+            WeaponOwner::Plane(_) => Some(Faction::Planes),
+            WeaponOwner::Mob => Some(Faction::Mobs),
+            WeaponOwner::Environment => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ProjectileState {
     pub mods: Vec<ProjectileStateMod>,
@@ -347,6 +524,28 @@ pub struct ProjectileState {
     pub remaining_seconds: Option<f32>,
     pub trail: Option<ParticleEmitterState>,
     pub exhaust_clip: Option<SpriteClipState>,
+    pub pos_prev: Vec2,
+    pub angle_prev: Angle,
+}
+
+// ExplosionFragment in flight. unlike particles it may hit, so it's simulated with the game rng
+#[derive(Clone, Debug)]
+pub struct DebrisState {
+    pub explosion: Resource<Explosion>,
+    // index in explosion.fragments
+    pub fragment: usize,
+    pub trans: TransState,
+    pub rot: RotState,
+    pub remaining_seconds: Option<f32>,
+    pub emission_queue: f32,
+    pub pos_prev: Vec2,
+    pub angle_prev: Angle,
+}
+
+impl DebrisState {
+    pub fn def(&self) -> &ExplosionFragment {
+        &self.explosion.fragments[self.fragment]
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -357,6 +556,7 @@ pub enum ProjectileStateMod {
 #[derive(Clone, Copy, Debug)]
 pub enum ProjectileHomingState {
     Plane(PlaneId),
+    Mob(MobId),
 }
 
 #[derive(Clone, Debug)]
@@ -383,6 +583,7 @@ pub struct PlayerState {
     pub god: bool,
     pub plane: Option<PlaneId>,
     pub camera_pos: Vec2,
+    pub camera_pos_prev: Vec2,
     pub resources: HashMap<GameResource, u32>,
     pub windows: VecDeque<UiWindow>,
     pub rpg: RpgState,
@@ -391,6 +592,75 @@ pub struct PlayerState {
     pub hp_max: f32,
     pub energy_max: f32,
     pub thrust_tech_level: u32,
+    pub assist: AssistLevel,
+    // landed on a cleared isle: repairing, recharging, shopping
+    pub docked: Option<IsleId>,
+    // only while a homing weapon is equipped (see game::lock_on)
+    pub lock_on: Option<LockOnState>,
+}
+
+#[derive(Clone, Debug)]
+pub struct LockOnState {
+    pub target: MobId,
+    // how long the target stays in the cone
+    pub seconds: f32,
+}
+
+impl LockOnState {
+    pub fn is_locked(&self) -> bool {
+        self.seconds >= LOCK_ON_SECONDS
+    }
+}
+
+// chosen in the menu. affects simulation, so written into replays
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssistLevel {
+    Off,
+    // PD controller instead of raw desired_rot
+    Damping,
+    // plus stall recovery
+    Full,
+}
+
+impl AssistLevel {
+    pub fn next(self) -> AssistLevel {
+        match self {
+            AssistLevel::Off => AssistLevel::Damping,
+            AssistLevel::Damping => AssistLevel::Full,
+            AssistLevel::Full => AssistLevel::Off,
+        }
+    }
+
+    pub fn title(self) -> &'static str {
+        match self {
+            AssistLevel::Off => "Off",
+            AssistLevel::Damping => "Damping",
+            AssistLevel::Full => "Full",
+        }
+    }
+}
+
+impl Display for AssistLevel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssistLevel::Off => write!(f, "off"),
+            AssistLevel::Damping => write!(f, "damping"),
+            AssistLevel::Full => write!(f, "full"),
+        }
+    }
+}
+
+impl FromStr for AssistLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(AssistLevel::Off),
+            "damping" => Ok(AssistLevel::Damping),
+            "full" => Ok(AssistLevel::Full),
+            _ => Err(format!("unknown assist level: {}", s)),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -464,11 +734,11 @@ pub struct MovingSpriteClipState {
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
-pub struct MobId(i32);
+pub struct MobId(Slot);
 
 impl PoolKey for MobId {
-    fn initial() -> Self { MobId(1) }
-    fn next(&self) -> Self { MobId(self.0 + 1) }
+    fn from_slot(slot: Slot) -> Self { MobId(slot) }
+    fn slot(&self) -> Slot { self.0 }
 }
 
 #[derive(Clone, Debug)]
@@ -480,7 +750,6 @@ pub struct MobBaseState {
     pub phase: MobPhase,
     pub animation: Option<MobAnimation>,
     pub durable: Durable,
-    pub xp_awarded: bool,
     pub death_initiated: bool,
     pub animation_ended: bool,
     pub debug_once: bool,
@@ -512,6 +781,10 @@ pub struct IsleTrans {
 pub struct MobState {
     pub base: MobBaseState,
     pub anchor: MobAnchor,
+    // absolute. None until first tick
+    pub pos_prev: Option<Vec2>,
+    // simulated, because close to the camera (see MobStreaming)
+    pub active: bool,
 }
 
 #[derive(Clone, Debug)]
@@ -591,6 +864,7 @@ pub struct IsleState {
     pub course_seconds_remaining: f32,
     pub guard_count_threshold: u32,
     pub guard_rank: Option<MobRank>,
+    pub pos_prev: Vec2,
 }
 
 #[derive(Clone, Debug)]
@@ -600,11 +874,11 @@ pub struct LootState {
 }
 
 #[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
-pub struct LootId(i32);
+pub struct LootId(Slot);
 
 impl PoolKey for LootId {
-    fn initial() -> Self { LootId(1) }
-    fn next(&self) -> Self { LootId(self.0 + 1) }
+    fn from_slot(slot: Slot) -> Self { LootId(slot) }
+    fn slot(&self) -> Slot { self.0 }
 }
 
 #[derive(Clone, Debug, Copy)]
@@ -629,3 +903,33 @@ pub enum RayTrans {
     },
     Plane(PlaneId),
 }
+
+#[cfg(test)]
+pub mod tests {
+    use crate::common::pool::{PoolKey, Slot};
+    use crate::model::def::FlightModel;
+    use crate::model::state::{FlightModelSetting, MobId, PlaneId};
+
+    // ids for tests that don't need a populated pool
+    pub fn mob(raw: i64) -> MobId {
+        MobId::from_slot(Slot::from_raw(raw))
+    }
+
+    pub fn plane(raw: i64) -> PlaneId {
+        PlaneId::from_slot(Slot::from_raw(raw))
+    }
+
+    #[test]
+    fn forced_flight_model_overrides_plane() {
+        assert_eq!(FlightModel::Simulation, FlightModelSetting::PerPlane.resolve(FlightModel::Simulation));
+        assert_eq!(FlightModel::Simulation, FlightModelSetting::Forced(FlightModel::Simulation).resolve(FlightModel::Arcade));
+        assert_eq!(FlightModel::Arcade, FlightModelSetting::Forced(FlightModel::Arcade).resolve(FlightModel::Simulation));
+    }
+
+    #[test]
+    fn flight_model_setting_round_trips_through_text() {
+        for it in [FlightModelSetting::PerPlane, FlightModelSetting::Forced(FlightModel::Simulation), FlightModelSetting::Forced(FlightModel::Arcade)] {
+            assert_eq!(Ok(it), it.to_string().parse::<FlightModelSetting>());
+        }
+    }
+}
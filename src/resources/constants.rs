@@ -13,8 +13,10 @@ pub const PLANE_THRUST_NOMINAL: f32 = 400.0 / K;
 pub const MISSILE_THRUST: f32 = 8000.0 / K;
 pub const DRAG: f32 = 0.0005 / K;
 pub const SLIDE: f32 = 0.15;
-pub const DT_MAX: f32 = 0.016;
-pub const DT_MIN: f32 = 0.008;
+// simulation step. doesn't depend on frame rate, because physics (steering especially) is sensitive to it
+pub const TICK_DT: f32 = 1.0 / 60.0;
+// if machine can't keep up, game slows down instead of trying to catch up forever
+pub const MAX_TICKS_PER_FRAME: u32 = 5;
 // used for evaluation curves based on speed
 pub const NOMINAL_SPEED: f32 = 700.0;
 
@@ -42,6 +44,30 @@ pub const INITIAL_ENERGY_RESTORE_PER_SEC: f32 = INITIAL_ENERGY / SECONDS_TO_REST
 
 pub const INITIAL_HP: f32 = 100.0;
 
+// isle shape resolution, texture pixels per collision circle
+pub const ISLE_COLLISION_CELL_PX: usize = 24;
+// share of the impact speed the plane keeps after bouncing off an isle
+pub const ISLE_BOUNCE: f32 = 0.3;
+// impacts slower than that are harmless (landing-like touches)
+pub const CRASH_SAFE_SPEED: f32 = NOMINAL_SPEED * 0.25;
+// full speed head-on crash takes about the whole initial hp
+pub const CRASH_DAMAGE_PER_SPEED: f32 = INITIAL_HP / (NOMINAL_SPEED * 0.75);
+
+// touchdown on a cleared isle slower than that docks the plane (slower than CRASH_SAFE_SPEED)
+pub const DOCK_MAX_SPEED: f32 = NOMINAL_SPEED * 0.15;
+// radians, both for the nose and for the deck slope
+pub const DOCK_MAX_TILT: f32 = 0.3;
+// weaker than the full throttle, so the plane can take off from the deck
+pub const DOCK_BRAKING: f32 = PLANE_THRUST_NOMINAL * 0.5;
+pub const DOCK_REPAIR_HP_PER_SEC: f32 = INITIAL_HP / 10.0;
+pub const DOCK_ENERGY_PER_SEC: f32 = INITIAL_ENERGY / 5.0;
+
+// player homing weapons pick targets inside the cone ahead of the nose
+pub const LOCK_ON_RANGE: f32 = 1800.0;
+// radians, half of the cone
+pub const LOCK_ON_CONE: f32 = 0.35;
+pub const LOCK_ON_SECONDS: f32 = 0.8;
+
 pub const FULL_THROTTLE_ENERGY_PER_SECOND: f32 = 15.0;
 
 pub const DEV: bool = false;
@@ -63,4 +89,8 @@ pub fn next_level_xp(current_level: u16) -> u32 {
 
 pub const XP_MUL: f32 = 1.0;
 
+// default for config.conf, when frame pacing is "sleep"
 pub const MAX_FPS: f64 = 60.0;
+
+// in simulated time, so pause and open windows don't trigger it
+pub const AUTOSAVE_INTERVAL_SEC: f32 = 120.0;
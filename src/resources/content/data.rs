@@ -0,0 +1,356 @@
+use std::collections::BTreeMap;
+use serde::Deserialize;
+
+// Mirrors of definitions in model::def, as they are written in content files.
+// References to other definitions are resource ids (see resources::registry).
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ContentFile {
+    pub projectiles: BTreeMap<String, ProjectileData>,
+    pub cannons: BTreeMap<String, CannonData>,
+    pub isles: BTreeMap<String, IsleData>,
+    pub mobs: BTreeMap<String, MobData>,
+    pub locations: BTreeMap<String, LocationData>,
+    pub shops: BTreeMap<String, Vec<ShopLotData>>,
+    pub skill_trees: BTreeMap<String, Vec<ImprovementData>>,
+}
+
+// either a single value or a list of points: `8.0` or `[8.0, 10.0]`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum CurveData<T> {
+    Const(T),
+    Points(Vec<T>),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProjectileData {
+    pub initial_speed: f32,
+    pub sprite: Option<String>,
+    pub drag: Option<f32>,
+    pub seconds_to_live: Option<f32>,
+    pub collision_radius: f32,
+    pub damage: CurveData<f32>,
+    // DamageKind name, Kinetic if omitted
+    #[serde(default)]
+    pub damage_kind: Option<String>,
+    pub splash_damage: Option<SplashDamageData>,
+    pub explosion: Option<ExplosionSourceData>,
+    pub acceleration: Option<f32>,
+    pub stabilization: Option<String>,
+    pub trail: Option<TrailData>,
+    pub exhaust_clip: Option<String>,
+    pub hit_sound: Option<String>,
+    #[serde(default)]
+    pub rotation: RotationData,
+    pub pulsation: Option<CurveData<f32>>,
+    #[serde(default)]
+    pub homing: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SplashDamageData {
+    pub damage: CurveData<f32>,
+    // Explosive if omitted
+    #[serde(default)]
+    pub damage_kind: Option<String>,
+    pub radius: f32,
+    pub damage_factor_by_distance_norm: CurveData<f32>,
+    #[serde(default)]
+    pub friendly_fire: bool,
+    #[serde(default)]
+    pub self_damage: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ExplosionSourceData {
+    pub offset: (f32, f32),
+    pub explosion: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TrailData {
+    pub offset: (f32, f32),
+    pub emitter: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub enum RotationData {
+    #[default]
+    InitialVelocity,
+    Spinning(f32),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CannonData {
+    pub title: String,
+    pub rate: f32,
+    pub barrel: BarrelData,
+    pub spread_degrees: CurveData<f32>,
+    pub sound: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub enum BarrelData {
+    Projectile(String),
+    HitScan(HitScanData),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HitScanData {
+    pub damage: CurveData<f32>,
+    #[serde(default)]
+    pub damage_kind: Option<String>,
+    pub range: f32,
+    #[serde(default)]
+    pub collider_thickness: f32,
+    pub ray: Option<RayData>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RayData {
+    pub width: CurveData<f32>,
+    pub color: CurveData<String>,
+    pub duration_sec: f32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IsleData {
+    pub sprite: String,
+    pub scale: f32,
+    pub bounds: (f32, f32),
+    pub course_change_interval_seconds: CurveData<f32>,
+    pub drift_speed: f32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MobData {
+    pub rank: u32,
+    pub kind: MobKindData,
+    pub sprite_set: String,
+    pub tint: Option<String>,
+    pub scale: f32,
+    pub move_speed: f32,
+    pub move_seconds: CurveData<f32>,
+    pub idle_seconds: CurveData<f32>,
+    pub collider: CircleData,
+    pub hp: f32,
+    // DamageKind name to absorbed share
+    #[serde(default)]
+    pub resistances: BTreeMap<String, f32>,
+    pub attacks: Vec<MobAttackData>,
+    pub pod_offset: (f32, f32),
+    #[serde(default)]
+    pub flier_aggro_distance: f32,
+    #[serde(default)]
+    pub flier_chase_step: f32,
+    #[serde(default)]
+    pub loot: Vec<LootChanceData>,
+    pub xp_reward: u32,
+    pub death_sound: Option<String>,
+    pub pain_sound: Option<String>,
+    #[serde(default)]
+    pub explosion: Option<String>,
+    pub burst: Option<u16>,
+    pub attack_chance: f32,
+}
+
+#[derive(Debug, Deserialize)]
+pub enum MobKindData {
+    Walker,
+    Flyer,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CircleData {
+    pub center: (f32, f32),
+    pub radius: f32,
+}
+
+#[derive(Debug, Deserialize)]
+pub enum MobAttackData {
+    Ref(String),
+    Inline(MobAttackSpecData),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MobAttackSpecData {
+    pub trigger_range: f32,
+    pub hold_sec: f32,
+    pub cooldown_sec: CurveData<f32>,
+    pub pattern: AttackPatternData,
+    pub charge: Option<ChargeData>,
+    pub burst: Option<BurstData>,
+    #[serde(default)]
+    pub late_aim: bool,
+    pub hold_circle: Option<CircleEffectData>,
+}
+
+#[derive(Debug, Deserialize)]
+pub enum AttackPatternData {
+    Melee {
+        connect_range: f32,
+        damage: CurveData<f32>,
+        #[serde(default)]
+        damage_kind: Option<String>,
+    },
+    Distant { cannon: String },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ChargeData {
+    pub velocity: f32,
+    pub duration_sec: f32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BurstData {
+    pub rounds_in_row: u16,
+    pub cannon: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CircleEffectData {
+    pub radius: CurveData<f32>,
+    pub color: CurveData<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LootChanceData {
+    pub loot: String,
+    pub probability: f32,
+    #[serde(default)]
+    pub rule: LootRuleData,
+}
+
+// predicates are code, so only these ones are available for content files
+#[derive(Debug, Default, Deserialize)]
+pub enum LootRuleData {
+    #[default]
+    Always,
+    // name of GameResource
+    Found(String),
+    NotFound(String),
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LocationData {
+    pub sky: CurveData<String>,
+    pub size: (f32, f32),
+    pub start_pos_norm: (f32, f32),
+    #[serde(default)]
+    pub background_objects: Vec<BackgroundObjectData>,
+    pub layers: Vec<LayerData>,
+    #[serde(default)]
+    pub lava: bool,
+    // names of built-in objective sets: "tutorial", "location003"
+    #[serde(default)]
+    pub objectives: Vec<String>,
+    pub default_weapon: Option<WeaponData>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BackgroundObjectData {
+    pub z: CurveData<f32>,
+    pub size: CurveData<f32>,
+    pub height_normal: Option<CurveData<f32>>,
+    pub sprites: Vec<String>,
+    pub count: CurveData<i32>,
+    pub material: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LayerData {
+    pub name: String,
+    pub mobs: Vec<(String, u32)>,
+    pub mob_per_isle: CurveData<f32>,
+    pub isles_per_archipelago: CurveData<f32>,
+    pub isles: Vec<String>,
+    pub height_normal: CurveData<f32>,
+    pub x_normal: CurveData<f32>,
+    #[serde(default)]
+    pub rank_minimap_penalty: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WeaponData {
+    pub cannon: String,
+    pub ammo: AmmoData,
+    pub order: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShopLotData {
+    // GameResource name -> count
+    #[serde(default)]
+    pub price: BTreeMap<String, u32>,
+    pub item: ObtainableData,
+}
+
+#[derive(Debug, Deserialize)]
+pub enum ObtainableData {
+    Weapon { slot: SlotData, cannon: String, ammo: AmmoData },
+    HP { title: String, amount: f32 },
+    Consumable { slot: SlotData, buff: BuffData, reserve_sec: f32 },
+    Passive { buff: BuffData },
+    PassiveReset { title: String },
+}
+
+#[derive(Debug, Deserialize)]
+pub enum SlotData {
+    Primary,
+    Secondary,
+}
+
+#[derive(Debug, Deserialize)]
+pub enum AmmoData {
+    Infinite,
+    Finite(u32),
+    Energy(f32),
+    Heat { heat_per_shot: f32, cooling_per_sec: f32, lockout_sec: f32 },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BuffData {
+    // buffs are built in place, but saves refer them, so they need ids too
+    pub id: String,
+    pub title: String,
+    pub spec: BuffSpecData,
+}
+
+#[derive(Debug, Deserialize)]
+pub enum BuffSpecData {
+    ThrustAddendum { extra_acceleration: f32 },
+    Nitro { smoke_factor: f32, top_speed: f32, acceleration_by_speed: CurveData<f32> },
+    ThrustMultiplier { acceleration_multiplier: f32, smoke_factor_rel: f32 },
+    SteerBooster { steering_by_speed: CurveData<f32>, steering_by_attack: CurveData<f32>, max_angular_acceleration: f32, smoke_factor_abs: f32 },
+    Repair { hp_per_sec: f32 },
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImprovementData {
+    // if omitted, title is taken from the weapon or buff of the first level
+    pub title: Option<String>,
+    // name of ImprovementCategory
+    pub category: String,
+    pub levels: Vec<ImprovementLevelData>,
+    #[serde(default)]
+    pub description: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImprovementLevelData {
+    pub spec: ImprovementSpecData,
+    pub points: u32,
+}
+
+#[derive(Debug, Deserialize)]
+pub enum ImprovementSpecData {
+    Weapon { cannon: String, ammo: AmmoData, order: i32 },
+    Buff { buff: BuffData, energy_per_second: f32, order: i32 },
+    Passive { id: String, spec: BuffSpecData },
+    Power(f32),
+    Armor(f32),
+    ThrustTechInc,
+}
@@ -0,0 +1,594 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+use enum_iterator::Sequence;
+use macroquad::logging::{error, info};
+use macroquad::prelude::{Color, Vec2, YELLOW};
+use crate::common::curve::Curve;
+use crate::common::hot_reload;
+use crate::common::hot_reload::Reload;
+use crate::common::pool::Pool;
+use crate::common::resource::{Resource, ResourceManager};
+use crate::game::generator_002::{Layer, LocationGenerator002, MobConfig};
+use crate::model::def::{BackgroundObject, BonusSpec, Buff, BuffSpec, Burst, Cannon, CannonBarrel, CannonPodProps, CircleEffect, CollisionCircle, DamageKind, DeviceSlot, ExplosionSource, GameResource, HitScan, HitScanAction, HitScanLook, HitScanRay, HoldEffect, Improvement, ImprovementCategory, ImprovementId, ImprovementLevel, ImprovementSpec, ImprovementTitle, Isle, Location, LocationContent, MobAttack, MobAttackPattern, MobCharge, MobKind, MobLootChance, Mob, Obtainable, PlaneBuff, PlaneWeapon, ProgressPredicate, ProgressPredicateFn, Projectile, ProjectileMod, ProjectilePulsation, ProjectileRot, ShopLot, Sky, SplashDamage, SteerStabilization, TrailSource, TransientBallisticBody};
+use crate::model::state::{Ammo, DeviceOrder};
+use crate::resources::constants::{DRAG, standard_lava_damage_per_sec_norm};
+use crate::resources::content::data::*;
+use crate::resources::materials::huer::create_huer_material;
+use crate::resources::objects::locations::{location003_objectives, tutorial_objectives};
+use crate::resources::objects::mobs::is_present_resource;
+
+pub mod data;
+
+// Content files are RON files in the "content" directory, loaded in the order of their names.
+// Every entry has an id. If a built-in definition has the same id, it's replaced (and everything
+// built-in that refers it gets the new one), otherwise the entry is a new definition available
+// to the later entries and files by its id. Entries are built category by category in the order
+// of ContentFile fields, so they can refer only the categories above them (and any built-in).
+// Subdirectories are not scanned, so examples can be kept in "content/examples".
+// With hot reload all the files are loaded again when any of them changes. Added files are not
+// noticed until restart.
+
+const CONTENT_DIR: &str = "content";
+
+// must be called before resources are sealed, i.e. before the first `poll_tasks`
+pub fn load_all(rm: &ResourceManager) {
+    let mut content = ContentFile::default();
+    for path in list_files() {
+        hot_reload::watch(&path, Rc::new(ContentReload { rm: rm.clone() }));
+        match read_file(&path) {
+            Ok(file) => {
+                info!("[CONTENT] loaded {}", path.display());
+                merge(&mut content, file);
+            }
+            Err(err) => error!("[CONTENT] {}", err),
+        }
+    }
+    define_all(rm, content);
+}
+
+struct ContentReload {
+    rm: ResourceManager,
+}
+
+impl Reload for ContentReload {
+    fn key(&self) -> usize {
+        CONTENT_DIR.as_ptr() as usize
+    }
+
+    fn reload(&self) {
+        load_all(&self.rm);
+    }
+}
+
+fn list_files() -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = match fs::read_dir(CONTENT_DIR) {
+        Ok(entries) => entries
+            .filter_map(|it| it.ok())
+            .map(|it| it.path())
+            .filter(|it| it.is_file() && it.extension().map(|ext| ext == "ron").unwrap_or(false))
+            .collect(),
+        Err(_) => vec![],
+    };
+    paths.sort();
+    paths
+}
+
+fn read_file(path: &PathBuf) -> Result<ContentFile, String> {
+    let text = fs::read_to_string(path).map_err(|err| format!("failed to read {}: {}", path.display(), err))?;
+    ron::from_str(&text).map_err(|err| format!("{}: {}", path.display(), err))
+}
+
+// later files win
+fn merge(content: &mut ContentFile, file: ContentFile) {
+    content.projectiles.extend(file.projectiles);
+    content.cannons.extend(file.cannons);
+    content.isles.extend(file.isles);
+    content.mobs.extend(file.mobs);
+    content.locations.extend(file.locations);
+    content.shops.extend(file.shops);
+    content.skill_trees.extend(file.skill_trees);
+}
+
+fn define_all(rm: &ResourceManager, content: ContentFile) {
+    define_each(rm, "projectile", &content.projectiles, build_projectile);
+    define_each(rm, "cannon", &content.cannons, build_cannon);
+    define_each(rm, "isle", &content.isles, build_isle);
+    define_each(rm, "mob", &content.mobs, build_mob);
+    define_each(rm, "location", &content.locations, build_location);
+    define_each(rm, "shop", &content.shops, build_shop);
+    define_each(rm, "skill tree", &content.skill_trees, build_skill_tree);
+}
+
+fn define_each<D, T: 'static + Debug>(
+    rm: &ResourceManager,
+    kind: &str,
+    entries: &BTreeMap<String, D>,
+    build: fn(&ResourceManager, &D) -> Result<T, String>,
+) {
+    for (id, data) in entries {
+        match build(rm, data) {
+            Ok(value) => {
+                rm.define(id, value);
+            }
+            Err(err) => error!("[CONTENT] {} {}: {}", kind, id, err),
+        }
+    }
+}
+
+//==================================================================================================
+
+fn get<T: 'static + Debug>(rm: &ResourceManager, id: &str) -> Result<Resource<T>, String> {
+    rm.get_by_id(id).ok_or_else(|| format!("unknown {}: {}", std::any::type_name::<T>().rsplit("::").next().unwrap_or("resource"), id))
+}
+
+fn get_opt<T: 'static + Debug>(rm: &ResourceManager, id: &Option<String>) -> Result<Option<Resource<T>>, String> {
+    id.as_ref().map(|id| get(rm, id)).transpose()
+}
+
+fn damage_kind(name: &Option<String>, default: DamageKind) -> Result<DamageKind, String> {
+    name.as_deref().map(by_name).transpose().map(|it| it.unwrap_or(default))
+}
+
+fn by_name<T: Sequence + Debug>(name: &str) -> Result<T, String> {
+    enum_iterator::all::<T>()
+        .find(|it| format!("{:?}", it) == name)
+        .ok_or_else(|| format!("unknown {}: {}", std::any::type_name::<T>().rsplit("::").next().unwrap_or("name"), name))
+}
+
+thread_local! {
+    static INTERNED: RefCell<HashSet<&'static str>> = RefCell::new(HashSet::new());
+}
+
+// definitions keep static strings. content is loaded again on every hot reload, so the strings
+// are interned: only the edited ones are leaked
+fn intern(s: &str) -> &'static str {
+    INTERNED.with(|it| {
+        let mut interned = it.borrow_mut();
+        if let Some(s) = interned.get(s) {
+            return *s;
+        }
+        let s: &'static str = Box::leak(s.to_owned().into_boxed_str());
+        interned.insert(s);
+        s
+    })
+}
+
+fn vec2((x, y): (f32, f32)) -> Vec2 {
+    Vec2::new(x, y)
+}
+
+fn color(s: &str) -> Result<Color, String> {
+    let c = csscolorparser::parse(s).map_err(|err| format!("invalid color {}: {}", s, err))?;
+    Ok(Color::new(c.r as f32, c.g as f32, c.b as f32, c.a as f32))
+}
+
+fn curve<T: Clone>(data: &CurveData<T>) -> Result<Curve<T>, String> {
+    let points = match data {
+        CurveData::Const(value) => vec![value.clone()],
+        CurveData::Points(points) => points.clone(),
+    };
+    if points.is_empty() {
+        return Err("curve must contain at least one point".to_owned());
+    }
+    Ok(Curve::Manual { points })
+}
+
+fn color_curve(data: &CurveData<String>) -> Result<Curve<Color>, String> {
+    let points = match data {
+        CurveData::Const(value) => CurveData::Const(color(value)?),
+        CurveData::Points(points) => CurveData::Points(points.iter().map(|it| color(it)).collect::<Result<_, _>>()?),
+    };
+    curve(&points)
+}
+
+//==================================================================================================
+
+fn build_projectile(rm: &ResourceManager, data: &ProjectileData) -> Result<Projectile, String> {
+    Ok(Projectile {
+        body: TransientBallisticBody {
+            initial_speed: data.initial_speed,
+            sprite: get_opt(rm, &data.sprite)?,
+            drag: data.drag.unwrap_or(DRAG),
+            seconds_to_live: data.seconds_to_live,
+        },
+        collision_radius: data.collision_radius,
+        splash_damage: match &data.splash_damage {
+            None => None,
+            Some(splash) => Some(SplashDamage {
+                damage: curve(&splash.damage)?,
+                damage_kind: damage_kind(&splash.damage_kind, DamageKind::Explosive)?,
+                radius: splash.radius,
+                damage_factor_by_distance_norm: curve(&splash.damage_factor_by_distance_norm)?,
+                friendly_fire: splash.friendly_fire,
+                self_damage: splash.self_damage,
+            }),
+        },
+        damage: curve(&data.damage)?,
+        damage_kind: damage_kind(&data.damage_kind, DamageKind::Kinetic)?,
+        explosion: match &data.explosion {
+            None => None,
+            Some(explosion) => Some(ExplosionSource {
+                offset: vec2(explosion.offset),
+                explosion: get(rm, &explosion.explosion)?,
+            }),
+        },
+        acceleration: data.acceleration,
+        stabilization: get_opt(rm, &data.stabilization)?,
+        trail: match &data.trail {
+            None => None,
+            Some(trail) => Some(Resource::detached(TrailSource {
+                offset: vec2(trail.offset),
+                emitter: get(rm, &trail.emitter)?,
+            })),
+        },
+        exhaust_clip: get_opt(rm, &data.exhaust_clip)?,
+        hit_sound: get_opt(rm, &data.hit_sound)?,
+        rotation: match data.rotation {
+            RotationData::InitialVelocity => ProjectileRot::InitialVelocity,
+            RotationData::Spinning(degrees_per_second) => ProjectileRot::Spinning { degrees_per_second },
+        },
+        pulsation: match &data.pulsation {
+            None => None,
+            Some(scale) => Some(ProjectilePulsation { scale: curve(scale)? }),
+        },
+        mods: if data.homing { vec![ProjectileMod::Homing] } else { vec![] },
+    })
+}
+
+fn build_cannon(rm: &ResourceManager, data: &CannonData) -> Result<Cannon, String> {
+    Ok(Cannon {
+        title: intern(&data.title),
+        rate: data.rate,
+        barrel: match &data.barrel {
+            BarrelData::Projectile(id) => CannonBarrel::Projectile(get(rm, id)?),
+            BarrelData::HitScan(hit_scan) => CannonBarrel::HitScan(Resource::detached(HitScan {
+                action: HitScanAction {
+                    damage: curve(&hit_scan.damage)?,
+                    damage_kind: damage_kind(&hit_scan.damage_kind, DamageKind::Kinetic)?,
+                    range: hit_scan.range,
+                    collider_thickness: hit_scan.collider_thickness,
+                },
+                look: match &hit_scan.ray {
+                    None => HitScanLook::None,
+                    Some(ray) => HitScanLook::Ray(Resource::detached(HitScanRay {
+                        width: curve(&ray.width)?,
+                        color: color_curve(&ray.color)?,
+                        duration_sec: ray.duration_sec,
+                    })),
+                },
+            })),
+        },
+        spread_degrees: curve(&data.spread_degrees)?,
+        sound: get_opt(rm, &data.sound)?,
+    })
+}
+
+fn build_isle(rm: &ResourceManager, data: &IsleData) -> Result<Isle, String> {
+    Ok(Isle {
+        scale: data.scale,
+        sprite: get(rm, &data.sprite)?,
+        bounds: data.bounds.0..data.bounds.1,
+        course_change_interval_seconds: curve(&data.course_change_interval_seconds)?,
+        drift_speed: data.drift_speed,
+    })
+}
+
+fn build_mob(rm: &ResourceManager, data: &MobData) -> Result<Mob, String> {
+    Ok(Mob {
+        rank: data.rank,
+        sprite_set: get(rm, &data.sprite_set)?,
+        scale: data.scale,
+        move_speed: data.move_speed,
+        move_seconds: curve(&data.move_seconds)?,
+        idle_seconds: curve(&data.idle_seconds)?,
+        collider_unscaled: CollisionCircle { center: vec2(data.collider.center), radius: data.collider.radius },
+        hp: data.hp,
+        resistances: data.resistances.iter()
+            .map(|(kind, value)| resistance(kind, *value))
+            .collect::<Result<_, String>>()?,
+        attacks: data.attacks.iter().map(|it| build_mob_attack(rm, it)).collect::<Result<_, _>>()?,
+        pod: CannonPodProps { offset: vec2(data.pod_offset) },
+        flier_aggro_distance: data.flier_aggro_distance,
+        flier_chase_step: data.flier_chase_step,
+        material: match &data.tint {
+            None => None,
+            Some(tint) => Some(Resource::detached(create_huer_material(rm, YELLOW, color(tint)?))),
+        },
+        loot_chances: data.loot.iter()
+            .map(|it| -> Result<_, String> {
+                Ok(MobLootChance {
+                    probability: it.probability,
+                    loot: get(rm, &it.loot)?,
+                    rule: ProgressPredicate(loot_rule(&it.rule)?),
+                })
+            })
+            .collect::<Result<_, _>>()?,
+        kind: match data.kind {
+            MobKindData::Walker => MobKind::Walker,
+            MobKindData::Flyer => MobKind::Flyer,
+        },
+        xp_reward: data.xp_reward,
+        death_sound: get_opt(rm, &data.death_sound)?,
+        pain_sound: get_opt(rm, &data.pain_sound)?,
+        explosion: get_opt(rm, &data.explosion)?,
+        burst: data.burst,
+        attack_chance: data.attack_chance,
+    })
+}
+
+// above 1 damage would heal, below 0 is a weakness
+fn resistance(kind: &str, value: f32) -> Result<(DamageKind, f32), String> {
+    if value > 1.0 {
+        return Err(format!("resistance to {} above 1: {}", kind, value));
+    }
+    Ok((by_name::<DamageKind>(kind)?, value))
+}
+
+fn build_mob_attack(rm: &ResourceManager, data: &MobAttackData) -> Result<Resource<MobAttack>, String> {
+    let spec = match data {
+        MobAttackData::Ref(id) => return get(rm, id),
+        MobAttackData::Inline(spec) => spec,
+    };
+    Ok(Resource::detached(MobAttack {
+        trigger_range: spec.trigger_range,
+        hold_sec: spec.hold_sec,
+        cooldown_sec: curve(&spec.cooldown_sec)?,
+        pattern: match &spec.pattern {
+            AttackPatternData::Melee { connect_range, damage, damage_kind: kind } => MobAttackPattern::Melee {
+                connect_range: *connect_range,
+                damage: curve(damage)?,
+                damage_kind: damage_kind(kind, DamageKind::Kinetic)?,
+            },
+            AttackPatternData::Distant { cannon } => MobAttackPattern::Distant { cannon: get(rm, cannon)? },
+        },
+        charge: spec.charge.as_ref().map(|it| MobCharge { velocity: it.velocity, duration_sec: it.duration_sec }),
+        burst: match &spec.burst {
+            None => None,
+            Some(burst) => Some(Resource::detached(Burst { rounds_in_row: burst.rounds_in_row, cannon: get(rm, &burst.cannon)? })),
+        },
+        late_aim: spec.late_aim,
+        hold_effect: match &spec.hold_circle {
+            None => None,
+            Some(circle) => Some(HoldEffect::Circle(Resource::detached(CircleEffect {
+                radius: curve(&circle.radius)?,
+                color: color_curve(&circle.color)?,
+            }))),
+        },
+    }))
+}
+
+fn loot_rule(data: &LootRuleData) -> Result<ProgressPredicateFn, String> {
+    use GameResource::{A, B, C};
+    let rule: ProgressPredicateFn = match data {
+        LootRuleData::Always => |_| true,
+        LootRuleData::Found(resource) => match by_name::<GameResource>(resource)? {
+            A => |game| is_present_resource(game.player, game.loot, A),
+            B => |game| is_present_resource(game.player, game.loot, B),
+            C => |game| is_present_resource(game.player, game.loot, C),
+        },
+        LootRuleData::NotFound(resource) => match by_name::<GameResource>(resource)? {
+            A => |game| !is_present_resource(game.player, game.loot, A),
+            B => |game| !is_present_resource(game.player, game.loot, B),
+            C => |game| !is_present_resource(game.player, game.loot, C),
+        },
+    };
+    Ok(rule)
+}
+
+fn build_location(rm: &ResourceManager, data: &LocationData) -> Result<Location, String> {
+    let mut progression = vec![];
+    for objectives in &data.objectives {
+        progression.extend(match objectives.as_str() {
+            "tutorial" => tutorial_objectives::objectives(),
+            "location003" => location003_objectives::objectives(),
+            _ => return Err(format!("unknown objectives: {}", objectives)),
+        });
+    }
+    Ok(Location {
+        sky: Sky { color_by_height: color_curve(&data.sky)? },
+        size: vec2(data.size),
+        start_pos_norm: vec2(data.start_pos_norm),
+        background_objects: data.background_objects.iter()
+            .map(|it| -> Result<_, String> {
+                Ok(BackgroundObject {
+                    size: curve(&it.size)?,
+                    height_normal: it.height_normal.as_ref().map(curve).transpose()?,
+                    sprite: it.sprites.iter().map(|id| get(rm, id)).collect::<Result<_, _>>()?,
+                    z: curve(&it.z)?,
+                    count: curve(&it.count)?,
+                    material: get_opt(rm, &it.material)?,
+                })
+            })
+            .collect::<Result<_, _>>()?,
+        bots: vec![],
+        content: LocationContent::Generator002(LocationGenerator002 {
+            layers: data.layers.iter()
+                .map(|it| -> Result<_, String> {
+                    Ok(Layer {
+                        name: intern(&it.name),
+                        mobs: it.mobs.iter()
+                            .map(|(id, count)| get(rm, id).map(|mob| MobConfig { mob, count: *count }))
+                            .collect::<Result<_, _>>()?,
+                        mob_per_isle: curve(&it.mob_per_isle)?,
+                        isles_per_archipelago: curve(&it.isles_per_archipelago)?,
+                        isles: it.isles.iter().map(|id| get(rm, id)).collect::<Result<_, _>>()?,
+                        height_normal: curve(&it.height_normal)?,
+                        x_normal: curve(&it.x_normal)?,
+                        rank_minimap_penalty: it.rank_minimap_penalty,
+                    })
+                })
+                .collect::<Result<_, _>>()?,
+        }),
+        lava_damage_by_height_per_sec_norm: if data.lava { Some(standard_lava_damage_per_sec_norm()) } else { None },
+        progression,
+        journal: vec![],
+        default_weapon: match &data.default_weapon {
+            None => None,
+            Some(weapon) => Some(PlaneWeapon {
+                spec: get(rm, &weapon.cannon)?,
+                ammo: build_ammo(&weapon.ammo),
+                order: DeviceOrder(weapon.order),
+            }),
+        },
+    })
+}
+
+fn build_ammo(data: &AmmoData) -> Ammo {
+    match data {
+        AmmoData::Infinite => Ammo::Infinite,
+        AmmoData::Finite(count) => Ammo::Finite(*count),
+        AmmoData::Energy(energy_per_shot) => Ammo::Energy { energy_per_shot: *energy_per_shot },
+        AmmoData::Heat { heat_per_shot, cooling_per_sec, lockout_sec } => Ammo::Heat {
+            heat_per_shot: *heat_per_shot,
+            cooling_per_sec: *cooling_per_sec,
+            lockout_sec: *lockout_sec,
+        },
+    }
+}
+
+fn build_shop(rm: &ResourceManager, data: &Vec<ShopLotData>) -> Result<Vec<Resource<ShopLot>>, String> {
+    data.iter()
+        .map(|lot| -> Result<_, String> {
+            let mut price: HashMap<GameResource, u32> = enum_iterator::all::<GameResource>().map(|it| (it, 0)).collect();
+            for (resource, count) in &lot.price {
+                price.insert(by_name(resource)?, *count);
+            }
+            let item = match &lot.item {
+                ObtainableData::Weapon { slot, cannon, ammo } => Obtainable::Weapon {
+                    slot: device_slot(slot),
+                    weapon: get(rm, cannon)?,
+                    ammo: build_ammo(ammo),
+                },
+                ObtainableData::HP { title, amount } => Obtainable::HP { title: intern(title), amount: *amount },
+                ObtainableData::Consumable { slot, buff, reserve_sec } => Obtainable::Consumable {
+                    slot: device_slot(slot),
+                    def: rm.named(intern(&buff.id), build_buff(buff)?),
+                    reserve_sec: *reserve_sec,
+                },
+                ObtainableData::Passive { buff } => Obtainable::Passive { def: rm.named(intern(&buff.id), build_buff(buff)?) },
+                ObtainableData::PassiveReset { title } => Obtainable::PassiveReset { title: intern(title) },
+            };
+            Ok(Resource::detached(ShopLot { price, item }))
+        })
+        .collect()
+}
+
+fn device_slot(data: &SlotData) -> DeviceSlot {
+    match data {
+        SlotData::Primary => DeviceSlot::Primary,
+        SlotData::Secondary => DeviceSlot::Secondary,
+    }
+}
+
+fn build_buff(data: &BuffData) -> Result<Buff, String> {
+    Ok(Buff { title: intern(&data.title), spec: build_buff_spec(&data.spec)? })
+}
+
+fn build_buff_spec(data: &BuffSpecData) -> Result<BuffSpec, String> {
+    Ok(match data {
+        BuffSpecData::ThrustAddendum { extra_acceleration } => BuffSpec::ThrustAddendum { extra_acceleration: *extra_acceleration },
+        BuffSpecData::Nitro { smoke_factor, top_speed, acceleration_by_speed } => BuffSpec::Nitro {
+            smoke_factor: *smoke_factor,
+            top_speed: *top_speed,
+            acceleration_by_speed: curve(acceleration_by_speed)?,
+        },
+        BuffSpecData::ThrustMultiplier { acceleration_multiplier, smoke_factor_rel } => BuffSpec::ThrustMultiplier {
+            acceleration_multiplier: *acceleration_multiplier,
+            smoke_factor_rel: *smoke_factor_rel,
+        },
+        BuffSpecData::SteerBooster { steering_by_speed, steering_by_attack, max_angular_acceleration, smoke_factor_abs } => BuffSpec::SteerBooster {
+            stabilization: SteerStabilization {
+                steering_by_speed: curve(steering_by_speed)?,
+                steering_by_attack: curve(steering_by_attack)?,
+                max_angular_acceleration: *max_angular_acceleration,
+            },
+            smoke_factor_abs: *smoke_factor_abs,
+        },
+        BuffSpecData::Repair { hp_per_sec } => BuffSpec::Repair { hp_per_sec: *hp_per_sec },
+    })
+}
+
+fn build_skill_tree(rm: &ResourceManager, data: &Vec<ImprovementData>) -> Result<Pool<ImprovementId, Improvement>, String> {
+    let improvements = data.iter()
+        .map(|it| build_improvement(rm, it))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Pool::from(improvements))
+}
+
+fn build_improvement(rm: &ResourceManager, data: &ImprovementData) -> Result<Improvement, String> {
+    let levels = data.levels.iter()
+        .map(|level| -> Result<_, String> {
+            Ok(ImprovementLevel {
+                spec: match &level.spec {
+                    ImprovementSpecData::Weapon { cannon, ammo, order } => ImprovementSpec::WeaponSkill(PlaneWeapon {
+                        spec: get(rm, cannon)?,
+                        ammo: build_ammo(ammo),
+                        order: DeviceOrder(*order),
+                    }),
+                    ImprovementSpecData::Buff { buff, energy_per_second, order } => ImprovementSpec::BuffSkill(PlaneBuff {
+                        spec: rm.named(intern(&buff.id), build_buff(buff)?),
+                        energy_per_second: *energy_per_second,
+                        order: DeviceOrder(*order),
+                    }),
+                    ImprovementSpecData::Passive { id, spec } => ImprovementSpec::Passive { spec: rm.named(intern(id), build_buff_spec(spec)?) },
+                    ImprovementSpecData::Power(extra_energy) => ImprovementSpec::Bonus { spec: BonusSpec::Power { extra_energy: *extra_energy } },
+                    ImprovementSpecData::Armor(extra_hp) => ImprovementSpec::Bonus { spec: BonusSpec::Armor { extra_hp: *extra_hp } },
+                    ImprovementSpecData::ThrustTechInc => ImprovementSpec::Bonus { spec: BonusSpec::ThrustTechInc },
+                },
+                points: level.points,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let title = match (&data.title, levels.first().map(|it| &it.spec)) {
+        (Some(title), _) => ImprovementTitle::Hard(intern(title)),
+        (None, Some(ImprovementSpec::WeaponSkill(weapon))) => ImprovementTitle::FromWeapon(weapon.spec.clone()),
+        (None, Some(ImprovementSpec::BuffSkill(buff))) => ImprovementTitle::FromBuff(buff.spec.clone()),
+        (None, _) => return Err("title is required unless the first level is a weapon or buff".to_owned()),
+    };
+    let mut description = data.description.iter().map(|it| intern(it));
+    Ok(Improvement {
+        title,
+        category: by_name::<ImprovementCategory>(&data.category)?,
+        levels,
+        description: [
+            description.next().unwrap_or(""),
+            description.next().unwrap_or(""),
+            description.next().unwrap_or(""),
+        ],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::resources::content::data::{AmmoData, ContentFile, CurveData, ImprovementSpecData, ObtainableData};
+    use crate::model::def::DamageKind;
+    use crate::resources::content::{intern, resistance};
+
+    #[test]
+    fn example_parses() {
+        let content: ContentFile = ron::from_str(include_str!("../../../content/examples/example.ron")).unwrap();
+        assert!(content.cannons.contains_key("cannon_gatling"));
+        assert!(matches!(content.projectiles["bullet_heavy"].damage, CurveData::Points(ref it) if it.len() == 2));
+        assert_eq!(content.shops["shop_001"].len(), 5);
+        assert!(matches!(content.shops["shop_001"][2].item, ObtainableData::Weapon { ammo: AmmoData::Heat { .. }, .. }));
+        assert!(matches!(content.skill_trees["skills_001"][1].levels[0].spec, ImprovementSpecData::Weapon { ammo: AmmoData::Heat { .. }, .. }));
+    }
+
+    #[test]
+    fn same_text_is_interned_once() {
+        let a = intern(&"cannon_gatling".to_owned());
+        let b = intern(&"cannon_gatling".to_owned());
+        assert_eq!(a.as_ptr(), b.as_ptr());
+    }
+
+    #[test]
+    fn resistance_above_one_is_rejected() {
+        assert_eq!(Ok((DamageKind::Kinetic, 1.0)), resistance("Kinetic", 1.0));
+        assert_eq!(Ok((DamageKind::Kinetic, -0.5)), resistance("Kinetic", -0.5));
+        assert!(resistance("Kinetic", 1.5).is_err());
+    }
+}
@@ -1,8 +1,8 @@
 use crate::common::curve::{Curve};
 use crate::common::resource::{ResourceGet, ResourceLoad, ResourceManagerRc};
 use crate::FutureExt;
-use crate::model::def::{Explosion, ExplosionFragment, ExplosionParticleEmission, ParticleEmission, ParticleEmitter, TransientBallisticBody};
-use crate::resources::constants::SCALE_SPEED;
+use crate::model::def::{DamageKind, Explosion, ExplosionFragment, ExplosionParticleEmission, FragmentDamage, ParticleEmission, ParticleEmitter, TransientBallisticBody};
+use crate::resources::constants::{DRAG, SCALE_SPEED};
 use crate::resources::fx::sprites::{smoke_clip_001, smoke_clip_001_dark, smoke_clip_plane_explosion};
 use crate::resources::sounds::sound_explosion_001;
 use crate::resources::sprites::explosion_facepalm33_clip::explosion_facepalm33_clip;
@@ -81,11 +81,57 @@ pub const plane_explosion_composite: ResourceLoad<Explosion> = |rm| Explosion {
                 },
                 spawn_rate: 0.0,
             },
+            damage: None,
         }
     ],
     sound: Some(sound_explosion_001.get(&rm)),
 };
 
+// burning wreckage of big mobs. the mob has its own death sound
+pub const wreckage_composite: ResourceLoad<Explosion> = |rm| Explosion {
+    particles: vec![
+        ExplosionParticleEmission {
+            emission: ParticleEmission {
+                clip_variants: vec![
+                    explosion_facepalm33_clip.get(&rm),
+                    explosion_facepalm34_clip.get(&rm),
+                ],
+                spread_distance: 0.0,
+                scale: Curve::new([0.8]),
+                rate: Curve::new([1.0]),
+                delay: Curve::new([0.0]),
+            },
+            count: Curve::new([1]),
+            off_center_speed: Curve::new([0.0]),
+            speed_factor: 0.5,
+        },
+    ],
+    fragments: (0..5).map(|_| ExplosionFragment {
+        body: TransientBallisticBody {
+            initial_speed: 220.0 * SCALE_SPEED,
+            sprite: None,
+            drag: DRAG,
+            seconds_to_live: Some(2.5),
+        },
+        trail: ParticleEmitter {
+            emission: ParticleEmission {
+                clip_variants: vec![smoke_clip_001_dark.get(&rm)],
+                spread_distance: 4.0 * SCALE_SPEED,
+                scale: Curve::new([0.4, 0.7]),
+                rate: 1.0.into(),
+                delay: 0.0.into(),
+            },
+            spawn_rate: 30.0,
+        },
+        damage: Some(FragmentDamage {
+            amount: 10.0,
+            kind: DamageKind::Fire,
+            radius: 8.0 * SCALE_SPEED,
+        }),
+    }).collect(),
+    sound: None,
+};
+
 pub const exhaust_trail_plane: ResourceLoad<ParticleEmitter> = |rm| ParticleEmitter {
     emission: ParticleEmission {
         clip_variants: vec![smoke_clip_001.get(&rm)],
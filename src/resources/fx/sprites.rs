@@ -1,3 +1,4 @@
+use crate::asset_bytes;
 use std::f32::consts::PI;
 use std::ops::Mul;
 use crate::common::curve::{Curve, Point};
@@ -12,6 +13,7 @@ use crate::model::def::SpriteClipMod::{ColorMult, Scale};
 
 pub const explosion3_clip: ResourceLoad<SpriteClip> = |rm| SpriteClip {
     frames: load_sprites_from_sheet(
+        &rm,
         todo!("../../art/sinestesia-2d-explosions-animations/explosion 3.png"),
         (8, 8),
         SheetRegion::All,
@@ -24,6 +26,7 @@ pub const explosion3_clip: ResourceLoad<SpriteClip> = |rm| SpriteClip {
 
 pub const explosion3a_clip: ResourceLoad<SpriteClip> = |rm| SpriteClip {
     frames: load_sprites_from_sheet(
+        &rm,
         todo!("../../art/sinestesia-free-2d-explosion-animations-2/3.png"),
         (8, 8),
         SheetRegion::All,
@@ -36,7 +39,8 @@ pub const explosion3a_clip: ResourceLoad<SpriteClip> = |rm| SpriteClip {
 
 pub const smoke_clip_001: ResourceLoad<SpriteClip> = |rm| SpriteClip {
     frames: load_sprites_from_sheet(
-        include_bytes!("../../../art/my/smoke_001.png"),
+        &rm,
+        asset_bytes!("../../../art/my/smoke_001.png"),
         (1, 1),
         SheetRegion::All,
         |it| {},
@@ -51,7 +55,8 @@ pub const smoke_clip_001: ResourceLoad<SpriteClip> = |rm| SpriteClip {
 
 pub const smoke_clip_001_dark: ResourceLoad<SpriteClip> = |rm| SpriteClip {
     frames: load_sprites_from_sheet(
-        include_bytes!("../../../art/my/smoke_001.png"),
+        &rm,
+        asset_bytes!("../../../art/my/smoke_001.png"),
         (1, 1),
         SheetRegion::All,
         |it| {},
@@ -71,7 +76,8 @@ pub const smoke_clip_001_dark: ResourceLoad<SpriteClip> = |rm| SpriteClip {
 
 pub const smoke_clip_plane_explosion: ResourceLoad<SpriteClip> = |rm| SpriteClip {
     frames: load_sprites_from_sheet(
-        include_bytes!("../../../art/my/smoke_001.png"),
+        &rm,
+        asset_bytes!("../../../art/my/smoke_001.png"),
         (1, 1),
         SheetRegion::All,
         |it| {},
@@ -90,6 +96,7 @@ pub const smoke_clip_plane_explosion: ResourceLoad<SpriteClip> = |rm| SpriteClip
 
 pub const cloud_swirling_clip: ResourceLoad<SpriteClip> = |rm| SpriteClip {
     frames: load_sprites_from_sheet(
+        &rm,
         todo!("../../art/smoke-aura/Smoke15Frames.png"),
         (4, 4),
         SheetRegion::Range((0, 2)..(3, 3)),
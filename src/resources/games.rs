@@ -4,11 +4,11 @@ use crate::{Game, ResourceGet};
 use crate::common::curve::Curve;
 use crate::common::enum_maps::new_enum_map;
 use crate::common::pool::Pool;
-use crate::common::resource::{Resource, ResourceLoad};
-use crate::model::def::{Cannon, GameResource, Obtainable, ShopLot, DeviceSlot, Buff, BuffSpec, SteerStabilization, Improvement, ImprovementLevel, ImprovementSpec, BonusSpec, ImprovementTitle, ImprovementCategory, PlaneWeapon, PlaneBuff};
+use crate::common::resource::{Resource, ResourceLoad, ResourceManager};
+use crate::model::def::{Cannon, GameResource, Obtainable, ShopLot, DeviceSlot, Buff, BuffSpec, SteerStabilization, Improvement, ImprovementLevel, ImprovementSpec, BonusSpec, ImprovementTitle, ImprovementCategory, PlaneWeapon, PlaneBuff, ImprovementId, MobStreaming};
 use crate::model::def::Obtainable::Weapon;
 use crate::model::state::{Ammo, DeviceOrder, LimitedCannon, WeaponSelector};
-use crate::model::state::Ammo::{Energy, Finite, Infinite};
+use crate::model::state::Ammo::{Energy, Finite, Heat, Infinite};
 use crate::resources::constants::{PLANE_THRUST_NOMINAL, SPEED_ABS_MAX};
 use crate::resources::materials::pain::pain_material;
 use crate::resources::objects::arms::{cannon_gatling, cannon_plasma, launcher_player, cannon_rail, launcher_jagger_homing, cannon_rail2, cannon_default, cannon_rail_player};
@@ -34,97 +34,106 @@ pub const game_001: ResourceLoad<Game> = |rm| Game {
         ],
         selected: 0,
     },
-    shop_assortment: vec![
-        shop_lot(price(0, 0, 0), weapon(DeviceSlot::Primary, Energy { energy_per_shot: 2.5 }, cannon_gatling.get(&rm))),
-        shop_lot(price(1, 0, 0), Obtainable::HP { title: "20HP", amount: 20.0 }),
-        shop_lot(price(0, 0, 0), Obtainable::HP { title: "Cheat: 120HP", amount: 120.0 }),
-        shop_lot(price(0, 0, 0), consumable("Jet Nitro", 30.0, booster_add_spec(12.0))),
-        shop_lot(price(0, 0, 0), consumable("Jet Steer", 30.0, steer_booster_spec())),
-        shop_lot(price(0, 0, 0), consumable("Jet Steer", 30.0, steer_booster_spec())),
-        shop_lot(price(0, 0, 0), passive("Boosters", Some(booster_mul_spec(5.0)))),
-        shop_lot(price(0, 0, 0), passive("None", None)),
-        shop_lot(price(0, 0, 0), weapon(DeviceSlot::Primary, Infinite, cannon_rail.get(&rm))),
-        shop_lot(price(0, 0, 0), weapon(DeviceSlot::Primary, Infinite, cannon_rail2.get(&rm))),
-        shop_lot(price(0, 0, 0), weapon(DeviceSlot::Primary, Energy { energy_per_shot: 1.5 }, cannon_default.get(&rm))),
-        shop_lot(price(0, 0, 0), weapon(DeviceSlot::Secondary, Finite(10), launcher_player.get(&rm))),
-        shop_lot(price(2, 1, 0), weapon(DeviceSlot::Secondary, Finite(50), cannon_plasma.get(&rm))),
-        shop_lot(price(3, 2, 1), weapon(DeviceSlot::Secondary, Finite(5), launcher_jagger_homing.get(&rm))),
-    ],
-    rpg: Pool::from([
-        Improvement {
-            title: ImprovementTitle::Hard("Extra Energy"),
-            category: ImprovementCategory::Passives,
-            levels: vec![
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Power { extra_energy: 25.0 } }, points: 1 },
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Power { extra_energy: 35.0 } }, points: 1 },
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Power { extra_energy: 40.0 } }, points: 1 },
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Power { extra_energy: 50.0 } }, points: 2 },
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Power { extra_energy: 50.0 } }, points: 3 },
-            ],
-            description: [
-                "Energy used for everything - for flying, for shooting, for buffing.",
-                "Make a pig of oneself with up to +100% Energy divided into 3 upgrades.",
-                "Increases energy regen proportionally.",
-            ],
-        },
-        Improvement {
-            title: ImprovementTitle::Hard("Extra Armor"),
-            category: ImprovementCategory::Passives,
-            levels: vec![
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Armor { extra_hp: 25.0 } }, points: 1 },
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Armor { extra_hp: 35.0 } }, points: 1 },
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Armor { extra_hp: 40.0 } }, points: 1 },
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Armor { extra_hp: 50.0 } }, points: 2 },
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Armor { extra_hp: 50.0 } }, points: 3 },
-            ],
-            description: ["Endure more damage with up to +100% HP divided into 3 upgrades.", "", ""],
-        },
-        Improvement {
-            title: ImprovementTitle::Hard("Extra Acceleration"),
-            category: ImprovementCategory::Passives,
-            levels: vec![
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::ThrustTechInc }, points: 1 },
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::ThrustTechInc }, points: 1 },
-                ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::ThrustTechInc }, points: 1 },
-            ],
-            description: [
-                "Fly faster if you like with additional acceleration stages.",
-                "With additional Energy, of course.", "",
-            ],
-        },
-        skill_weapon(cannon_gatling.get(&rm), 1, 2.5, 1, [
-            "Stuff enemies with a lead salad on 90000 rounds per minute.", "", ""
-        ]),
-        skill_weapon(launcher_player.get(&rm), 2, 10.0, 1, [
-            "Devastate groups of enemies with high-explosive missiles.", "", ""]),
-        skill_weapon(cannon_rail_player.get(&rm), 3, 8.0, 1, [
-            "Burn them all on a speed-of-light using continuous instantly reaching beam.", "", ""
-        ]),
-        skill_buff(Resource::detached(Buff { title: "In-Flight Repair", spec: BuffSpec::Repair { hp_per_sec: 40.0 } }), 4, 30.0, 1, [
-            "Restore HP using Energy", "", ""
-        ]),
-        skill_buff(Resource::detached(Buff { title: "Nitro-Jet", spec: BuffSpec::Nitro {
-            smoke_factor: 5.0,
-            top_speed: SPEED_ABS_MAX,
-            acceleration_by_speed: Curve::new([PLANE_THRUST_NOMINAL * 30.0, 0.0]),
-        } }), 5, 50.0, 1, [
-            "Easily get out of trouble with temporary huge acceleration boost.",
-            "Drains Energy in a seconds.", ""
-        ]),
-        skill_buff(Resource::detached(Buff { title: "Side Thrusters", spec: steer_booster_spec() }), 6, 10.0, 1, [
-            "Feel yourself mobile as UFO with jet side thrusters.",
-            "While it drains your energy, of course.", ""
-        ]),
-    ]),
+    shop_assortment: shop_001.get(&rm),
+    rpg: skills_001.get(&rm),
     sound_level_up: Some(sound_levelup.get(&rm)),
     sound_skill_up: Some(sound_skillup.get(&rm)),
+    mob_streaming: MobStreaming {
+        activation_radius: 2000.0,
+        deactivation_radius: 2600.0,
+    },
 };
 
-fn skill_weapon(spec: Resource<Cannon>, order: i32, energy_per_shot: f32, skill_points: u32, description: [&'static str; 3]) -> Improvement {
+pub const shop_001: ResourceLoad<Vec<Resource<ShopLot>>> = |rm| vec![
+    shop_lot(price(0, 0, 0), weapon(DeviceSlot::Primary, Heat { heat_per_shot: 0.04, cooling_per_sec: 0.25, lockout_sec: 2.0 }, cannon_gatling.get(&rm))),
+    shop_lot(price(1, 0, 0), Obtainable::HP { title: "20HP", amount: 20.0 }),
+    shop_lot(price(0, 0, 0), Obtainable::HP { title: "Cheat: 120HP", amount: 120.0 }),
+    shop_lot(price(0, 0, 0), consumable(&rm, "shop_001.jet_nitro", "Jet Nitro", 30.0, booster_add_spec(12.0))),
+    shop_lot(price(0, 0, 0), consumable(&rm, "shop_001.jet_steer", "Jet Steer", 30.0, steer_booster_spec())),
+    shop_lot(price(0, 0, 0), consumable(&rm, "shop_001.jet_steer_2", "Jet Steer", 30.0, steer_booster_spec())),
+    shop_lot(price(0, 0, 0), passive(&rm, "shop_001.boosters", "Boosters", Some(booster_mul_spec(5.0)))),
+    shop_lot(price(0, 0, 0), passive(&rm, "shop_001.none", "None", None)),
+    shop_lot(price(0, 0, 0), weapon(DeviceSlot::Primary, Infinite, cannon_rail.get(&rm))),
+    shop_lot(price(0, 0, 0), weapon(DeviceSlot::Primary, Infinite, cannon_rail2.get(&rm))),
+    shop_lot(price(0, 0, 0), weapon(DeviceSlot::Primary, Energy { energy_per_shot: 1.5 }, cannon_default.get(&rm))),
+    shop_lot(price(0, 0, 0), weapon(DeviceSlot::Secondary, Finite(10), launcher_player.get(&rm))),
+    shop_lot(price(2, 1, 0), weapon(DeviceSlot::Secondary, Finite(50), cannon_plasma.get(&rm))),
+    shop_lot(price(3, 2, 1), weapon(DeviceSlot::Secondary, Finite(5), launcher_jagger_homing.get(&rm))),
+];
+
+pub const skills_001: ResourceLoad<Pool<ImprovementId, Improvement>> = |rm| Pool::from([
+    Improvement {
+        title: ImprovementTitle::Hard("Extra Energy"),
+        category: ImprovementCategory::Passives,
+        levels: vec![
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Power { extra_energy: 25.0 } }, points: 1 },
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Power { extra_energy: 35.0 } }, points: 1 },
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Power { extra_energy: 40.0 } }, points: 1 },
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Power { extra_energy: 50.0 } }, points: 2 },
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Power { extra_energy: 50.0 } }, points: 3 },
+        ],
+        description: [
+            "Energy used for everything - for flying, for shooting, for buffing.",
+            "Make a pig of oneself with up to +100% Energy divided into 3 upgrades.",
+            "Increases energy regen proportionally.",
+        ],
+    },
+    Improvement {
+        title: ImprovementTitle::Hard("Extra Armor"),
+        category: ImprovementCategory::Passives,
+        levels: vec![
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Armor { extra_hp: 25.0 } }, points: 1 },
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Armor { extra_hp: 35.0 } }, points: 1 },
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Armor { extra_hp: 40.0 } }, points: 1 },
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Armor { extra_hp: 50.0 } }, points: 2 },
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::Armor { extra_hp: 50.0 } }, points: 3 },
+        ],
+        description: ["Endure more damage with up to +100% HP divided into 3 upgrades.", "", ""],
+    },
+    Improvement {
+        title: ImprovementTitle::Hard("Extra Acceleration"),
+        category: ImprovementCategory::Passives,
+        levels: vec![
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::ThrustTechInc }, points: 1 },
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::ThrustTechInc }, points: 1 },
+            ImprovementLevel { spec: ImprovementSpec::Bonus { spec: BonusSpec::ThrustTechInc }, points: 1 },
+        ],
+        description: [
+            "Fly faster if you like with additional acceleration stages.",
+            "With additional Energy, of course.", "",
+        ],
+    },
+    skill_weapon(cannon_gatling.get(&rm), 1, Heat { heat_per_shot: 0.04, cooling_per_sec: 0.25, lockout_sec: 2.0 }, 1, [
+        "Stuff enemies with a lead salad on 90000 rounds per minute.", "", ""
+    ]),
+    skill_weapon(launcher_player.get(&rm), 2, Energy { energy_per_shot: 10.0 }, 1, [
+        "Devastate groups of enemies with high-explosive missiles.",
+        "Keep the nose on a target to lock missiles on it.", ""]),
+    skill_weapon(cannon_rail_player.get(&rm), 3, Energy { energy_per_shot: 8.0 }, 1, [
+        "Burn them all on a speed-of-light using continuous instantly reaching beam.", "", ""
+    ]),
+    skill_buff(rm.named("skills_001.repair", Buff { title: "In-Flight Repair", spec: BuffSpec::Repair { hp_per_sec: 40.0 } }), 4, 30.0, 1, [
+        "Restore HP using Energy", "", ""
+    ]),
+    skill_buff(rm.named("skills_001.nitro", Buff { title: "Nitro-Jet", spec: BuffSpec::Nitro {
+        smoke_factor: 5.0,
+        top_speed: SPEED_ABS_MAX,
+        acceleration_by_speed: Curve::new([PLANE_THRUST_NOMINAL * 30.0, 0.0]),
+    } }), 5, 50.0, 1, [
+        "Easily get out of trouble with temporary huge acceleration boost.",
+        "Drains Energy in a seconds.", ""
+    ]),
+    skill_buff(rm.named("skills_001.side_thrusters", Buff { title: "Side Thrusters", spec: steer_booster_spec() }), 6, 10.0, 1, [
+        "Feel yourself mobile as UFO with jet side thrusters.",
+        "While it drains your energy, of course.", ""
+    ]),
+]);
+
+fn skill_weapon(spec: Resource<Cannon>, order: i32, ammo: Ammo, skill_points: u32, description: [&'static str; 3]) -> Improvement {
     Improvement {
         title: ImprovementTitle::FromWeapon(spec.clone()),
         category: ImprovementCategory::Weapons,
-        levels: vec![ImprovementLevel { spec: ImprovementSpec::WeaponSkill(PlaneWeapon { spec, energy_per_shot, order: DeviceOrder(order) }), points: skill_points }],
+        levels: vec![ImprovementLevel { spec: ImprovementSpec::WeaponSkill(PlaneWeapon { spec, ammo, order: DeviceOrder(order) }), points: skill_points }],
         description,
     }
 }
@@ -146,10 +155,10 @@ fn weapon(slot: DeviceSlot, ammo: Ammo, weapon: Resource<Cannon>) -> Obtainable
     Obtainable::Weapon { slot, weapon, ammo }
 }
 
-fn passive(title: &'static str, spec: Option<BuffSpec>) -> Obtainable {
+fn passive(rm: &ResourceManager, id: &'static str, title: &'static str, spec: Option<BuffSpec>) -> Obtainable {
     match spec {
         Some(spec) => Obtainable::Passive {
-            def: Resource::detached(Buff {
+            def: rm.named(id, Buff {
                 title,
                 spec,
             })
@@ -158,10 +167,10 @@ fn passive(title: &'static str, spec: Option<BuffSpec>) -> Obtainable {
     }
 }
 
-fn consumable(title: &'static str, reserve_sec: f32, spec: BuffSpec) -> Obtainable {
+fn consumable(rm: &ResourceManager, id: &'static str, title: &'static str, reserve_sec: f32, spec: BuffSpec) -> Obtainable {
     Obtainable::Consumable {
         slot: DeviceSlot::Secondary,
-        def: Resource::detached(Buff {
+        def: rm.named(id, Buff {
             title,
             spec,
         }),
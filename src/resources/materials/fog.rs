@@ -1,6 +1,7 @@
 #![allow(non_upper_case_globals)]
 
 use futures::FutureExt;
+use crate::asset_str;
 use macroquad::miniquad::{BlendState, Equation};
 use macroquad::prelude::{load_material, Material, MaterialParams, PipelineParams, UniformType};
 use macroquad::prelude::Vec4Swizzles;
@@ -9,8 +10,8 @@ use crate::common::unsorted::{ToColor, ColorOps};
 use crate::miniquad::{BlendFactor, BlendValue};
 use crate::model::def::{MaterialInstance, UniformSupplier};
 
-const VERTEX: &str = include_str!("default.vert");
-const FRAGMENT: &str = include_str!("fog.frag");
+fn vertex() -> &'static str { asset_str!("default.vert") }
+fn fragment() -> &'static str { asset_str!("fog.frag") }
 
 pub const U_FOG_COLOR: &str = "FogColor";
 
@@ -23,7 +24,7 @@ pub const fog_shader: ResourceLoad<Material> = |rm| {
         )),
         ..Default::default()
     };
-    load_material(VERTEX, FRAGMENT, MaterialParams {
+    load_material(vertex(), fragment(), MaterialParams {
         pipeline_params: params,
         uniforms: vec![
             (U_FOG_COLOR.to_owned(), UniformType::Float4)
@@ -1,6 +1,7 @@
 #![allow(non_upper_case_globals)]
 
 use futures::FutureExt;
+use crate::asset_str;
 use macroquad::miniquad::{BlendState, Equation};
 use macroquad::prelude::{Color, load_material, Material, MaterialParams, PipelineParams, UniformType};
 use macroquad::prelude::Vec4Swizzles;
@@ -10,8 +11,8 @@ use crate::miniquad::{BlendFactor, BlendValue};
 use crate::model::def::{MaterialInstance, UniformSupplier};
 use crate::ResourceManager;
 
-const VERTEX: &str = include_str!("default.vert");
-const FRAGMENT: &str = include_str!("huer.frag");
+fn vertex() -> &'static str { asset_str!("default.vert") }
+fn fragment() -> &'static str { asset_str!("huer.frag") }
 
 pub const U_SOURCE_COLOR: &str = "SourceColor";
 pub const U_TARGET_COLOR: &str = "TargetColor";
@@ -25,7 +26,7 @@ pub const huer_shader: ResourceLoad<Material> = |rm| {
         )),
         ..Default::default()
     };
-    load_material(VERTEX, FRAGMENT, MaterialParams {
+    load_material(vertex(), fragment(), MaterialParams {
         pipeline_params: params,
         uniforms: vec![
             (U_SOURCE_COLOR.to_owned(), UniformType::Float4),
@@ -7,4 +7,6 @@ pub mod fx;
 pub mod materials;
 pub mod sounds;
 pub mod games;
+pub mod registry;
+pub mod content;
 
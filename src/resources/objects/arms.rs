@@ -2,7 +2,7 @@ use std::f32::consts::PI;
 use macroquad::color::{RED, WHITE, YELLOW};
 use crate::common::curve::{Curve};
 use crate::common::resource::{Resource, ResourceLoad, ResourceLoadAsync};
-use crate::model::def::{Cannon, CannonBarrel, ExplosionSource, HitScan, HitScanAction, HitScanLook, HitScanRay, Projectile, ProjectileMod, ProjectilePulsation, ProjectileRot, SlideStabilization, SplashDamage, Sprite, Stabilization, SteerStabilization, TrailSource, TransientBallisticBody};
+use crate::model::def::{Cannon, CannonBarrel, DamageKind, ExplosionSource, HitScan, HitScanAction, HitScanLook, HitScanRay, Projectile, ProjectileMod, ProjectilePulsation, ProjectileRot, SlideStabilization, SplashDamage, Sprite, Stabilization, SteerStabilization, TrailSource, TransientBallisticBody};
 use crate::{ResourceGet, ResourceManager, Vec2};
 use crate::common::curve::Point::{Transition, Value};
 use crate::resources::constants::{DRAG, MISSILE_THRUST, SCALE_SPEED, SLIDE};
@@ -52,6 +52,7 @@ pub const cannon_plasma: ResourceLoad<Cannon> = |rm| Cannon {
         &rm,
         600.0,
         Curve::new([10.0, 30.0]),
+        DamageKind::Fire,
         ProjectileRot::Spinning { degrees_per_second: 12345.0 },
         Some(ProjectilePulsation { scale: Curve::new([0.5, 1.3]) }),
         1.5,
@@ -70,6 +71,7 @@ pub const cannon_rail: ResourceLoad<Cannon> = |rm| Cannon {
     barrel: CannonBarrel::HitScan(Resource::detached( HitScan {
         action: HitScanAction {
             damage: Curve::new([40.0, 80.0]),
+            damage_kind: DamageKind::Energy,
             range: 1600.0,
             collider_thickness: 0.0
         },
@@ -93,6 +95,7 @@ pub const cannon_rail2: ResourceLoad<Cannon> = |rm| {
         barrel: CannonBarrel::HitScan(Resource::detached(HitScan {
             action: HitScanAction {
                 damage: Curve::new([10.0, 15.0]),
+                damage_kind: DamageKind::Energy,
                 range: 1600.0,
                 collider_thickness: 0.0
             },
@@ -117,6 +120,7 @@ pub const cannon_rail_player: ResourceLoad<Cannon> = |rm| {
         barrel: CannonBarrel::HitScan(Resource::detached(HitScan {
             action: HitScanAction {
                 damage: Curve::new([10.0, 15.0]),
+                damage_kind: DamageKind::Energy,
                 range: 1600.0,
                 collider_thickness: 0.0
             },
@@ -146,10 +150,15 @@ pub const launcher_player: ResourceLoad<Cannon> = |rm| Cannon {
         collision_radius: 32.0 * SCALE_SPEED,
         splash_damage: Some(SplashDamage {
             damage: Curve::new([30.0, 70.0]),
+            damage_kind: DamageKind::Explosive,
             radius: 200.0,
             damage_factor_by_distance_norm: Curve::new([1.0, 0.0]),
+            // bots share the player faction, yet player rockets must still hurt them
+            friendly_fire: true,
+            self_damage: false,
         }),
         damage: Curve::new([0.0]),
+        damage_kind: DamageKind::Explosive,
         explosion: Some(ExplosionSource {
             offset: Vec2::new(60.0, 0.0),
             explosion: missile_explosion_simple.get(&rm),
@@ -164,7 +173,7 @@ pub const launcher_player: ResourceLoad<Cannon> = |rm| Cannon {
         hit_sound: None,
         rotation: ProjectileRot::InitialVelocity,
         pulsation: None,
-        mods: vec![]
+        mods: vec![ProjectileMod::Homing]
     })),
     spread_degrees: Curve::new([
         0.0,
@@ -203,10 +212,10 @@ pub const launcher_jagger_homing_fast: ResourceLoad<Cannon> = |rm| Cannon {
 };
 
 fn bullet(rm: &ResourceManager, initial_speed: f32, damage: Curve<f32>, sprite: ResourceLoadAsync<Sprite>) -> Resource<Projectile> {
-    bullet_ext(rm, initial_speed, damage, ProjectileRot::InitialVelocity, None, 1.0, sprite)
+    bullet_ext(rm, initial_speed, damage, DamageKind::Kinetic, ProjectileRot::InitialVelocity, None, 1.0, sprite)
 }
 
-fn bullet_ext(rm: &ResourceManager, initial_speed: f32, damage: Curve<f32>, rotation: ProjectileRot, pulsation: Option<ProjectilePulsation>, seconds_to_live: f32, sprite: ResourceLoadAsync<Sprite>) -> Resource<Projectile> {
+fn bullet_ext(rm: &ResourceManager, initial_speed: f32, damage: Curve<f32>, damage_kind: DamageKind, rotation: ProjectileRot, pulsation: Option<ProjectilePulsation>, seconds_to_live: f32, sprite: ResourceLoadAsync<Sprite>) -> Resource<Projectile> {
     Resource::detached(Projectile {
         body: TransientBallisticBody {
             initial_speed,
@@ -217,6 +226,7 @@ fn bullet_ext(rm: &ResourceManager, initial_speed: f32, damage: Curve<f32>, rota
         collision_radius: 8.0 * SCALE_SPEED,
         splash_damage: None,
         damage,
+        damage_kind,
         explosion: None,
         acceleration: None,
         stabilization: None,
@@ -239,8 +249,16 @@ fn missile_mob(rm: &ResourceManager, initial_speed: f32, collision_radius: f32,
                 seconds_to_live: Some(2.0),
             },
             collision_radius: collision_radius * SCALE_SPEED,
-            splash_damage: None,
+            splash_damage: Some(SplashDamage {
+                damage: Curve::new([10.0, 30.0]),
+                damage_kind: DamageKind::Explosive,
+                radius: 150.0,
+                damage_factor_by_distance_norm: Curve::new([1.0, 0.0]),
+                friendly_fire: false,
+                self_damage: false,
+            }),
             damage,
+            damage_kind: DamageKind::Explosive,
             explosion: Some(ExplosionSource {
                 offset: Vec2::new(60.0, 0.0),
                 explosion: missile_explosion_simple.get(&rm),
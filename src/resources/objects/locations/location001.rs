@@ -7,7 +7,7 @@ use crate::common::unsorted::ToColor;
 use crate::model::def::{BackgroundObject, Bot, Isle, IsleSpawn, Location, LocationContent, Mob, MobSpawn, Sky, Sprite};
 use crate::resources::constants::{SCALE_SPEED, standard_lava_damage_per_sec_norm};
 use crate::resources::materials::fog::fog_material;
-use crate::resources::objects::objects::plane001;
+use crate::resources::objects::objects::plane_bot001;
 use crate::{FutureExt, ResourceManager, Vec2};
 use crate::common::resource::Resource;
 use crate::game::generator_001::{ArchipelagoSpawn, LocationGenerator001};
@@ -20,7 +20,7 @@ use crate::resources::sprites::isles::isle1_sprite;
 use crate::resources::sprites::robot_001_clip_set::robot_001_clip_set;
 
 pub const location001: ResourceLoad<Location> = |rm| {
-    let bot_pane = plane001.get(&rm);
+    let bot_pane = plane_bot001.get(&rm);
     let bots = (0..locations::dev_scale(500))
         .map(|_| {
             Bot {
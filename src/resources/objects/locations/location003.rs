@@ -7,7 +7,7 @@ use crate::common::resource::ResourceLoad;
 use crate::common::unsorted::ToColor;
 use crate::game::generator_002::{Layer, LocationGenerator002, MobConfig};
 use crate::model::def::{BackgroundObject, GameResource, JournalEntry, Location, LocationContent, PlaneWeapon, ProgressFlag, ProgressPredicate, ProgressRule, Sky};
-use crate::model::state::DeviceOrder;
+use crate::model::state::{Ammo, DeviceOrder};
 use crate::ResourceGet;
 use crate::resources::constants::{SCALE_SPEED, standard_lava_damage_per_sec_norm};
 use crate::resources::materials::fog::fog_material;
@@ -219,6 +219,6 @@ pub const location003: ResourceLoad<Location> = |rm| {
             location003_objectives::objectives(),
         ].into_iter().flatten().to_vec(),
         journal: vec![],
-        default_weapon: Some(PlaneWeapon { spec: cannon_default.get(&rm), energy_per_shot: 1.5, order: DeviceOrder(-1) }),
+        default_weapon: Some(PlaneWeapon { spec: cannon_default.get(&rm), ammo: Ammo::Energy { energy_per_shot: 1.5 }, order: DeviceOrder(-1) }),
     }
 };
\ No newline at end of file
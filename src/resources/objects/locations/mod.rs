@@ -2,8 +2,8 @@ pub mod location001;
 pub mod location002;
 pub mod location003;
 pub mod location003_training;
-mod location003_objectives;
-mod tutorial_objectives;
+pub(crate) mod location003_objectives;
+pub(crate) mod tutorial_objectives;
 
 pub(crate) const DEV_SCALE: f32 = 1.0;
 
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use macroquad::prelude::{Vec2, YELLOW};
 use rust_macroquad_ui::common::to_vec::ToVec;
 
@@ -7,10 +8,11 @@ use crate::common::pool::Pool;
 use crate::common::resource::ResourceLoad;
 use crate::common::resource::Resource;
 use crate::common::unsorted::ToColor;
-use crate::model::def::{Cannon, CannonPodProps, CollisionCircle, Loot, ProgressPredicate, ProgressPredicateFn, Mob, MobAttack, MobCharge, MobKind, MobLootChance, MobRank, ProgressFlag, GameResource, Burst, Item};
+use crate::model::def::{Cannon, CannonPodProps, CollisionCircle, DamageKind, Explosion, Loot, ProgressPredicate, ProgressPredicateFn, Mob, MobAttack, MobCharge, MobKind, MobLootChance, MobRank, ProgressFlag, GameResource, Burst, Item};
 use crate::model::def::GameResource::{A, B, C};
 use crate::model::def::MobAttackPattern::{Distant, Melee};
 use crate::model::state::{LootId, LootState};
+use crate::resources::fx::settings::wreckage_composite;
 use crate::resources::materials::huer::create_huer_material;
 use crate::resources::objects::arms::{cannon_gatling_robot, launcher_jagger_homing, launcher_jagger_homing_fast};
 use crate::resources::objects::attacks::{attack_plasma, attack_railgun, attack_railgun2};
@@ -22,7 +24,7 @@ use crate::resources::sprites::wasp_001_clip_set::wasp_001_clip_set;
 
 const RANK_STYLE: [(Option<&'static str>, MobRank); 3] = [(None, (1)), (Some("#07F"), (2)), (Some("#F77"), (3))];
 
-fn is_present_resource(player: &PlayerState, loot: &Pool<LootId, LootState>, res: GameResource) -> bool {
+pub(crate) fn is_present_resource(player: &PlayerState, loot: &Pool<LootId, LootState>, res: GameResource) -> bool {
     player.resources.get(&res).copied().unwrap_or_default() > 0
         ||
         loot.iter()
@@ -89,6 +91,8 @@ fn robot(rm: &ResourceManager, rank: (Option<&'static str>, MobRank), scale: f32
         idle_seconds: Curve::new([0.1, 1.0]),
         collider_unscaled: CollisionCircle { center, radius: 100.0 },
         hp,
+        // armored: rails go through, bullets don't
+        resistances: HashMap::from([(DamageKind::Kinetic, 0.5), (DamageKind::Energy, -0.5)]),
         pod: CannonPodProps { offset: center },
         attacks: vec![
             Resource::detached(MobAttack {
@@ -98,6 +102,7 @@ fn robot(rm: &ResourceManager, rank: (Option<&'static str>, MobRank), scale: f32
                 pattern: Melee {
                     connect_range: 150.0,
                     damage: Curve::new([80.0, 120.0]),
+                    damage_kind: DamageKind::Kinetic,
                 },
                 charge: None,
                 burst: None,
@@ -141,11 +146,17 @@ fn robot(rm: &ResourceManager, rank: (Option<&'static str>, MobRank), scale: f32
         xp_reward,
         death_sound: Some(sound_death_robot.get(&rm)),
         pain_sound: Some(sound_hit_001.get(&rm)),
+        explosion: wreckage(&rm, rank.1),
         burst: Some(burst),
         attack_chance: 1.0,
     }
 }
 
+// only big ones fall apart into burning pieces
+fn wreckage(rm: &ResourceManager, rank: MobRank) -> Option<Resource<Explosion>> {
+    (rank > 1).then(|| wreckage_composite.get(rm))
+}
+
 fn convert_loot(loot: Vec<(ProgressPredicateFn, f32, Resource<Loot>)>) -> Vec<MobLootChance> {
     loot.iter()
         .cloned()
@@ -169,6 +180,8 @@ fn drone(rm: &ResourceManager, rank: (Option<&'static str>, MobRank), scale: f32
         idle_seconds: Curve::new([0.6, 1.6]),
         collider_unscaled: CollisionCircle { center, radius: 40.0 },
         hp,
+        // shielded electronics, but plasma melts them
+        resistances: HashMap::from([(DamageKind::Energy, 0.5), (DamageKind::Fire, -0.5)]),
         pod: CannonPodProps {
             offset: (center + Vec2::new(12.0, -17.0)) * scale
         },
@@ -183,6 +196,7 @@ fn drone(rm: &ResourceManager, rank: (Option<&'static str>, MobRank), scale: f32
         xp_reward,
         death_sound: Some(sound_death_drone.get(&rm)),
         pain_sound: Some(sound_hit_001.get(&rm)),
+        explosion: wreckage(&rm, rank.1),
         burst: None,
         attack_chance: 1.0,
     }
@@ -199,6 +213,8 @@ fn wasp_(rm: &ResourceManager, rank: (Option<&'static str>, MobRank), scale: f32
         idle_seconds: Curve::new([0.1, 0.3]),
         collider_unscaled: CollisionCircle { center, radius: 40.0 },
         hp,
+        // fire-proof chitin, soft against bullets
+        resistances: HashMap::from([(DamageKind::Fire, 0.5), (DamageKind::Kinetic, -0.5)]),
         pod: CannonPodProps { offset: center },
         attacks: vec![
             Resource::detached(MobAttack {
@@ -208,6 +224,7 @@ fn wasp_(rm: &ResourceManager, rank: (Option<&'static str>, MobRank), scale: f32
                 pattern: Melee {
                     connect_range: 50.0,
                     damage: Curve::new([0.5 * damage, damage]),
+                    damage_kind: DamageKind::Kinetic,
                 },
                 charge: Some(MobCharge {
                     velocity: 1000.0 * move_speed,
@@ -226,6 +243,7 @@ fn wasp_(rm: &ResourceManager, rank: (Option<&'static str>, MobRank), scale: f32
         xp_reward,
         death_sound: Some(sound_death_wasp.get(&rm)),
         pain_sound: Some(sound_hit_001.get(&rm)),
+        explosion: wreckage(&rm, rank.1),
         burst: None,
         attack_chance: 0.5,
     }
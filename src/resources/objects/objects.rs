@@ -13,7 +13,7 @@ use macroquad::color::{BLUE, RED, WHITE};
 use macroquad::prelude::{BLACK, GRAY};
 use rust_macroquad_ui::common::to_vec::ToVec;
 
-use crate::model::def::{BackgroundObject, Bot, Cannon, CannonPod, CannonPodProps, Explosion, ExplosionFragment, ExplosionParticleEmission, ExplosionSource, GameSound, Gear, HitScan, HitScanAction, HitScanLook, HitScanRay, Location, MaterialInstance, ParticleEmission, ParticleEmitter, Plane, PlaneArms, Projectile, ProjectilePulsation, ProjectileRot, Sky, SlideStabilization, SplashDamage, SpriteClip, Stabilization, SteerStabilization, TrailSource, UniformSupplier};
+use crate::model::def::{Aerodynamics, ArcadeFlight, BackgroundObject, Bot, Cannon, CannonPod, CannonPodProps, Explosion, ExplosionFragment, ExplosionParticleEmission, ExplosionSource, FlightAssist, FlightModel, GameSound, Gear, HitScan, HitScanAction, HitScanLook, HitScanRay, Location, MaterialInstance, ParticleEmission, ParticleEmitter, Plane, PlaneArms, Projectile, ProjectilePulsation, ProjectileRot, Sky, SlideStabilization, SplashDamage, SpriteClip, Stabilization, SteerStabilization, TrailSource, UniformSupplier};
 use crate::common::{sprite, unsorted};
 use crate::common::angle::{Angle, AsRadians};
 use crate::common::resource::{Resource, ResourceLoadAsync};
@@ -26,7 +26,7 @@ use crate::model::def::CannonBarrel;
 use crate::model::def::Sprite;
 use crate::model::def::TransientBallisticBody;
 use crate::{ResourceGet, ResourceManager};
-use crate::resources::constants::{DRAG, INITIAL_ENERGY, INITIAL_ENERGY_RESTORE_PER_SEC, INITIAL_HP, MISSILE_THRUST, PLANE_THRUST_NOMINAL, SCALE_SPEED, SECONDS_TO_RESTORE_FULL_ENERGY, SLIDE};
+use crate::resources::constants::{DRAG, GRAVITY, INITIAL_ENERGY, INITIAL_ENERGY_RESTORE_PER_SEC, INITIAL_HP, MISSILE_THRUST, NOMINAL_SPEED, PLANE_THRUST_NOMINAL, SCALE_SPEED, SECONDS_TO_RESTORE_FULL_ENERGY, SLIDE, SPEED_ABS_MAX};
 use crate::resources::fx::settings::{exhaust_trail_missile, exhaust_trail_plane, missile_explosion_simple, plane_explosion_composite};
 use crate::resources::materials::fog::fog_material;
 use crate::resources::fx::sprites::explosion3_clip;
@@ -36,14 +36,19 @@ use crate::resources::sprites::explosion_facepalm33_clip::explosion_facepalm33_c
 use crate::resources::sprites::fire_facepalm11_clip::fire_facepalm11_clip;
 use crate::resources::sprites::sprites::{bullet_sprite, missile_sprite, missile_sprite_blue, missile_sprite_yellow, plane_sprite, sprite_plasma_001};
 
-pub const standard_stabilization: ResourceLoad<Stabilization> = |rm| {
+pub const standard_stabilization: ResourceLoad<Stabilization> = |rm| create_stabilization(PI * 2.0);
+
+// turns twice slower, so bots can be out-manoeuvred
+pub const bot_stabilization: ResourceLoad<Stabilization> = |rm| create_stabilization(PI);
+
+fn create_stabilization(max_angular_acceleration: f32) -> Stabilization {
     Stabilization {
         slide: SlideStabilization {
             slide_by_speed: Curve::from_function(|it| SLIDE * it.powi(2)),
             slide_by_attack: Curve::from_function(|it| (it * PI).cos().abs()),
         },
         steer: SteerStabilization {
-            max_angular_acceleration: PI * 2.0,
+            max_angular_acceleration,
             steering_by_speed: Curve::new_ext(&[
                 Value(0.0),
                 // Value(0.8),
@@ -57,37 +62,93 @@ pub const standard_stabilization: ResourceLoad<Stabilization> = |rm| {
             ]),
         },
     }
-};
+}
+
+pub const plane001: ResourceLoad<Plane> = |rm| create_plane(
+    &rm,
+    ["plane001.primary", "plane001.secondary"],
+    standard_stabilization.get(&rm),
+    Aerodynamics {
+        drag: DRAG,
+        lift: GRAVITY * 0.5,
+        lift_by_attack: Curve::new_ext(&[
+            Value(0.0),
+            Transition(2),
+            Value(1.0),
+            Value(0.7),
+        ]),
+        stall_angle: PI / 12.0,
+        max_speed: SPEED_ABS_MAX,
+    },
+);
 
-pub const plane001: ResourceLoad<Plane> = |rm| Plane {
-    sprite: plane_sprite.get(&rm),
-    hp: INITIAL_HP,
-    gears: vec![
-        Gear { thrust: 0.0, energy_per_sec: 0.0, tech_level: 0 },
-        Gear { thrust: PLANE_THRUST_NOMINAL * 0.5, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 0.25, tech_level: 0 },
-        Gear { thrust: PLANE_THRUST_NOMINAL * 1.0, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 0.50, tech_level: 0 },
-        Gear { thrust: PLANE_THRUST_NOMINAL * 1.5, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 1.00, tech_level: 0 },
-        Gear { thrust: PLANE_THRUST_NOMINAL * 2.0, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 2.00, tech_level: 0 },
-        Gear { thrust: PLANE_THRUST_NOMINAL * 3.0, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 3.00, tech_level: 1 },
-        Gear { thrust: PLANE_THRUST_NOMINAL * 4.5, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 4.50, tech_level: 2 },
-        Gear { thrust: PLANE_THRUST_NOMINAL * 5.5, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 5.50, tech_level: 3 },
-    ],
-    default_gear: 2,
-    trail: Some(Resource::detached(TrailSource {
-        emitter: exhaust_trail_plane.get(&rm),
-        offset: Vec2::new(-40.0, 0.0),
-    })),
-    stabilization: standard_stabilization.get(&rm),
-    explosion: Some(plane_explosion_composite.get(&rm)),
-    arms: PlaneArms {
-        primary_default: (cannon_default.get(&rm)),
-        primary: Resource::detached(CannonPodProps { offset: Vec2::new(25.0, 9.0) * SCALE_SPEED }),
-        secondary: Resource::detached(CannonPodProps { offset: Vec2::new(25.0, 9.0) * SCALE_SPEED }),
+// draggier, stalls earlier and turns slower than the player's one
+pub const plane_bot001: ResourceLoad<Plane> = |rm| create_plane(
+    &rm,
+    ["plane_bot001.primary", "plane_bot001.secondary"],
+    bot_stabilization.get(&rm),
+    Aerodynamics {
+        drag: DRAG * 1.5,
+        lift: GRAVITY * 0.35,
+        lift_by_attack: Curve::new_ext(&[
+            Value(0.0),
+            Transition(2),
+            Value(1.0),
+            Value(0.5),
+        ]),
+        stall_angle: PI / 18.0,
+        max_speed: NOMINAL_SPEED * 1.5,
     },
-    collision_radius: 25.0 * SCALE_SPEED,
-    engine_sound: engine_001_sound.iter()
-        .map(|it| it.as_ref().map(|it| it.get(&rm)))
-        .to_vec(),
-    death_sound: Some(sound_death_001.get(&rm)),
-    pain_sound: Some(sound_pain.get(&rm)),
-};
+);
+
+fn create_plane(rm: &ResourceManager, pods: [&'static str; 2], stabilization: Resource<Stabilization>, aerodynamics: Aerodynamics) -> Plane {
+    Plane {
+        sprite: plane_sprite.get(&rm),
+        hp: INITIAL_HP,
+        resistances: HashMap::new(),
+        gears: vec![
+            Gear { thrust: 0.0, energy_per_sec: 0.0, tech_level: 0 },
+            Gear { thrust: PLANE_THRUST_NOMINAL * 0.5, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 0.25, tech_level: 0 },
+            Gear { thrust: PLANE_THRUST_NOMINAL * 1.0, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 0.50, tech_level: 0 },
+            Gear { thrust: PLANE_THRUST_NOMINAL * 1.5, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 1.00, tech_level: 0 },
+            Gear { thrust: PLANE_THRUST_NOMINAL * 2.0, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 2.00, tech_level: 0 },
+            Gear { thrust: PLANE_THRUST_NOMINAL * 3.0, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 3.00, tech_level: 1 },
+            Gear { thrust: PLANE_THRUST_NOMINAL * 4.5, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 4.50, tech_level: 2 },
+            Gear { thrust: PLANE_THRUST_NOMINAL * 5.5, energy_per_sec: INITIAL_ENERGY_RESTORE_PER_SEC * 5.50, tech_level: 3 },
+        ],
+        default_gear: 2,
+        trail: Some(Resource::detached(TrailSource {
+            emitter: exhaust_trail_plane.get(&rm),
+            offset: Vec2::new(-40.0, 0.0),
+        })),
+        stabilization,
+        aerodynamics,
+        flight_model: FlightModel::Simulation,
+        arcade: ArcadeFlight {
+            inertia_sec: 0.3,
+            gravity_by_thrust: Curve::new_ext(&[
+                Value(1.0),
+                Value(0.0),
+            ]),
+        },
+        assist: FlightAssist {
+            kp: 1.0,
+            kd: 0.3,
+            stall_speed: NOMINAL_SPEED * 0.3,
+            recovery_speed: NOMINAL_SPEED * 0.6,
+            recovery_dive: Angle::degrees(45.0),
+        },
+        explosion: Some(plane_explosion_composite.get(&rm)),
+        arms: PlaneArms {
+            primary_default: (cannon_default.get(&rm)),
+            primary: rm.named(pods[0], CannonPodProps { offset: Vec2::new(25.0, 9.0) * SCALE_SPEED }),
+            secondary: rm.named(pods[1], CannonPodProps { offset: Vec2::new(25.0, 9.0) * SCALE_SPEED }),
+        },
+        collision_radius: 25.0 * SCALE_SPEED,
+        engine_sound: engine_001_sound.iter()
+            .map(|it| it.as_ref().map(|it| it.get(&rm)))
+            .to_vec(),
+        death_sound: Some(sound_death_001.get(&rm)),
+        pain_sound: Some(sound_pain.get(&rm)),
+    }
+}
@@ -0,0 +1,69 @@
+use crate::common::resource::ResourceManager;
+use crate::register_resources;
+use crate::resources::fx::settings::*;
+use crate::resources::fx::sprites::*;
+use crate::resources::games::*;
+use crate::resources::materials::fog::*;
+use crate::resources::materials::huer::*;
+use crate::resources::materials::pain::*;
+use crate::resources::objects::arms::*;
+use crate::resources::objects::attacks::*;
+use crate::resources::objects::isles::*;
+use crate::resources::objects::locations::location001::*;
+use crate::resources::objects::locations::location002::*;
+use crate::resources::objects::locations::location003::*;
+use crate::resources::objects::locations::location003_training::*;
+use crate::resources::objects::loot::*;
+use crate::resources::objects::mobs::*;
+use crate::resources::objects::objects::*;
+use crate::resources::sounds::*;
+use crate::resources::sprites::clouds::*;
+use crate::resources::sprites::explosion_facepalm33_clip::*;
+use crate::resources::sprites::explosion_facepalm34_clip::*;
+use crate::resources::sprites::fire_facepalm11_clip::*;
+use crate::resources::sprites::isles::*;
+use crate::resources::sprites::loot::*;
+use crate::resources::sprites::mob_drone_001_clip_set::*;
+use crate::resources::sprites::robot_001_clip_set::*;
+use crate::resources::sprites::sprites::*;
+use crate::resources::sprites::wasp_001_clip_set::*;
+
+// id of a definition is the name of its constant. renaming the constant breaks everything
+// that refers it by id (saves, data files), so rename with care.
+pub fn register_all(rm: &ResourceManager) {
+    register_resources!(rm,
+        fog_shader, fog_material, huer_shader, pain_material,
+    );
+    register_resources!(rm,
+        attack_railgun, attack_railgun2, attack_plasma,
+        standard_stabilization, bot_stabilization, plane001, plane_bot001,
+        cannon_default, cannon_gatling, cannon_gatling_robot, cannon_plasma, cannon_rail, cannon_rail2, cannon_rail_player,
+        launcher_player, launcher_jagger, launcher_jagger_homing, launcher_jagger_homing_fast,
+        isle_001, isle_slow,
+        loot_A, loot_B, loot_C,
+        mob_drone__001, mob_drone__002, mob_drone__003,
+        mob_wasp___001, mob_wasp___002, mob_wasp___003,
+        mob_jagger_001, mob_jagger_002, mob_jagger_003,
+        location001, location002, location003, location003_training,
+    );
+    register_resources!(rm,
+        missile_explosion_simple, plane_explosion_composite, wreckage_composite, exhaust_trail_plane, exhaust_trail_missile,
+        explosion3_clip, explosion3a_clip, smoke_clip_001, smoke_clip_001_dark, smoke_clip_plane_explosion, cloud_swirling_clip,
+    );
+    register_resources!(rm,
+        sound_missile_001, sound_explosion_001, sound_cannon_001, sound_cannon_002, sound_hit_001, sound_death_001,
+        sound_pick_001, sound_plasma, sound_rail, sound_levelup, sound_skillup,
+        sound_death_robot, sound_death_wasp, sound_death_drone, sound_pain,
+    );
+    register_resources!(rm,
+        explosion_facepalm33_clip, explosion_facepalm34_clip, fire_facepalm11_clip,
+        cloud1_sprite, cloud2_sprite, cloud3_sprite,
+        isle1_sprite, isle2_sprite, isle3_sprite, isle_empy_1_sprite,
+        sprite_resource_a, sprite_resource_b, sprite_resource_c,
+        plane_sprite, bullet_sprite, bullet_sprite_big, missile_sprite, missile_sprite_yellow, missile_sprite_blue, sprite_plasma_001,
+        robot_001_clip_set, wasp_001_clip_set, mob_drone_001_clip_set,
+    );
+    register_resources!(rm,
+        game_001, shop_001, skills_001,
+    );
+}
@@ -1,3 +1,4 @@
+use crate::asset_bytes;
 use crate::common::resource::{ResourceLoad, ResourceLoadAsync};
 use crate::lifecycle::loading;
 use crate::model::data::{GenericSplitSpriteClipData, SplitSpriteClipData};
@@ -11,29 +12,29 @@ pub const explosion_facepalm33_clip: ResourceLoadAsync<SpriteClip> = |rm| loadin
         rate: 30.0,
         sprite_mod: vec![],
         data: vec![
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0001.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0002.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0003.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0004.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0005.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0006.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0007.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0008.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0009.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0010.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0011.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0012.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0013.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0014.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0015.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0016.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0017.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0018.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0019.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0020.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0021.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0022.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0023.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0001.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0002.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0003.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0004.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0005.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0006.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0007.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0008.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0009.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0010.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0011.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0012.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0013.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0014.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0015.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0016.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0017.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0018.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0019.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0020.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0021.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0022.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.3/EXPLOSION_3.3-0023.png"),
         ],
         on_end: Clamp,
     },
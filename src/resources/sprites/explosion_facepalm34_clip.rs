@@ -1,3 +1,4 @@
+use crate::asset_bytes;
 use crate::common::resource::{ResourceLoad, ResourceLoadAsync};
 use crate::lifecycle::loading;
 use crate::model::data::{GenericSplitSpriteClipData, SplitSpriteClipData};
@@ -11,29 +12,29 @@ pub const explosion_facepalm34_clip: ResourceLoadAsync<SpriteClip> = |rm| loadin
         rate: 30.0,
         sprite_mod: vec![],
         data: vec![
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0001.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0002.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0003.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0004.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0005.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0006.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0007.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0008.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0009.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0010.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0011.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0012.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0013.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0014.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0015.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0016.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0017.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0018.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0019.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0020.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0021.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0022.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0023.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0001.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0002.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0003.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0004.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0005.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0006.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0007.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0008.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0009.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0010.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0011.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0012.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0013.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0014.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0015.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0016.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0017.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0018.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0019.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0020.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0021.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0022.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXPLOSIONS_3/EXPLOSION_3.4/EXPLOSION_3.4-0023.png"),
         ],
         on_end: Clamp,
     },
@@ -1,3 +1,4 @@
+use crate::asset_bytes;
 use crate::common::angle::Angle;
 use crate::common::curve::{Curve};
 use crate::common::resource::{ResourceLoad, ResourceLoadAsync};
@@ -18,31 +19,31 @@ pub const fire_facepalm11_clip: ResourceLoadAsync<SpriteClip> = |rm| loading::cl
             OriginNorm(Vec2::new(0.5, 0.98))
         ],
         data: vec![
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0001.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0002.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0003.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0004.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0005.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0006.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0007.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0008.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0009.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0010.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0011.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0012.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0013.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0014.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0015.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0016.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0017.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0018.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0019.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0020.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0021.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0022.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0023.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0024.png"),
-            include_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0025.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0001.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0002.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0003.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0004.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0005.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0006.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0007.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0008.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0009.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0010.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0011.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0012.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0013.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0014.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0015.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0016.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0017.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0018.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0019.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0020.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0021.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0022.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0023.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0024.png"),
+            asset_bytes!("../../../art/FacePalms 2D Explosions and Effects/EXTRAS/FIRE/FIRE_1/FIRE_1.1/FIRE_1.1-0025.png"),
         ],
         on_end: Repeat
     },
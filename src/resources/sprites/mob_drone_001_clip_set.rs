@@ -1,3 +1,4 @@
+use crate::asset_bytes;
 use macroquad::prelude::Vec2;
 use crate::common::resource::{ResourceLoad, ResourceLoadAsync};
 use crate::lifecycle::loading;
@@ -16,16 +17,16 @@ pub const mob_drone_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading
             sprite_mod: vec![],
             rate: 12.0,
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (1).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (2).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (3).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (4).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (5).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (6).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (7).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (8).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (9).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (10).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (1).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (2).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (3).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (4).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (5).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (6).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (7).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (8).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (9).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (10).png"),
             ],
             on_end: Repeat,
         },
@@ -33,16 +34,16 @@ pub const mob_drone_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading
             sprite_mod: vec![],
             rate: 30.0,
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (1).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (2).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (3).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (4).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (5).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (6).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (7).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (8).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (9).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (10).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (1).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (2).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (3).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (4).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (5).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (6).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (7).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (8).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (9).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Walk (10).png"),
             ],
             on_end: Repeat,
         },
@@ -50,16 +51,16 @@ pub const mob_drone_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading
             sprite_mod: vec![],
             rate: 15.0,
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (1).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (2).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (3).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (4).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (5).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (6).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (7).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (8).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (9).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (10).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (1).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (2).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (3).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (4).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (5).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (6).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (7).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (8).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (9).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (10).png"),
             ],
             on_end: Clamp,
         },
@@ -67,7 +68,7 @@ pub const mob_drone_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading
             sprite_mod: vec![],
             rate: 0.0,
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (10).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Dead (10).png"),
             ],
             on_end: Repeat,
         },
@@ -75,7 +76,7 @@ pub const mob_drone_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading
             rate: 30.0,
             sprite_mod: vec![],
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (1).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (1).png"),
             ],
             on_end: Clamp,
         },
@@ -83,7 +84,7 @@ pub const mob_drone_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading
             rate: 30.0,
             sprite_mod: vec![],
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (1).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Drone2/Idle (1).png"),
             ],
             on_end: Clamp,
         },
@@ -1,3 +1,4 @@
+use crate::asset_bytes;
 use macroquad::prelude::Vec2;
 use crate::common::resource::{ResourceLoad, ResourceLoadAsync};
 use crate::lifecycle::loading;
@@ -16,16 +17,16 @@ pub const robot_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading::mo
             sprite_mod: vec![],
             rate: 12.0,
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (1).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (2).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (3).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (4).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (5).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (6).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (7).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (8).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (9).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (10).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (1).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (2).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (3).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (4).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (5).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (6).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (7).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (8).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (9).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Idle (10).png"),
             ],
             on_end: Repeat,
         },
@@ -33,21 +34,21 @@ pub const robot_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading::mo
             sprite_mod: vec![],
             rate: 30.0,
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (1).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (2).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (3).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (4).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (5).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (6).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (7).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (8).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (9).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (10).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (11).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (12).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (13).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (14).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (15).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (1).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (2).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (3).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (4).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (5).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (6).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (7).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (8).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (9).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (10).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (11).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (12).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (13).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (14).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Walk (15).png"),
             ],
             on_end: Repeat,
         },
@@ -55,14 +56,14 @@ pub const robot_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading::mo
             sprite_mod: vec![],
             rate: 15.0,
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (1).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (2).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (3).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (4).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (5).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (6).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (7).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (8).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (1).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (2).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (3).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (4).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (5).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (6).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (7).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (8).png"),
             ],
             on_end: Clamp,
         },
@@ -70,7 +71,7 @@ pub const robot_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading::mo
             sprite_mod: vec![],
             rate: 0.0,
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (8).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Dead (8).png"),
             ],
             on_end: Repeat,
         },
@@ -78,10 +79,10 @@ pub const robot_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading::mo
             rate: 30.0,
             sprite_mod: vec![],
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (1).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (2).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (3).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (4).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (1).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (2).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (3).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (4).png"),
             ],
             on_end: Clamp,
         },
@@ -89,7 +90,7 @@ pub const robot_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading::mo
             rate: 30.0,
             sprite_mod: vec![],
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (5).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (5).png"),
             ],
             on_end: Clamp,
         },
@@ -97,9 +98,9 @@ pub const robot_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading::mo
             rate: 30.0,
             sprite_mod: vec![],
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (6).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (7).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (8).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (6).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (7).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (8).png"),
             ],
             on_end: Clamp,
         },
@@ -107,7 +108,7 @@ pub const robot_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading::mo
             rate: 30.0,
             sprite_mod: vec![],
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (7).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (7).png"),
             ],
             on_end: Repeat,
         },
@@ -115,13 +116,13 @@ pub const robot_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading::mo
             rate: 30.0,
             sprite_mod: vec![],
             data: vec![
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (9).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (10).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (11).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (12).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (13).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (14).png"),
-                include_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (15).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (9).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (10).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (11).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (12).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (13).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (14).png"),
+                asset_bytes!("../../../art/pzuh/Steampunk/Robot/Attack (15).png"),
             ],
             on_end: Clamp,
         },
@@ -1,5 +1,6 @@
 #![allow(non_upper_case_globals)]
 
+use crate::asset_bytes;
 use std::collections::HashMap;
 use std::rc::Rc;
 use futures::{FutureExt, StreamExt, TryFutureExt};
@@ -18,38 +19,41 @@ use crate::model::def::SpriteClip;
 use crate::{ResourceManager, Vec2};
 
 pub const plane_sprite: ResourceLoadAsync<Sprite> = |rm| {
-    sprite::load_sprite(include_bytes!("../../../art/free_plane/Plane/Fly (1).png"), vec![Scale(0.12)]).boxed_local()
+    sprite::load_sprite(rm, asset_bytes!("../../../art/free_plane/Plane/Fly (1).png"), vec![Scale(0.12)]).boxed_local()
 };
-const bullet_sprite_bytes: &[u8] = include_bytes!("../../../art/my/bullet_001.png");
+fn bullet_sprite_bytes() -> &'static [u8] { asset_bytes!("../../../art/my/bullet_001.png") }
 
 pub const bullet_sprite: ResourceLoadAsync<Sprite> = |rm| {
-    sprite::load_sprite(bullet_sprite_bytes, vec![Scale(0.1)]).boxed_local()
+    sprite::load_sprite(rm, bullet_sprite_bytes(), vec![Scale(0.1)]).boxed_local()
 };
 
 pub const bullet_sprite_big: ResourceLoadAsync<Sprite> = |rm| {
-    sprite::load_sprite(bullet_sprite_bytes, vec![Scale(0.15)]).boxed_local()
+    sprite::load_sprite(rm, bullet_sprite_bytes(), vec![Scale(0.15)]).boxed_local()
 };
 
 pub const missile_sprite: ResourceLoadAsync<Sprite> = |rm| sprite::load_sprite(
-    include_bytes!("../../../art/my/missile_001.png"),
+    rm,
+    asset_bytes!("../../../art/my/missile_001.png"),
     vec![
         Scale(0.08),
         OriginNorm(Vec2::new(0.1, 0.5)),
     ]).boxed_local();
 
 pub const missile_sprite_yellow: ResourceLoadAsync<Sprite> = |rm| sprite::load_sprite(
-    include_bytes!("../../../art/my/missile_001_yellow.png"),
+    rm,
+    asset_bytes!("../../../art/my/missile_001_yellow.png"),
     vec![
         Scale(0.08),
         OriginNorm(Vec2::new(0.1, 0.5)),
     ]).boxed_local();
 
 pub const missile_sprite_blue: ResourceLoadAsync<Sprite> = |rm| sprite::load_sprite(
-    include_bytes!("../../../art/my/missile_001_blue.png"),
+    rm,
+    asset_bytes!("../../../art/my/missile_001_blue.png"),
     vec![
         Scale(0.08),
         OriginNorm(Vec2::new(0.1, 0.5)),
     ]).boxed_local();
 
 pub const sprite_plasma_001: ResourceLoadAsync<Sprite> = |rm|
-    sprite::load_sprite(include_bytes!("../../../art/my/plasma_001.png"), vec![Scale(0.12)]).boxed_local();
+    sprite::load_sprite(rm, asset_bytes!("../../../art/my/plasma_001.png"), vec![Scale(0.12)]).boxed_local();
@@ -1,3 +1,4 @@
+use crate::asset_bytes;
 use macroquad::prelude::Vec2;
 use crate::common::resource::{ResourceLoad, ResourceLoadAsync};
 use crate::common::sprite::SheetRegion;
@@ -7,9 +8,9 @@ use crate::model::data::SpriteMod::{OriginNorm, Scale};
 use crate::model::def::{CollisionCircle, CollisionCircleNorm, MobAnimation, MobSpriteSet};
 use crate::model::def::OnClipEnd::{Clamp, Repeat};
 
-fn wasp_flying_bytes() -> &'static [u8] { include_bytes!("../../../art/gamedeveloperstudio/__wasp-flying_fly_776px_by_867px_per_frame.png") }
-fn wasp_sting_bytes() ->  &'static [u8] { include_bytes!("../../../art/gamedeveloperstudio/__wasp-flying_sting_776px_by_867px_per_frame.png") }
-fn wasp_corpse_bytes() -> &'static [u8] { include_bytes!("../../../art/gamedeveloperstudio/__wasp-flying_corpse.png") }
+fn wasp_flying_bytes() -> &'static [u8] { asset_bytes!("../../../art/gamedeveloperstudio/__wasp-flying_fly_776px_by_867px_per_frame.png") }
+fn wasp_sting_bytes() ->  &'static [u8] { asset_bytes!("../../../art/gamedeveloperstudio/__wasp-flying_sting_776px_by_867px_per_frame.png") }
+fn wasp_corpse_bytes() -> &'static [u8] { asset_bytes!("../../../art/gamedeveloperstudio/__wasp-flying_corpse.png") }
 
 pub const wasp_001_clip_set: ResourceLoadAsync<MobSpriteSet> = |rm| loading::mob_clips(rm, MobSpriteSetData {
     sprite_mod: vec![